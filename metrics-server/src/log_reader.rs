@@ -63,10 +63,10 @@ impl LogReader {
         let current_inode = meta.ino();
 
         // Detect rotation: if inode changed, drop state so we re-open
-        if let Some(state) = self.states.get(app) {
-            if state.inode != current_inode {
-                self.states.remove(app);
-            }
+        if let Some(state) = self.states.get(app)
+            && state.inode != current_inode
+        {
+            self.states.remove(app);
         }
 
         // Open file if no state yet
@@ -85,7 +85,7 @@ async fn main() -> anyhow::Result<()> {
         .route("/metrics", get(get_metrics))
         .with_state(state);
 
-    let addr = format!("127.0.0.1:{port}");
+    let addr = format!("127.0.0.1:{}", config.port);
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     eprintln!("metrics-server listening on {addr}");
     axum::serve(listener, app).await?;
@@ -1,9 +1,12 @@
 use crate::checker::Status;
 use crate::config::Config;
-use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum StatusTransition {
     UpToDown,
     UpToWarn,
@@ -11,6 +14,8 @@ pub enum StatusTransition {
     WarnToDown,
     WarnToUp,
     DownToWarn,
+    /// No result came in for two intervals, so the status shown is old
+    Stale,
 }
 
 impl StatusTransition {
@@ -28,34 +33,26 @@ impl StatusTransition {
 }
 
 /// Tracks state needed for alert detection decisions
+#[derive(Default)]
 struct SiteAlertState {
     consecutive_failures: usize,
-    last_alert_time: Option<DateTime<Utc>>,
+    last_alert_time: Option<Instant>, // Monotonic, so wall-clock jumps can't skew cooldowns
     last_alert_status: Option<Status>,
     stable_status: Option<Status>, // Status before current failure streak began
+    suppressed: bool,              // Failures this streak were hidden behind a down dependency
 }
 
 pub struct AlertDetector {
-    config: Config,
+    config: Arc<Config>,
     site_states: HashMap<String, SiteAlertState>,
 }
 
 impl AlertDetector {
-    pub fn new(config: Config) -> Self {
+    pub fn new(config: Arc<Config>) -> Self {
         let site_states = config
             .sites
             .iter()
-            .map(|site| {
-                (
-                    site.name.clone(),
-                    SiteAlertState {
-                        consecutive_failures: 0,
-                        last_alert_time: None,
-                        last_alert_status: None,
-                        stable_status: None,
-                    },
-                )
-            })
+            .map(|site| (site.name.clone(), SiteAlertState::default()))
             .collect();
 
         Self {
@@ -64,27 +61,74 @@ impl AlertDetector {
         }
     }
 
-    /// Evaluate whether a status change should trigger an alert
+    /// Switch to a config whose sites changed at runtime, starting fresh
+    /// state for new sites and forgetting removed ones
+    pub fn set_config(&mut self, config: Arc<Config>) {
+        self.site_states.retain(|name, _| config.site(name).is_some());
+        for site in &config.sites {
+            self.site_states.entry(site.name.clone()).or_default();
+        }
+        self.config = config;
+    }
+
+    /// Current failure streak and the streak length that triggers an alert,
+    /// while the site is failing and hasn't reached it yet
+    pub fn failure_progress(&self, site_name: &str) -> Option<(usize, usize)> {
+        let state = self.site_states.get(site_name)?;
+        let threshold = self.threshold(site_name);
+        (state.consecutive_failures > 0 && state.consecutive_failures < threshold)
+            .then_some((state.consecutive_failures, threshold))
+    }
+
+    /// Consecutive failures required before alerting, with site overrides applied
+    fn threshold(&self, site_name: &str) -> usize {
+        self.config
+            .site(site_name)
+            .and_then(|s| s.alerts.as_ref())
+            .and_then(|a| a.consecutive_failures)
+            .unwrap_or(self.config.settings.alerts.consecutive_failures)
+    }
+
+    /// Whether alerts are on for the site, with its override applied
+    fn enabled(&self, site_name: &str) -> bool {
+        let global = self.config.settings.alerts.enabled;
+        self.config
+            .site(site_name)
+            .and_then(|s| s.alerts.as_ref())
+            .and_then(|a| a.enabled)
+            .unwrap_or(global)
+    }
+
+    /// Whether a site whose results stopped coming in should alert
+    pub fn alerts_on_stale(&self, site_name: &str) -> bool {
+        self.enabled(site_name) && self.config.settings.alerts.transitions.stale
+    }
+
+    /// Evaluate whether a status change should trigger an alert. `blocked`
+    /// means a dependency is Down, so failures are expected and not alerted.
     pub fn evaluate(
         &mut self,
         site_name: &str,
         previous_status: Option<&Status>,
         current_status: &Status,
+        blocked: bool,
     ) -> Option<StatusTransition> {
+        // Nothing was checked (or the site turned the check away), so there
+        // is nothing to alert on and the failure streak stands
+        if !current_status.is_conclusive() {
+            return None;
+        }
+
         // Get alert settings for this site (merging global and site-specific)
-        let site_config = self.config.sites.iter().find(|s| s.name == site_name)?;
+        let site_config = self.config.site(site_name)?;
         let global_alerts = &self.config.settings.alerts;
         let site_alerts = &site_config.alerts;
 
-        // Check if alerts are enabled
-        let enabled = site_alerts
-            .as_ref()
-            .and_then(|a| a.enabled)
-            .unwrap_or(global_alerts.enabled);
-        if !enabled {
+        if !self.enabled(site_name) {
             return None;
         }
 
+        let threshold = self.threshold(site_name);
         let state = self.site_states.get_mut(site_name)?;
 
         // Update stable status and consecutive failure count
@@ -100,11 +144,14 @@ impl AlertDetector {
             state.stable_status = Some(current_status.clone());
         }
 
-        // Check consecutive failure threshold
-        let threshold = site_alerts
-            .as_ref()
-            .and_then(|a| a.consecutive_failures)
-            .unwrap_or(global_alerts.consecutive_failures);
+        // Stay quiet while blocked, and about the recovery from a failure nobody was told about
+        if *current_status != Status::Up && blocked {
+            state.suppressed = true;
+            return None;
+        }
+        if *current_status == Status::Up && std::mem::take(&mut state.suppressed) {
+            return None;
+        }
 
         // For non-UP statuses, wait for threshold before alerting
         if (*current_status == Status::Down || *current_status == Status::Warning)
@@ -119,16 +166,12 @@ impl AlertDetector {
             .and_then(|a| a.cooldown_seconds)
             .unwrap_or(global_alerts.cooldown_seconds);
 
-        if let Some(last_time) = state.last_alert_time {
-            let elapsed = Utc::now().signed_duration_since(last_time);
-            if elapsed < Duration::seconds(cooldown_seconds as i64) {
-                // During cooldown, only alert if status changed
-                if let Some(last_status) = &state.last_alert_status {
-                    if last_status == current_status {
-                        return None;
-                    }
-                }
-            }
+        if let Some(last_time) = state.last_alert_time
+            && last_time.elapsed() < Duration::from_secs(cooldown_seconds)
+            // During cooldown, only alert if status changed
+            && state.last_alert_status.as_ref() == Some(current_status)
+        {
+            return None;
         }
 
         // Determine if this status transition should alert
@@ -150,11 +193,14 @@ impl AlertDetector {
             StatusTransition::WarnToDown => global_alerts.transitions.warn_to_down,
             StatusTransition::WarnToUp => global_alerts.transitions.warn_to_up,
             StatusTransition::DownToWarn => global_alerts.transitions.down_to_warn,
+            StatusTransition::Stale => global_alerts.transitions.stale,
         };
 
         if should_alert {
-            state.last_alert_time = Some(Utc::now());
+            state.last_alert_time = Some(Instant::now());
             state.last_alert_status = Some(current_status.clone());
+            // This outage was reported after all, so its recovery is too
+            state.suppressed = false;
             Some(transition)
         } else {
             None
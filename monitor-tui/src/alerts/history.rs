@@ -1,13 +1,17 @@
 use crate::alerts::StatusTransition;
-use crate::checker::Status;
-use chrono::{DateTime, Utc};
-use std::collections::VecDeque;
+use crate::checker::{CheckResult, Status};
+use crate::i18n;
+use chrono::{DateTime, Local, NaiveDate, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum AlertSeverity {
     Critical, // Site went down
     Warning,  // Site has warning status
     Recovery, // Site recovered
+    Info,     // Worth recording, not acting on (only by config)
 }
 
 impl AlertSeverity {
@@ -16,7 +20,28 @@ impl AlertSeverity {
             StatusTransition::UpToDown | StatusTransition::WarnToDown => Self::Critical,
             StatusTransition::UpToWarn => Self::Warning,
             StatusTransition::DownToUp | StatusTransition::WarnToUp => Self::Recovery,
-            StatusTransition::DownToWarn => Self::Warning,
+            StatusTransition::DownToWarn | StatusTransition::Stale => Self::Warning,
+        }
+    }
+
+    /// Severity by its config name, e.g. "warning"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "critical" => Some(Self::Critical),
+            "warning" => Some(Self::Warning),
+            "recovery" => Some(Self::Recovery),
+            "info" => Some(Self::Info),
+            _ => None,
+        }
+    }
+
+    /// Name of the severity in plain text
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Critical => "CRITICAL",
+            Self::Warning => "WARNING",
+            Self::Recovery => "RECOVERY",
+            Self::Info => "INFO",
         }
     }
 }
@@ -25,22 +50,39 @@ impl AlertSeverity {
 pub struct Alert {
     pub timestamp: DateTime<Utc>,
     pub site_name: String,
-    #[allow(dead_code)]
     pub transition: StatusTransition,
     pub severity: AlertSeverity,
     pub current_status: Status,
     pub previous_status: Status,
     pub message: String,
+    /// The check that raised the alert, for its latency, status code,
+    /// error and body
+    pub check: Option<CheckResult>,
+    /// Broad cause of the failing check, e.g. "Timeout"
+    pub failure_kind: Option<&'static str>,
+    /// Findings of the diagnostics run when the site went Down
+    pub diagnostics: Vec<String>,
+    /// The site's description, owner and runbook, from its config
+    pub notes: Vec<String>,
+    /// Note added from the alert detail view, e.g. "known issue, ISP maintenance"
+    pub comment: Option<String>,
+    /// Matched a silence, so it was recorded without notifying anyone
+    pub silenced: bool,
+    /// Marked as handled from the alerts view
+    pub acknowledged: bool,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    /// Sent again once diagnostics findings came in after the first notification
+    pub follow_up: bool,
 }
 
 impl Alert {
     pub fn new(
         site_name: String,
         transition: StatusTransition,
+        severity: AlertSeverity,
         previous_status: Status,
         current_status: Status,
     ) -> Self {
-        let severity = AlertSeverity::from_transition(&transition);
         let message = Self::format_message(&site_name, &transition);
 
         Self {
@@ -51,31 +93,97 @@ impl Alert {
             current_status,
             previous_status,
             message,
+            check: None,
+            failure_kind: None,
+            diagnostics: Vec::new(),
+            notes: Vec::new(),
+            comment: None,
+            silenced: false,
+            acknowledged: false,
+            acknowledged_at: None,
+            follow_up: false,
         }
     }
 
-    fn format_message(site_name: &str, transition: &StatusTransition) -> String {
-        match transition {
-            StatusTransition::UpToDown => {
-                format!("{} is DOWN", site_name)
-            }
-            StatusTransition::UpToWarn => {
-                format!("{} has WARNING status", site_name)
-            }
-            StatusTransition::DownToUp => {
-                format!("{} has RECOVERED", site_name)
-            }
-            StatusTransition::WarnToDown => {
-                format!("{} went from WARNING to DOWN", site_name)
-            }
-            StatusTransition::WarnToUp => {
-                format!("{} recovered from WARNING", site_name)
-            }
-            StatusTransition::DownToWarn => {
-                format!("{} went from DOWN to WARNING", site_name)
-            }
+    /// The notification to send again now that its diagnostics are in
+    pub fn diagnosed_follow_up(&self) -> Self {
+        Self {
+            message: i18n::tf("alert.diagnosed", &[("site", &self.site_name)]),
+            follow_up: true,
+            ..self.clone()
         }
     }
+
+    /// Error of the check that raised the alert, e.g. "Connection refused"
+    pub fn error(&self) -> Option<&str> {
+        self.check.as_ref()?.error_message.as_deref()
+    }
+
+    /// What the check that raised the alert saw, e.g. "Unexpected status, HTTP 503, 120ms"
+    pub fn check_summary(&self) -> Option<String> {
+        let check = self.check.as_ref()?;
+        let parts: Vec<String> = self
+            .failure_kind
+            .map(str::to_string)
+            .into_iter()
+            .chain(check.http_status.map(|code| format!("HTTP {}", code)))
+            .chain(check.response_time_ms.map(|ms| format!("{}ms", ms)))
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// What the check that raised the alert saw, a line each: the summary,
+    /// the error and the start of the body
+    pub fn check_context(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self.check_summary().map(|s| format!("Check: {}", s)).into_iter().collect();
+        if let Some(error) = self.error() {
+            lines.push(format!("Error: {}", error));
+        }
+        if let Some(snippet) = self.check.as_ref().and_then(|c| c.body_snippet.as_deref()) {
+            lines.push(format!("Body: {}", snippet));
+        }
+        lines
+    }
+
+    fn format_message(site_name: &str, transition: &StatusTransition) -> String {
+        let key = match transition {
+            StatusTransition::UpToDown => "alert.down",
+            StatusTransition::UpToWarn => "alert.warning",
+            StatusTransition::DownToUp => "alert.recovered",
+            StatusTransition::WarnToDown => "alert.warning_to_down",
+            StatusTransition::WarnToUp => "alert.recovered_from_warning",
+            StatusTransition::DownToWarn => "alert.down_to_warning",
+            StatusTransition::Stale => "alert.stale",
+        };
+        i18n::tf(key, &[("site", site_name)])
+    }
+}
+
+/// Alerts of one site, for the statistics view
+#[derive(Debug, Clone)]
+pub struct SiteAlertStats {
+    pub site_name: String,
+    pub total: usize,
+    /// Times the site went Down
+    pub failures: usize,
+    pub warnings: usize,
+    /// Mean time between failures, from the first to the last of them
+    pub mtbf: Option<TimeDelta>,
+}
+
+/// Aggregates over the alert history, to spot chronic offenders
+#[derive(Debug, Clone)]
+pub struct AlertStats {
+    /// Sites with alerts, most alerts first
+    pub sites: Vec<SiteAlertStats>,
+    /// Alerts on each of the last days (local time), oldest first
+    pub per_day: Vec<(NaiveDate, u64)>,
+    /// Mean time between failures across sites that failed more than once
+    pub mtbf: Option<TimeDelta>,
+    /// Errors that raised alerts, most frequent first
+    pub top_errors: Vec<(String, usize)>,
+    /// When the oldest kept alert fired
+    pub since: Option<DateTime<Utc>>,
 }
 
 /// History of alerts with ring buffer storage
@@ -116,6 +224,144 @@ impl AlertHistory {
         self.alerts.is_empty()
     }
 
+    /// Set or clear the comment on the alert that fired at `timestamp` for `site_name`.
+    /// Returns false if it has since dropped out of the history.
+    pub fn set_comment(&mut self, timestamp: DateTime<Utc>, site_name: &str, comment: Option<String>) -> bool {
+        match self
+            .alerts
+            .iter_mut()
+            .find(|a| a.timestamp == timestamp && a.site_name == site_name)
+        {
+            Some(alert) => {
+                alert.comment = comment;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Acknowledge the alert that fired at `timestamp` for `site_name`, or
+    /// take the acknowledgement back. Returns false if it has since dropped
+    /// out of the history.
+    pub fn set_acknowledged(&mut self, timestamp: DateTime<Utc>, site_name: &str, acknowledged: bool) -> bool {
+        match self
+            .alerts
+            .iter_mut()
+            .find(|a| a.timestamp == timestamp && a.site_name == site_name)
+        {
+            Some(alert) => {
+                alert.acknowledged = acknowledged;
+                alert.acknowledged_at = acknowledged.then(Utc::now);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Give diagnostics findings to the alerts `site_name`'s check at
+    /// `checked_at` raised before they came in. Returns the latest of them,
+    /// whose notification went out without the findings.
+    pub fn attach_diagnostics(&mut self, site_name: &str, checked_at: DateTime<Utc>, findings: &[String]) -> Option<Alert> {
+        let mut diagnosed = None;
+        for alert in self.alerts.iter_mut().rev().filter(|a| a.site_name == site_name) {
+            if alert.check.as_ref().is_some_and(|check| check.timestamp == checked_at) && alert.diagnostics.is_empty() {
+                alert.diagnostics = findings.to_vec();
+                diagnosed.get_or_insert_with(|| alert.clone());
+            }
+        }
+        diagnosed
+    }
+
+    /// How many alerts are acknowledged
+    pub fn acknowledged_count(&self) -> usize {
+        self.alerts.iter().filter(|a| a.acknowledged).count()
+    }
+
+    /// Forget every alert
+    pub fn clear(&mut self) {
+        self.alerts.clear();
+    }
+
+    /// Forget the recovery alerts, keeping those about problems; returns how many went
+    pub fn clear_recoveries(&mut self) -> usize {
+        let before = self.alerts.len();
+        self.alerts.retain(|a| a.severity != AlertSeverity::Recovery);
+        before - self.alerts.len()
+    }
+
+    /// Aggregates over the kept alerts, with per-day counts for the last `days` days
+    pub fn stats(&self, days: u32) -> AlertStats {
+        let mut by_site: HashMap<&str, (SiteAlertStats, Vec<DateTime<Utc>>)> = HashMap::new();
+        let mut errors: HashMap<&str, usize> = HashMap::new();
+        for alert in &self.alerts {
+            let (stats, failures) = by_site.entry(&alert.site_name).or_insert_with(|| {
+                let stats = SiteAlertStats {
+                    site_name: alert.site_name.clone(),
+                    total: 0,
+                    failures: 0,
+                    warnings: 0,
+                    mtbf: None,
+                };
+                (stats, Vec::new())
+            });
+            stats.total += 1;
+            // A failure is going Down, whatever severity the config gave it
+            if alert.current_status == Status::Down {
+                stats.failures += 1;
+                failures.push(alert.timestamp);
+            } else if alert.current_status == Status::Warning {
+                stats.warnings += 1;
+            }
+            if let Some(error) = alert.error() {
+                *errors.entry(error).or_default() += 1;
+            }
+        }
+
+        // Fleet MTBF weighs every gap between failures equally
+        let mut gaps = TimeDelta::zero();
+        let mut gap_count = 0;
+        let mut sites: Vec<SiteAlertStats> = by_site
+            .into_values()
+            .map(|(mut stats, failures)| {
+                if let (Some(first), Some(last)) = (failures.first(), failures.last())
+                    && failures.len() > 1
+                {
+                    let span = *last - *first;
+                    stats.mtbf = Some(span / (failures.len() as i32 - 1));
+                    gaps += span;
+                    gap_count += failures.len() as i32 - 1;
+                }
+                stats
+            })
+            .collect();
+        sites.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.site_name.cmp(&b.site_name)));
+
+        let today = Local::now().date_naive();
+        let mut per_day: Vec<(NaiveDate, u64)> = (0..days)
+            .rev()
+            .filter_map(|ago| today.checked_sub_days(chrono::Days::new(ago as u64)))
+            .map(|day| (day, 0))
+            .collect();
+        for alert in &self.alerts {
+            let day = alert.timestamp.with_timezone(&Local).date_naive();
+            if let Some((_, count)) = per_day.iter_mut().find(|(d, _)| *d == day) {
+                *count += 1;
+            }
+        }
+
+        let mut top_errors: Vec<(String, usize)> =
+            errors.into_iter().map(|(error, count)| (error.to_string(), count)).collect();
+        top_errors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        AlertStats {
+            sites,
+            per_day,
+            mtbf: (gap_count > 0).then(|| gaps / gap_count),
+            top_errors,
+            since: self.alerts.front().map(|a| a.timestamp),
+        }
+    }
+
     /// Get alerts filtered by severity
     pub fn by_severity(&self, severity: AlertSeverity) -> Vec<&Alert> {
         self.alerts
@@ -1,7 +1,9 @@
 pub mod detector;
 pub mod history;
 pub mod notifier;
+pub mod silence;
 
 pub use detector::{AlertDetector, StatusTransition};
-pub use history::{Alert, AlertHistory, AlertSeverity};
-pub use notifier::AlertNotifier;
+pub use history::{Alert, AlertHistory, AlertSeverity, AlertStats};
+pub use notifier::{AlertNotifier, NotificationStats};
+pub use silence::Silence;
@@ -1,20 +1,155 @@
 use crate::alerts::{Alert, AlertSeverity};
 use crate::config::Config;
+use crate::i18n;
+use crate::checker::http::DEFAULT_USER_AGENT;
+use anyhow::Result;
 use notify_rust::{Notification, Urgency};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::future::Future;
 use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Alerts that can wait for each notification method before new ones are dropped
+const NOTIFICATION_QUEUE_SIZE: usize = 32;
+
+/// Characters of detail kept in a chat message; Discord caps an embed's
+/// description at 4096
+const CHAT_DETAIL_CHARS: usize = 3500;
+
+/// Chat services alerts are posted to as formatted messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Chat {
+    Slack,
+    Discord,
+}
+
+impl Chat {
+    fn name(self) -> &'static str {
+        match self {
+            Chat::Slack => "slack",
+            Chat::Discord => "discord",
+        }
+    }
+}
+
+/// Outcome counters for notifications, shown in the status bar
+#[derive(Debug, Default)]
+pub struct NotificationStats {
+    /// Alerts discarded because a method's queue was full
+    pub dropped: AtomicU64,
+    /// Sends that returned an error or timed out
+    pub failed: AtomicU64,
+    /// Most recent failure, for display
+    pub last_error: Mutex<Option<String>>,
+}
+
+impl NotificationStats {
+    pub fn record_failure(&self, channel: &str, error: String) {
+        self.failed.fetch_add(1, Ordering::Relaxed);
+        *self.last_error.lock().unwrap() = Some(format!("{}: {}", channel, error));
+    }
+}
 
 #[derive(Clone)]
 pub struct AlertNotifier {
-    config: Config,
+    config: Arc<Config>,
+    stats: Arc<NotificationStats>,
+    desktop: mpsc::Sender<Alert>,
+    /// One queue per webhook URL, so a slow endpoint doesn't hold up the others
+    webhooks: Vec<mpsc::Sender<Alert>>,
+    /// One queue per Slack or Discord webhook, global or a site's own
+    chats: HashMap<(Chat, String), mpsc::Sender<Alert>>,
+    client: reqwest::Client,
 }
 
 impl AlertNotifier {
-    pub fn new(config: Config) -> Self {
-        Self { config }
+    pub fn new(config: Arc<Config>, stats: Arc<NotificationStats>) -> Self {
+        let timeout = Duration::from_secs(config.settings.alerts.notification_timeout_seconds);
+
+        // notify-rust blocks on D-Bus; allow one call in flight so a hung
+        // notification daemon can't pile up blocking threads
+        let desktop_busy = Arc::new(AtomicBool::new(false));
+        let desktop = spawn_channel("desktop", Arc::clone(&stats), timeout, move |alert| {
+            let busy = Arc::clone(&desktop_busy);
+            async move {
+                if busy.swap(true, Ordering::AcqRel) {
+                    anyhow::bail!("previous notification still pending");
+                }
+                let handle = tokio::task::spawn_blocking(move || {
+                    let result = send_desktop_notification(&alert);
+                    busy.store(false, Ordering::Release);
+                    result
+                });
+                handle.await?
+            }
+        });
+
+        let client = reqwest::Client::builder().user_agent(DEFAULT_USER_AGENT).build().unwrap_or_default();
+        let webhooks = config
+            .settings
+            .alerts
+            .webhooks
+            .iter()
+            .map(|url| {
+                let client = client.clone();
+                let url = url.clone();
+                spawn_channel("webhook", Arc::clone(&stats), timeout, move |alert| {
+                    send_webhook(client.clone(), url.clone(), alert)
+                })
+            })
+            .collect();
+
+        let mut notifier = Self { config, stats, desktop, webhooks, chats: HashMap::new(), client };
+        notifier.spawn_chats();
+        notifier
+    }
+
+    /// Use a config whose sites changed at runtime
+    pub fn set_config(&mut self, config: Arc<Config>) {
+        self.config = config;
+        self.spawn_chats();
+    }
+
+    /// Start a queue for each Slack and Discord webhook in the config that doesn't have one
+    fn spawn_chats(&mut self) {
+        let global = &self.config.settings.alerts;
+        let sites = self.config.sites.iter().filter_map(|site| site.alerts.as_ref());
+        let mut wanted: Vec<(Chat, &String)> = Vec::new();
+        wanted.extend(global.slack_webhooks.iter().map(|url| (Chat::Slack, url)));
+        wanted.extend(global.discord_webhooks.iter().map(|url| (Chat::Discord, url)));
+        for alerts in sites {
+            wanted.extend(alerts.slack_webhooks.iter().flatten().map(|url| (Chat::Slack, url)));
+            wanted.extend(alerts.discord_webhooks.iter().flatten().map(|url| (Chat::Discord, url)));
+        }
+
+        let timeout = Duration::from_secs(global.notification_timeout_seconds);
+        for (chat, url) in wanted {
+            let key = (chat, url.clone());
+            if self.chats.contains_key(&key) {
+                continue;
+            }
+            let client = self.client.clone();
+            let url = url.clone();
+            let host = endpoint_host(&url);
+            let channel = spawn_channel(chat.name(), Arc::clone(&self.stats), timeout, move |alert| {
+                let payload = match chat {
+                    Chat::Slack => slack_message(&alert),
+                    Chat::Discord => discord_message(&alert),
+                };
+                let (client, url, host) = (client.clone(), url.clone(), host.clone());
+                async move { post_json(client, &url, &host, payload).await }
+            });
+            self.chats.insert(key, channel);
+        }
     }
 
-    pub async fn notify(&self, alert: &Alert) {
-        let site_config = self.config.sites.iter().find(|s| s.name == alert.site_name);
+    /// Deliver an alert through each enabled method without waiting on any of them
+    pub fn notify(&self, alert: &Alert) {
+        let site_config = self.config.site(&alert.site_name);
         let global_alerts = &self.config.settings.alerts;
         let site_alerts = site_config.and_then(|s| s.alerts.as_ref());
 
@@ -27,14 +162,36 @@ impl AlertNotifier {
             .and_then(|a| a.desktop_notifications)
             .unwrap_or(global_alerts.desktop_notifications);
 
-        // Send terminal bell
-        if terminal_bell {
+        // Send terminal bell, once per alert
+        if terminal_bell && !alert.follow_up {
             self.send_terminal_bell();
         }
 
-        // Send desktop notification
+        // Queue desktop notification
         if desktop_notifications {
-            self.send_desktop_notification(alert);
+            self.enqueue(&self.desktop, alert);
+        }
+
+        if site_alerts.and_then(|a| a.webhooks).unwrap_or(true) {
+            for webhook in &self.webhooks {
+                self.enqueue(webhook, alert);
+            }
+        }
+
+        // A site's own chat webhooks replace the global ones
+        let slack = site_alerts.and_then(|a| a.slack_webhooks.as_ref()).unwrap_or(&global_alerts.slack_webhooks);
+        let discord = site_alerts.and_then(|a| a.discord_webhooks.as_ref()).unwrap_or(&global_alerts.discord_webhooks);
+        let chats = slack.iter().map(|url| (Chat::Slack, url)).chain(discord.iter().map(|url| (Chat::Discord, url)));
+        for (chat, url) in chats {
+            if let Some(channel) = self.chats.get(&(chat, url.clone())) {
+                self.enqueue(channel, alert);
+            }
+        }
+    }
+
+    fn enqueue(&self, channel: &mpsc::Sender<Alert>, alert: &Alert) {
+        if channel.try_send(alert.clone()).is_err() {
+            self.stats.dropped.fetch_add(1, Ordering::Relaxed);
         }
     }
 
@@ -43,26 +200,164 @@ impl AlertNotifier {
         print!("\x07");
         let _ = io::stdout().flush();
     }
+}
 
-    fn send_desktop_notification(&self, alert: &Alert) {
-        // Determine urgency based on severity
-        let urgency = match alert.severity {
-            AlertSeverity::Critical => Urgency::Critical,
-            AlertSeverity::Warning => Urgency::Normal,
-            AlertSeverity::Recovery => Urgency::Low,
-        };
-
-        // Build notification
-        let result = Notification::new()
-            .summary("Monitor TUI Alert")
-            .body(&alert.message)
-            .urgency(urgency)
-            .timeout(10000) // 10 seconds
-            .show();
-
-        // Log error if notification fails (don't crash)
-        if let Err(e) = result {
-            eprintln!("Failed to send desktop notification: {}", e);
+/// Start a worker that sends queued alerts one at a time, giving up on any
+/// send that takes longer than `timeout`
+fn spawn_channel<F, Fut>(
+    name: &'static str,
+    stats: Arc<NotificationStats>,
+    timeout: Duration,
+    send: F,
+) -> mpsc::Sender<Alert>
+where
+    F: Fn(Alert) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<()>> + Send,
+{
+    let (tx, mut rx) = mpsc::channel::<Alert>(NOTIFICATION_QUEUE_SIZE);
+    tokio::spawn(async move {
+        while let Some(alert) = rx.recv().await {
+            match tokio::time::timeout(timeout, send(alert)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => stats.record_failure(name, e.to_string()),
+                Err(_) => stats.record_failure(name, format!("timed out after {}s", timeout.as_secs())),
+            }
         }
+    });
+    tx
+}
+
+fn send_desktop_notification(alert: &Alert) -> Result<()> {
+    // Determine urgency based on severity
+    let urgency = match alert.severity {
+        AlertSeverity::Critical => Urgency::Critical,
+        AlertSeverity::Warning => Urgency::Normal,
+        AlertSeverity::Recovery | AlertSeverity::Info => Urgency::Low,
+    };
+
+    let mut body = alert.message.clone();
+    for line in details(alert) {
+        body.push('\n');
+        body.push_str(&line);
+    }
+
+    Notification::new()
+        .summary(i18n::t("alert.notification_title"))
+        .body(&body)
+        .urgency(urgency)
+        .timeout(10000) // 10 seconds
+        .show()?;
+
+    Ok(())
+}
+
+/// What the check saw says why, diagnostics where it breaks, then who owns
+/// the site and where its runbook is, a line each
+fn details(alert: &Alert) -> Vec<String> {
+    let mut lines = alert.check_context();
+    lines.extend(alert.diagnostics.iter().cloned());
+    lines.extend(alert.notes.iter().cloned());
+    lines
+}
+
+/// Details for a chat message, cut short to fit
+fn chat_details(alert: &Alert) -> String {
+    let text = details(alert).join("\n");
+    match text.char_indices().nth(CHAT_DETAIL_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
+
+/// Color of the bar beside a chat message: red, amber, green or blue
+fn severity_color(severity: &AlertSeverity) -> u32 {
+    match severity {
+        AlertSeverity::Critical => 0xD32F2F,
+        AlertSeverity::Warning => 0xF9A825,
+        AlertSeverity::Recovery => 0x2E7D32,
+        AlertSeverity::Info => 0x1976D2,
+    }
+}
+
+/// Slack treats &, < and > as markup, e.g. <!channel>
+fn slack_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Incoming webhook message: the alert as the title of an attachment
+/// colored by severity, with the details under it
+fn slack_message(alert: &Alert) -> Value {
+    let title = slack_escape(&format!("{}: {}", alert.severity.label(), alert.message));
+    json!({
+        "text": title,
+        "attachments": [{
+            "fallback": title,
+            "color": format!("#{:06X}", severity_color(&alert.severity)),
+            "title": title,
+            "text": slack_escape(&chat_details(alert)),
+            "footer": i18n::t("alert.notification_title"),
+            "ts": alert.timestamp.timestamp(),
+        }],
+    })
+}
+
+/// Webhook message with one embed colored by severity. Mentions in error
+/// text or bodies are never turned into pings.
+fn discord_message(alert: &Alert) -> Value {
+    json!({
+        "allowed_mentions": { "parse": [] },
+        "embeds": [{
+            "title": format!("{}: {}", alert.severity.label(), alert.message),
+            "description": chat_details(alert),
+            "color": severity_color(&alert.severity),
+            "footer": { "text": i18n::t("alert.notification_title") },
+            "timestamp": alert.timestamp.to_rfc3339(),
+        }],
+    })
+}
+
+/// POST the alert to `url` as JSON
+async fn send_webhook(client: reqwest::Client, url: String, alert: Alert) -> Result<()> {
+    let payload = json!({
+        "site": alert.site_name,
+        "transition": alert.transition,
+        "severity": alert.severity,
+        "timestamp": alert.timestamp,
+        "previous_status": alert.previous_status.key(),
+        "current_status": alert.current_status.key(),
+        "message": alert.message,
+        "error": alert.error(),
+        "check": alert.check.as_ref().map(|check| json!({
+            "status": check.status.key(),
+            "latency_ms": check.response_time_ms,
+            "http_status": check.http_status,
+            "error_kind": check.failure,
+            "body_snippet": check.body_snippet,
+        })),
+        "diagnostics": alert.diagnostics,
+        "notes": alert.notes,
+        "follow_up": alert.follow_up,
+    });
+    post_json(client, &url, &endpoint_host(&url), payload).await
+}
+
+/// How errors name a webhook: its host alone, since the rest of the URL
+/// (a token in the path or query) is often the secret
+fn endpoint_host(url: &str) -> String {
+    url::Url::parse(url).ok().and_then(|u| u.host_str().map(str::to_string)).unwrap_or_default()
+}
+
+/// POST `payload` to `url`, failing on anything but a 2xx answer. Errors
+/// name the endpoint as `label`, for URLs that carry a secret.
+async fn post_json(client: reqwest::Client, url: &str, label: &str, payload: Value) -> Result<()> {
+    let response = client
+        .post(url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}: {}", label, e.without_url()))?;
+    if !response.status().is_success() {
+        anyhow::bail!("{} answered {}", label, response.status());
     }
+    Ok(())
 }
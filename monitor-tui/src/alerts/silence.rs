@@ -0,0 +1,109 @@
+use super::AlertSeverity;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDateTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+/// Alerts that match are recorded but not notified, e.g. "staging-*"
+/// warnings during a load test
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Silence {
+    /// Glob over site names, e.g. "staging-*", or several separated by commas
+    pub sites: String,
+    /// Only alerts of this severity; any when unset
+    #[serde(default)]
+    pub severity: Option<AlertSeverity>,
+    /// When the silence lifts, as "YYYY-MM-DD HH:MM" local time or RFC 3339;
+    /// never when unset
+    #[serde(default)]
+    pub until: Option<String>,
+}
+
+impl Silence {
+    /// Parse what's typed after 'm': a site glob, then optionally a severity
+    /// and how long the silence lasts, e.g. "staging-* warning 2h"
+    pub fn parse_input(text: &str) -> Result<Self> {
+        let mut words = text.split_whitespace();
+        let sites = words.next().context("Type a site name or glob, e.g. staging-*")?.to_string();
+        let mut silence = Self { sites, severity: None, until: None };
+        for word in words {
+            if let Some(severity) = AlertSeverity::from_name(word) {
+                silence.severity = Some(severity);
+            } else {
+                let duration = parse_duration(word).with_context(|| {
+                    format!("'{}' is neither a severity (critical, warning, recovery, info) nor a duration like 30m, 2h or 1d", word)
+                })?;
+                silence.until = Some((Local::now() + duration).to_rfc3339());
+            }
+        }
+        silence.validate()?;
+        Ok(silence)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        for glob in self.globs() {
+            glob::Pattern::new(glob).with_context(|| format!("Invalid site glob '{}'", glob))?;
+        }
+        self.until()?;
+        Ok(())
+    }
+
+    /// When the silence lifts, if ever
+    pub fn until(&self) -> Result<Option<DateTime<Local>>> {
+        let Some(until) = &self.until else {
+            return Ok(None);
+        };
+        if let Ok(at) = DateTime::parse_from_rfc3339(until) {
+            return Ok(Some(at.with_timezone(&Local)));
+        }
+        let naive = NaiveDateTime::parse_from_str(until, "%Y-%m-%d %H:%M")
+            .with_context(|| format!("Invalid until '{}' - use YYYY-MM-DD HH:MM", until))?;
+        naive
+            .and_local_timezone(Local)
+            .earliest()
+            .map(Some)
+            .with_context(|| format!("until '{}' doesn't exist in local time", until))
+    }
+
+    /// Whether the silence has lifted
+    pub fn expired(&self) -> bool {
+        matches!(self.until(), Ok(Some(until)) if until <= Local::now())
+    }
+
+    /// Whether an alert of `severity` for `site_name` is silenced
+    pub fn matches(&self, site_name: &str, severity: &AlertSeverity) -> bool {
+        !self.expired()
+            && self.severity.as_ref().is_none_or(|only| only == severity)
+            && self.globs().any(|glob| glob::Pattern::new(glob).is_ok_and(|pattern| pattern.matches(site_name)))
+    }
+
+    fn globs(&self) -> impl Iterator<Item = &str> {
+        self.sites.split(',').map(str::trim).filter(|glob| !glob.is_empty())
+    }
+
+    /// e.g. "staging-* (warning) until 18:30"
+    pub fn describe(&self) -> String {
+        let mut text = self.sites.clone();
+        if let Some(severity) = &self.severity {
+            text.push_str(&format!(" ({})", severity.label().to_lowercase()));
+        }
+        if let Ok(Some(until)) = self.until() {
+            let format = if until.date_naive() == Local::now().date_naive() { "%H:%M" } else { "%Y-%m-%d %H:%M" };
+            text.push_str(&format!(" until {}", until.format(format)));
+        }
+        text
+    }
+}
+
+/// "90s", "30m", "2h" or "1d"
+fn parse_duration(text: &str) -> Option<TimeDelta> {
+    let split = text.find(|c: char| !c.is_ascii_digit())?;
+    let (number, unit) = text.split_at(split);
+    let number: i64 = number.parse().ok().filter(|n| *n > 0)?;
+    match unit {
+        "s" => TimeDelta::try_seconds(number),
+        "m" => TimeDelta::try_minutes(number),
+        "h" => TimeDelta::try_hours(number),
+        "d" => TimeDelta::try_days(number),
+        _ => None,
+    }
+}
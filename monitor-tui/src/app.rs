@@ -1,14 +1,31 @@
-use crate::alerts::{Alert, AlertDetector, AlertHistory};
-use crate::checker::{CheckResult, Status};
-use crate::config::Config;
+use crate::alerts::{Alert, AlertDetector, AlertHistory, AlertSeverity, NotificationStats, Silence, StatusTransition};
+use crate::checker::{CheckResult, QueueStats, Status};
+use crate::clipboard;
+use crate::config::{Config, SiteConfig, SiteKind, UptimeWindow};
+use crate::control::{ControlCommand, StatusSummary};
+use crate::export::{self, ChartFormat};
 use crate::metrics_poller::{MetricsPoll, MetricsSnapshot};
-use crate::history::SiteHistory;
-use crate::ui::theme::{ResponsiveLayout, Theme, ThemeName};
-use chrono::{DateTime, Utc};
+use crate::history::{HistoryStore, SiteHistory};
+use crate::result_log::ResultLog;
+use crate::palette::Palette;
+use crate::preferences::Preferences;
+use crate::report;
+use crate::score;
+use crate::stats::SessionStats;
+use crate::ui::dashboard::CachedRow;
+use crate::ui::graphics::ChartPlacement;
+use crate::ui::theme::{Theme, ThemeName};
+use anyhow::Context;
+use chrono::{DateTime, Local, Utc};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use indexmap::IndexMap;
-use ratatui::layout::Rect;
-use tokio::sync::broadcast;
+use ratatui::layout::{Position, Rect};
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, watch, Notify};
 
 /// Actions that can result from handling events
 pub enum AppAction {
@@ -25,44 +42,198 @@ pub enum View {
     AlertDetail(usize),  // Alert detail view by index
     Help,                // Help screen showing keyboard shortcuts
     Server,              // Server metrics view
+    Overview,            // Fleet-wide statistics for the session
+    Slo,                 // Error budgets of sites with an SLO target
+    AlertStats,          // Aggregates over the alert history
+}
+
+/// Check intervals +/- step through, in seconds
+const INTERVAL_STEPS: [u64; 13] = [1, 2, 5, 10, 15, 30, 60, 120, 300, 600, 900, 1800, 3600];
+
+/// Longest comment that can be attached to an alert, in characters
+const MAX_COMMENT_LENGTH: usize = 200;
+
+/// A comment being typed in the alert detail view
+pub struct CommentInput {
+    /// The alert it's for, by when it fired and for which site, since display
+    /// positions shift as new alerts arrive
+    pub timestamp: DateTime<Utc>,
+    pub site_name: String,
+    pub text: String,
+}
+
+/// A destructive action waiting for a yes or no
+pub struct Confirmation {
+    pub prompt: String,
+    pub action: ConfirmAction,
+}
+
+/// What a confirmation carries out when answered yes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfirmAction {
+    Quit,
+    ClearAlerts,
+    ClearRecoveries,
+    LiftSilences,
+}
+
+/// Cycling through each site's detail view on a timer
+pub struct Carousel {
+    /// When the next site is shown; None while held on the current one
+    pub next_at: Option<Instant>,
+}
+
+/// What a click on a region registered during rendering selects
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClickTarget {
+    Site(usize),  // Dashboard row, by site index
+    Alert(usize), // Alert history row, by display index
 }
 
 /// Main application state
 pub struct App {
-    pub config: Config,
+    pub config: Arc<Config>,
     pub theme: Theme,
     pub theme_name: ThemeName,
     pub sites: IndexMap<String, SiteHistory>,
     pub selected_index: Option<usize>,
+    /// First site row shown on the dashboard; adjusted while rendering to keep
+    /// the selection in view
+    pub dashboard_scroll: Cell<usize>,
+    /// Dashboard rows from earlier frames, keyed by site name
+    pub dashboard_rows: RefCell<HashMap<String, CachedRow>>,
+    /// Composite sites whose members are listed under them on the dashboard
+    pub expanded: HashSet<String>,
+    /// Sites pinned to the top of the dashboard; `sites` keeps them first
+    pub pinned: HashSet<String>,
+    /// Sites marked with Space; bulk actions on the dashboard apply to them
+    /// instead of the selected site
+    pub marked: HashSet<String>,
+    /// Tags added with 'T', by site, on top of the configured ones
+    added_tags: BTreeMap<String, Vec<String>>,
+    /// List the dashboard worst health score first instead of in `sites` order
+    pub sort_by_health: bool,
+    /// Leave acknowledged alerts out of the alerts view
+    pub hide_acknowledged: bool,
+    /// Composite sites each member belongs to
+    composites_of: HashMap<String, Vec<String>>,
+    /// First alert row shown in the alert history
+    pub alert_scroll: Cell<usize>,
+    /// Clickable areas from the last frame, filled in by the render functions
+    click_regions: RefCell<Vec<(Rect, ClickTarget)>>,
+    /// Draw the detail chart as a terminal image rather than text
+    pub chart_graphics: Cell<bool>,
+    /// Where the last frame left room for the chart image
+    pub chart_placement: RefCell<Option<ChartPlacement>>,
     pub last_update: DateTime<Utc>,
+    /// Span of time uptime figures cover, cycled with 'u' in the detail view
+    pub uptime_window: UptimeWindow,
+    /// Sites with a check out, and when it went out
+    checking: HashMap<String, Instant>,
+    /// Sites whose checker task stopped, and when their last result came in;
+    /// cleared by the next result
+    stalled: HashMap<String, DateTime<Local>>,
+    /// When each site's data goes stale if no new result has come in: two
+    /// intervals after the last one, or one plus the request timeout if longer
+    stale_at: HashMap<String, Instant>,
+    /// Stale sites already alerted on, until their next result
+    stale_alerted: HashSet<String>,
     pub current_view: View,
     pub error_message: Option<String>,
-    pub error_timestamp: Option<DateTime<Utc>>,
+    pub error_timestamp: Option<Instant>,
     force_refresh_tx: broadcast::Sender<()>,
+    /// Per-site signals to check one site now, shared with its checker task
+    site_refresh: HashMap<String, Arc<Notify>>,
+    /// Whether each site's checks are held, watched by its checker task:
+    /// true while everything or that site alone is paused
+    pause_tx: HashMap<String, watch::Sender<bool>>,
+    /// Sites paused on their own, apart from pausing everything
+    paused_sites: HashSet<String>,
+    /// Check intervals set with +/- while running, watched by each site's
+    /// checker task; None follows the config
+    interval_tx: HashMap<String, watch::Sender<Option<Duration>>>,
+    /// When checking was paused, while it is
+    pub paused_since: Option<DateTime<Local>>,
     pub alert_history: AlertHistory,
+    /// Counts since startup for the overview
+    pub session: SessionStats,
+    /// Counts since the last scheduled report
+    report_stats: SessionStats,
     alert_detector: AlertDetector,
     pub alert_selected_index: Option<usize>,
+    /// Set while the detail view cycles through the sites by itself
+    pub carousel: Option<Carousel>,
+    /// Comment being typed; while set, keys edit it instead of navigating
+    pub comment_input: Option<CommentInput>,
+    /// Command palette, while open; keys filter and pick from it
+    pub palette: Option<Palette>,
+    /// Question shown over the view; while set, only y/n answer it
+    pub confirmation: Option<Confirmation>,
+    /// Silence being typed after 'm'; while set, keys edit it
+    pub silence_input: Option<String>,
+    /// Tag being typed after 'T'; while set, keys edit it
+    pub tag_input: Option<String>,
+    /// Silences added with 'm', on top of the configured ones
+    pub silences: Vec<Silence>,
     pub server_metrics: Option<MetricsSnapshot>,
     pub server_metrics_error: Option<String>,
     pub server_selected_index: usize,
+    /// Result queue overflow counters, shared with the checker tasks
+    pub queue_stats: Arc<QueueStats>,
+    /// Notification delivery counters, shared with the notifier
+    pub notification_stats: Arc<NotificationStats>,
+    /// Where runtime UI choices are saved, when persist_preferences is on
+    preferences_path: Option<PathBuf>,
+    /// Where check results are appended, when persist_history is on
+    history_store: Option<HistoryStore>,
+    /// Where results, alerts and messages are written when running headless
+    result_log: Option<ResultLog>,
 }
 
 impl App {
     /// Create a new App with the given configuration
-    pub fn new(config: Config, force_refresh_tx: broadcast::Sender<()>) -> Self {
+    pub fn new(
+        config: Arc<Config>,
+        force_refresh_tx: broadcast::Sender<()>,
+        queue_stats: Arc<QueueStats>,
+        notification_stats: Arc<NotificationStats>,
+    ) -> Self {
         let history_size = config.settings.history_size;
         let alert_history_size = config.settings.alerts.alert_history_size;
-        let theme_name = config.settings.theme;
+        // Runtime choices from earlier sessions take precedence over the config
+        let preferences_path = config
+            .settings
+            .persist_preferences
+            .then(|| config.settings.preferences_file.clone().or_else(Preferences::default_path))
+            .flatten();
+        let preferences = preferences_path.as_deref().map(Preferences::load).unwrap_or_default();
+        let theme_name = preferences.theme.unwrap_or(config.settings.theme);
         let theme = Theme::from_name(theme_name);
 
-        // Initialize empty history for each site
-        let sites: IndexMap<String, SiteHistory> = config
+        // Initialize empty history for each site, in the saved order with
+        // pinned sites first
+        let mut sites: IndexMap<String, SiteHistory> = config
             .sites
             .iter()
             .map(|site| (site.name.clone(), SiteHistory::new(history_size)))
             .collect();
+        // Pins of sites not present yet are kept for when discovery adds them
+        let pinned: HashSet<String> = preferences.pinned.into_iter().collect();
+        let position = |name: &String| preferences.order.iter().position(|n| n == name).unwrap_or(usize::MAX);
+        sites.sort_by_cached_key(|name, _| (!pinned.contains(name), position(name)));
 
-        let alert_detector = AlertDetector::new(config.clone());
+        let composites_of = composites_of(&config);
+
+        let silences = preferences.silences.into_iter().filter(|s| !s.expired()).collect();
+
+        let expanded = preferences
+            .expanded
+            .into_iter()
+            .filter(|name| config.site(name).is_some_and(|s| s.kind == SiteKind::Composite))
+            .collect();
+
+        let uptime_window = config.settings.uptime_window;
+        let alert_detector = AlertDetector::new(Arc::clone(&config));
         let alert_history = AlertHistory::new(alert_history_size);
 
         Self {
@@ -71,54 +242,270 @@ impl App {
             theme_name,
             sites,
             selected_index: None,
+            dashboard_scroll: Cell::new(0),
+            dashboard_rows: RefCell::new(HashMap::new()),
+            expanded,
+            pinned,
+            marked: HashSet::new(),
+            added_tags: preferences.tags,
+            sort_by_health: preferences.sort_by_health,
+            hide_acknowledged: preferences.hide_acknowledged,
+            composites_of,
+            alert_scroll: Cell::new(0),
+            click_regions: RefCell::new(Vec::new()),
+            chart_graphics: Cell::new(false),
+            chart_placement: RefCell::new(None),
             last_update: Utc::now(),
+            uptime_window,
+            checking: HashMap::new(),
+            stalled: HashMap::new(),
+            stale_at: HashMap::new(),
+            stale_alerted: HashSet::new(),
             current_view: View::Dashboard,
             error_message: None,
             error_timestamp: None,
             force_refresh_tx,
+            site_refresh: HashMap::new(),
+            pause_tx: HashMap::new(),
+            paused_sites: HashSet::new(),
+            interval_tx: HashMap::new(),
+            paused_since: None,
             alert_history,
+            session: SessionStats::new(),
+            report_stats: SessionStats::new(),
             alert_detector,
             alert_selected_index: None,
+            carousel: None,
+            comment_input: None,
+            palette: None,
+            confirmation: None,
+            silence_input: None,
+            tag_input: None,
+            silences,
             server_metrics: None,
             server_metrics_error: None,
             server_selected_index: 0,
+            queue_stats,
+            notification_stats,
+            preferences_path,
+            history_store: None,
+            result_log: None,
+        }
+    }
+
+    /// Load the check results kept at `path` from earlier sessions and keep
+    /// appending new ones there. Returns how many were restored.
+    pub fn restore_history(&mut self, path: &Path) -> anyhow::Result<usize> {
+        let (store, restored) = HistoryStore::open(path, &mut self.sites, self.config.settings.history_size)?;
+        self.history_store = Some(store);
+        Ok(restored)
+    }
+
+    /// Write results, alerts and messages to `log` from now on, for running
+    /// without the TUI
+    pub fn set_result_log(&mut self, log: ResultLog) {
+        self.result_log = Some(log);
+    }
+
+    /// Record the findings of diagnostics run after a check went Down, on
+    /// that result and on any alert it raised. Returns a follow-up to notify
+    /// with when that alert's notification went out before the findings.
+    pub fn attach_diagnostics(&mut self, site_name: &str, checked_at: DateTime<Utc>, findings: Vec<String>) -> Option<Alert> {
+        let diagnosed = self.alert_history.attach_diagnostics(site_name, checked_at, &findings);
+        if let Some(history) = self.sites.get_mut(site_name) {
+            history.attach_diagnostics(checked_at, findings);
         }
+        diagnosed
+            .filter(|alert| !alert.silenced && !alert.diagnostics.is_empty())
+            .map(|alert| alert.diagnosed_follow_up())
     }
 
     /// Handle a new check result
     pub fn handle_check_result(&mut self, site_name: String, result: CheckResult) -> Option<Alert> {
-        // Get previous status from history (clone to avoid borrow conflicts)
+        self.checking.remove(&site_name);
+        self.stalled.remove(&site_name);
+        self.stale_alerted.remove(&site_name);
+
+        // Get previous status from history (clone to avoid borrow conflicts);
+        // skipped checks say nothing about the site, so look past them
         let previous_status = self
             .sites
             .get(&site_name)
-            .and_then(|h| h.latest())
+            .and_then(|h| h.latest_checked())
             .map(|r| r.status.clone());
 
         // Add result to history (existing logic)
         if let Some(history) = self.sites.get_mut(&site_name) {
             history.add_result(result.clone());
             self.last_update = Utc::now();
+            if let Some(store) = &self.history_store {
+                store.record(&site_name, &result);
+            }
+            if let Some(log) = &self.result_log {
+                log.result(&site_name, &result);
+            }
+        }
+        // Composite results restate their members' checks, so only those count
+        if let Some(site) = self.config.site(&site_name)
+            && site.kind != SiteKind::Composite
+        {
+            self.session.record_check(&site_name, &result, site.expected_status);
+            self.report_stats.record_check(&site_name, &result, site.expected_status);
         }
 
         // Check if this should trigger an alert
+        let blocked = self.blocked_by(&site_name).is_some();
         if let Some(transition) = self.alert_detector.evaluate(
             &site_name,
             previous_status.as_ref(),
             &result.status,
+            blocked,
         ) {
-            let alert = Alert::new(
+            let severity = self.config.alert_severity(&site_name, &transition);
+            let mut alert = Alert::new(
                 site_name,
                 transition,
+                severity,
                 previous_status.unwrap_or(crate::checker::Status::Up),
-                result.status,
+                result.status.clone(),
             );
+            if let Some(site) = self.config.site(&alert.site_name) {
+                alert.failure_kind = result.failure_kind(site.expected_status);
+            }
+            alert.check = Some(result);
+            alert.silenced = self.silenced(&alert.site_name, &alert.severity);
+            if let Some(findings) = self.sites.get(&alert.site_name).and_then(|h| h.current_diagnostics()) {
+                alert.diagnostics = findings.to_vec();
+            }
+            if let Some(site) = self.config.site(&alert.site_name) {
+                alert.notes = site.notes();
+            }
+            self.session.record_alert(&alert.site_name, &alert.severity);
+            self.report_stats.record_alert(&alert.site_name, &alert.severity);
             self.alert_history.add_alert(alert.clone());
+            if let Some(log) = &self.result_log {
+                log.alert(&alert);
+            }
             return Some(alert);
         }
 
         None
     }
 
+    /// First dependency of this site that is currently Down
+    pub fn blocked_by(&self, site_name: &str) -> Option<&str> {
+        let site = self.config.site(site_name)?;
+        site.depends_on
+            .iter()
+            .find(|dependency| {
+                self.sites
+                    .get(dependency.as_str())
+                    .and_then(|h| h.latest())
+                    .is_some_and(|r| r.status == Status::Down)
+            })
+            .map(String::as_str)
+    }
+
+    /// Failure streak and alert threshold for a failing site, e.g. (3, 5)
+    pub fn failure_progress(&self, site_name: &str) -> Option<(usize, usize)> {
+        self.alert_detector.failure_progress(site_name)
+    }
+
+    /// Start showing a site found at runtime, unless one of that name or
+    /// checking that URL already exists, or it fails the checks a site in
+    /// the config file would
+    pub fn add_site(&mut self, site: SiteConfig) -> anyhow::Result<()> {
+        if self.sites.contains_key(&site.name) {
+            anyhow::bail!("Discovered site '{}' has the same name as an existing site", site.name);
+        }
+        if let Some(existing) = self.config.sites.iter().find(|s| s.url == site.url) {
+            anyhow::bail!("Discovered site '{}' is already checked as '{}'", site.name, existing.name);
+        }
+        let name = site.name.clone();
+        let config = self.config.with_site(site);
+        config
+            .validate_sites()
+            .with_context(|| format!("Discovered site '{}' is invalid", name))?;
+        let mut history = SiteHistory::new(self.config.settings.history_size);
+        if let Some(store) = &mut self.history_store {
+            store.replay(&name, &mut history);
+        }
+        self.sites.insert(name.clone(), history);
+        if self.pinned.contains(&name) {
+            self.sort_pinned_first();
+        }
+        self.config = Arc::new(config);
+        self.alert_detector.set_config(Arc::clone(&self.config));
+        Ok(())
+    }
+
+    /// Switch to a config edited while running: sites new to it start with
+    /// what the history file kept of them, if anything, sites no longer in it
+    /// go away, and the rest keep theirs.
+    /// Returns the names of the sites added and removed.
+    pub fn reload_config(&mut self, config: Arc<Config>) -> (Vec<String>, Vec<String>) {
+        let removed: Vec<String> = self.sites.keys().filter(|name| config.site(name).is_none()).cloned().collect();
+        for name in &removed {
+            self.remove_site(name);
+        }
+        let added: Vec<String> = config
+            .sites
+            .iter()
+            .filter(|site| !self.sites.contains_key(&site.name))
+            .map(|site| site.name.clone())
+            .collect();
+        for name in &added {
+            let mut history = SiteHistory::new(config.settings.history_size);
+            if let Some(store) = &mut self.history_store {
+                store.replay(name, &mut history);
+            }
+            self.sites.insert(name.clone(), history);
+        }
+        self.sort_pinned_first();
+        self.composites_of = composites_of(&config);
+        // Rows render parts of the config, like status code colors and targets
+        self.dashboard_rows.borrow_mut().clear();
+        self.config = config;
+        self.alert_detector.set_config(Arc::clone(&self.config));
+        (added, removed)
+    }
+
+    /// Stop showing a site found at runtime, along with its history
+    pub fn remove_site(&mut self, name: &str) {
+        // The carousel moves on rather than stopping on a site that went away
+        let next = self
+            .carousel
+            .as_ref()
+            .and_then(|_| self.carousel_neighbor(name, true))
+            .filter(|next| next != name);
+        let Some((index, _, _)) = self.sites.shift_remove_full(name) else {
+            return;
+        };
+        self.checking.remove(name);
+        self.stalled.remove(name);
+        self.stale_at.remove(name);
+        self.stale_alerted.remove(name);
+        self.marked.remove(name);
+        self.pause_tx.remove(name);
+        self.paused_sites.remove(name);
+        self.interval_tx.remove(name);
+        self.selected_index = match self.selected_index {
+            Some(selected) if selected == index => None,
+            Some(selected) if selected > index => Some(selected - 1),
+            selected => selected,
+        };
+        if self.current_view == View::Detail(name.to_string()) {
+            self.current_view = match next {
+                Some(next) => View::Detail(next),
+                None => View::Dashboard,
+            };
+        }
+        self.dashboard_rows.borrow_mut().remove(name);
+        self.site_refresh.remove(name);
+        self.config = Arc::new(self.config.without_site(name));
+        self.alert_detector.set_config(Arc::clone(&self.config));
+    }
+
     /// Handle a metrics poll result
     pub fn update_metrics(&mut self, result: MetricsPoll) {
         match result {
@@ -135,9 +522,35 @@ impl App {
 
     /// Handle keyboard input
     pub fn handle_key_event(&mut self, key: KeyEvent) -> AppAction {
+        if self.confirmation.is_some() {
+            return self.handle_confirmation_key(key);
+        }
+        if self.comment_input.is_some() {
+            return self.handle_comment_key(key);
+        }
+        if self.palette.is_some() {
+            return self.handle_palette_key(key);
+        }
+        if self.silence_input.is_some() {
+            return self.handle_silence_key(key);
+        }
+        if self.tag_input.is_some() {
+            return self.handle_tag_key(key);
+        }
+
         match key.code {
-            // Quit on 'q' or Ctrl+C
-            KeyCode::Char('q') => AppAction::Quit,
+            // Every action, searchable by name
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.palette = Some(Palette::default());
+                AppAction::Continue
+            }
+
+            // Quit on 'q' (asking first while sites are down) or Ctrl+C
+            KeyCode::Char('q') => match self.status_counts().1 {
+                0 => AppAction::Quit,
+                1 => self.confirm("1 site is down. Quit anyway?".to_string(), ConfirmAction::Quit),
+                down => self.confirm(format!("{} sites are down. Quit anyway?", down), ConfirmAction::Quit),
+            },
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => AppAction::Quit,
 
             // Help screen on '?' or 'h'
@@ -154,10 +567,16 @@ impl App {
                 AppAction::Continue
             }
 
+            // ESC on the dashboard drops the marks first
+            KeyCode::Esc if self.current_view == View::Dashboard && !self.marked.is_empty() => {
+                self.marked.clear();
+                AppAction::Continue
+            }
+
             // ESC key - return to previous view
             KeyCode::Esc => {
                 match self.current_view {
-                    View::AlertDetail(_) => {
+                    View::AlertDetail(_) | View::AlertStats => {
                         // Return to alerts list
                         self.current_view = View::Alerts;
                     }
@@ -194,23 +613,26 @@ impl App {
             KeyCode::Up | KeyCode::Char('k') => {
                 match self.current_view {
                     View::Dashboard => {
-                        if self.sites.is_empty() {
+                        let order = self.dashboard_order();
+                        if order.is_empty() {
                             return AppAction::Continue;
                         }
-                        self.selected_index = Some(match self.selected_index {
+                        let position = match self.dashboard_position(&order) {
                             None => 0,
-                            Some(idx) if idx > 0 => idx - 1,
-                            Some(_) => self.sites.len() - 1, // Wrap to bottom
-                        });
+                            Some(pos) if pos > 0 => pos - 1,
+                            Some(_) => order.len() - 1, // Wrap to bottom
+                        };
+                        self.selected_index = Some(order[position].0);
                     }
                     View::Alerts => {
-                        if self.alert_history.is_empty() {
+                        let count = self.listed_alerts().len();
+                        if count == 0 {
                             return AppAction::Continue;
                         }
                         self.alert_selected_index = Some(match self.alert_selected_index {
                             None => 0,
                             Some(idx) if idx > 0 => idx - 1,
-                            Some(_) => self.alert_history.len() - 1, // Wrap to bottom
+                            Some(_) => count - 1, // Wrap to bottom
                         });
                     }
                     View::Server => {
@@ -232,22 +654,25 @@ impl App {
             KeyCode::Down | KeyCode::Char('j') => {
                 match self.current_view {
                     View::Dashboard => {
-                        if self.sites.is_empty() {
+                        let order = self.dashboard_order();
+                        if order.is_empty() {
                             return AppAction::Continue;
                         }
-                        self.selected_index = Some(match self.selected_index {
+                        let position = match self.dashboard_position(&order) {
                             None => 0,
-                            Some(idx) if idx < self.sites.len() - 1 => idx + 1,
+                            Some(pos) if pos < order.len() - 1 => pos + 1,
                             Some(_) => 0, // Wrap to top
-                        });
+                        };
+                        self.selected_index = Some(order[position].0);
                     }
                     View::Alerts => {
-                        if self.alert_history.is_empty() {
+                        let count = self.listed_alerts().len();
+                        if count == 0 {
                             return AppAction::Continue;
                         }
                         self.alert_selected_index = Some(match self.alert_selected_index {
                             None => 0,
-                            Some(idx) if idx < self.alert_history.len() - 1 => idx + 1,
+                            Some(idx) if idx < count - 1 => idx + 1,
                             Some(_) => 0, // Wrap to top
                         });
                     }
@@ -266,6 +691,76 @@ impl App {
                 AppAction::Continue
             }
 
+            // Jump to the first / last site
+            KeyCode::Home | KeyCode::Char('g') if self.current_view == View::Dashboard => {
+                if let Some(&(index, _)) = self.dashboard_order().first() {
+                    self.selected_index = Some(index);
+                }
+                AppAction::Continue
+            }
+            KeyCode::End | KeyCode::Char('G') if self.current_view == View::Dashboard => {
+                if let Some(&(index, _)) = self.dashboard_order().last() {
+                    self.selected_index = Some(index);
+                }
+                AppAction::Continue
+            }
+
+            // Start or stop cycling through the detail views
+            KeyCode::Char('c') => {
+                if self.carousel.take().is_none() {
+                    self.start_carousel();
+                }
+                AppAction::Continue
+            }
+
+            // Skip to the next or previous site, or hold the current one, while cycling
+            KeyCode::Right | KeyCode::Tab if self.carousel.is_some() => {
+                self.step_carousel(true);
+                AppAction::Continue
+            }
+            KeyCode::Left | KeyCode::BackTab if self.carousel.is_some() => {
+                self.step_carousel(false);
+                AppAction::Continue
+            }
+            KeyCode::Char(' ') if matches!(self.current_view, View::Detail(_)) => {
+                if let Some(carousel) = &mut self.carousel {
+                    carousel.next_at = match carousel.next_at {
+                        Some(_) => None,
+                        None => Some(Instant::now() + Duration::from_secs(self.config.settings.carousel_interval)),
+                    };
+                }
+                AppAction::Continue
+            }
+
+            // Mark or unmark the selected site for bulk actions, and move on
+            KeyCode::Char(' ') if self.current_view == View::Dashboard => {
+                let order = self.dashboard_order();
+                if let Some(position) = self.dashboard_position(&order)
+                    && let Some((name, _)) = self.sites.get_index(order[position].0)
+                {
+                    if !self.marked.remove(name) {
+                        self.marked.insert(name.clone());
+                    }
+                    if let Some(&(next, _)) = order.get(position + 1) {
+                        self.selected_index = Some(next);
+                    }
+                }
+                AppAction::Continue
+            }
+
+            // Show or hide the selected composite's members
+            KeyCode::Char('z') if self.current_view == View::Dashboard => {
+                if let Some(name) = self.selected_site().map(|(name, _)| name.clone())
+                    && self.config.site(&name).is_some_and(|s| s.kind == SiteKind::Composite)
+                {
+                    if !self.expanded.remove(&name) {
+                        self.expanded.insert(name);
+                    }
+                    self.save_preferences();
+                }
+                AppAction::Continue
+            }
+
             // Force refresh all sites
             KeyCode::Char('r') => {
                 // Send broadcast to all checker tasks
@@ -274,6 +769,89 @@ impl App {
                 AppAction::Continue
             }
 
+            // Pause or resume checking of the marked sites, or else every site
+            KeyCode::Char('P') => {
+                if self.marked_targets().is_some() {
+                    self.toggle_sites_paused();
+                } else {
+                    let paused = self.paused_since.is_none();
+                    self.set_paused(paused);
+                }
+                AppAction::Continue
+            }
+
+            // Pause or resume checking of the marked sites, or else the selected
+            // one (or the detail view's), e.g. during its maintenance
+            KeyCode::Char('p') if self.current_site().is_some() || self.marked_targets().is_some() => {
+                self.toggle_sites_paused();
+                AppAction::Continue
+            }
+
+            // Open the selected site (or the detail view's) in a browser
+            KeyCode::Char('O') => {
+                self.open_site_url();
+                AppAction::Continue
+            }
+
+            // Copy the view as plain text, e.g. to paste into an incident chat
+            KeyCode::Char('y') => {
+                self.copy_view();
+                AppAction::Continue
+            }
+
+            // Re-check only the marked sites, or else the selected one (or the detail view's)
+            KeyCode::Char('R') => {
+                self.refresh_selected();
+                AppAction::Continue
+            }
+
+            // Move the selected site up or down the dashboard
+            KeyCode::Char(c @ ('K' | 'J')) if self.current_view == View::Dashboard => {
+                self.move_selected(c == 'K');
+                AppAction::Continue
+            }
+
+            // Pin the selected site to the top of the dashboard, or unpin it
+            KeyCode::Char('f') if self.current_view == View::Dashboard => {
+                if let Some(name) = self.selected_site().map(|(name, _)| name.clone()) {
+                    if !self.pinned.remove(&name) {
+                        self.pinned.insert(name);
+                    }
+                    self.sort_pinned_first();
+                    self.save_preferences();
+                }
+                AppAction::Continue
+            }
+
+            // Span of time uptime is shown over
+            KeyCode::Char('u') if matches!(self.current_view, View::Detail(_)) => {
+                self.uptime_window = self.uptime_window.next();
+                // Dashboard rows show uptime too
+                self.dashboard_rows.borrow_mut().clear();
+                AppAction::Continue
+            }
+
+            // Triage order: worst health score first
+            KeyCode::Char('H') if self.current_view == View::Dashboard => {
+                self.sort_by_health = !self.sort_by_health;
+                self.save_preferences();
+                AppAction::Continue
+            }
+
+            // Fleet-wide statistics
+            KeyCode::Char('o') => {
+                self.selected_index = None;
+                self.current_view = View::Overview;
+                AppAction::Continue
+            }
+
+            // Error budgets
+            KeyCode::Char('b') => {
+                self.selected_index = None;
+                self.current_view = View::Slo;
+                AppAction::Continue
+            }
+
             // Server metrics view (or refresh if already in it)
             KeyCode::Char('s') => {
                 if self.current_view == View::Server {
@@ -285,6 +863,88 @@ impl App {
                 AppAction::Continue
             }
 
+            // Empty the alert history ('x'), or drop its recovery alerts ('X')
+            KeyCode::Char('x') if self.current_view == View::Alerts && !self.alert_history.is_empty() => {
+                let prompt = format!("Clear all {} alerts from the history?", self.alert_history.len());
+                self.confirm(prompt, ConfirmAction::ClearAlerts)
+            }
+            KeyCode::Char('X') if self.current_view == View::Alerts => {
+                match self.alert_history.by_severity(AlertSeverity::Recovery).len() {
+                    0 => AppAction::Continue,
+                    n => self.confirm(format!("Clear {} recovery alerts from the history?", n), ConfirmAction::ClearRecoveries),
+                }
+            }
+
+            // Alert statistics from the alert history
+            KeyCode::Char('S') if self.current_view == View::Alerts => self.show_view(View::AlertStats),
+
+            // Silence alerts by site glob ('m'), or lift every silence ('M')
+            KeyCode::Char('m') => {
+                let sites = match self.marked_targets() {
+                    Some(marked) => marked.iter().map(|name| glob::Pattern::escape(name)).collect::<Vec<_>>().join(","),
+                    None => self.current_site().unwrap_or_default().to_string(),
+                };
+                self.silence_input = Some(sites);
+                AppAction::Continue
+            }
+
+            // Check the marked sites, or else the selected one, less or more often
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.step_interval(true);
+                AppAction::Continue
+            }
+            KeyCode::Char('-') => {
+                self.step_interval(false);
+                AppAction::Continue
+            }
+
+            // Tag the marked sites, or else the selected one (or the detail view's)
+            KeyCode::Char('T') if self.current_site().is_some() || self.marked_targets().is_some() => {
+                self.tag_input = Some(String::new());
+                AppAction::Continue
+            }
+            KeyCode::Char('M') => match self.silences.iter().filter(|s| !s.expired()).count() {
+                0 => AppAction::Continue,
+                n => self.confirm(format!("Lift the {} silences added here?", n), ConfirmAction::LiftSilences),
+            },
+
+            // Acknowledge the selected alert (or the alert detail view's), or
+            // take it back
+            KeyCode::Char('A') if matches!(self.current_view, View::Alerts | View::AlertDetail(_)) => {
+                self.toggle_acknowledged();
+                AppAction::Continue
+            }
+
+            // Show or hide acknowledged alerts
+            KeyCode::Char('F') if self.current_view == View::Alerts => {
+                self.hide_acknowledged = !self.hide_acknowledged;
+                self.alert_selected_index = None;
+                self.alert_scroll.set(0);
+                self.save_preferences();
+                AppAction::Continue
+            }
+
+            // Comment on the alert in the alert detail view
+            KeyCode::Char('n') => {
+                if let View::AlertDetail(index) = self.current_view
+                    && let Some(alert) = self.listed_alerts().get(index)
+                {
+                    self.comment_input = Some(CommentInput {
+                        timestamp: alert.timestamp,
+                        site_name: alert.site_name.clone(),
+                        text: alert.comment.clone().unwrap_or_default(),
+                    });
+                }
+                AppAction::Continue
+            }
+
+            // Export the detail view's history as an SVG ('e') or PNG ('E') chart
+            KeyCode::Char(c @ ('e' | 'E')) if matches!(self.current_view, View::Detail(_)) => {
+                let format = if c == 'e' { ChartFormat::Svg } else { ChartFormat::Png };
+                self.export_chart(format);
+                AppAction::Continue
+            }
+
             // Cycle through themes
             KeyCode::Char('t') => {
                 self.cycle_theme();
@@ -295,22 +955,881 @@ impl App {
         }
     }
 
+    /// Edit the comment being typed: Enter saves it (empty clears it), ESC discards it
+    fn handle_comment_key(&mut self, key: KeyEvent) -> AppAction {
+        let Some(input) = &mut self.comment_input else {
+            return AppAction::Continue;
+        };
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return AppAction::Quit,
+            KeyCode::Char(c)
+                if !key.modifiers.contains(KeyModifiers::CONTROL) && input.text.chars().count() < MAX_COMMENT_LENGTH =>
+            {
+                input.text.push(c);
+            }
+            KeyCode::Backspace => {
+                input.text.pop();
+            }
+            KeyCode::Esc => self.comment_input = None,
+            KeyCode::Enter => {
+                if let Some(input) = self.comment_input.take() {
+                    let text = input.text.trim();
+                    let comment = (!text.is_empty()).then(|| text.to_string());
+                    if !self.alert_history.set_comment(input.timestamp, &input.site_name, comment) {
+                        self.set_error("The alert is no longer in the history; comment discarded".to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+        AppAction::Continue
+    }
+
+    /// Ask before carrying out `action`, unless confirmations are turned off
+    pub fn confirm(&mut self, prompt: String, action: ConfirmAction) -> AppAction {
+        if !self.config.settings.confirm_actions {
+            return self.run_confirmed(action);
+        }
+        self.confirmation = Some(Confirmation { prompt, action });
+        AppAction::Continue
+    }
+
+    fn run_confirmed(&mut self, action: ConfirmAction) -> AppAction {
+        match action {
+            ConfirmAction::Quit => AppAction::Quit,
+            ConfirmAction::ClearAlerts | ConfirmAction::ClearRecoveries => {
+                self.clear_alerts(action == ConfirmAction::ClearRecoveries);
+                AppAction::Continue
+            }
+            ConfirmAction::LiftSilences => {
+                self.lift_silences();
+                AppAction::Continue
+            }
+        }
+    }
+
+    /// Edit the silence being typed: Enter adds it, ESC discards it
+    fn handle_silence_key(&mut self, key: KeyEvent) -> AppAction {
+        let Some(input) = &mut self.silence_input else {
+            return AppAction::Continue;
+        };
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return AppAction::Quit,
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.silence_input = None,
+            KeyCode::Enter => {
+                if let Some(input) = self.silence_input.take() {
+                    match Silence::parse_input(&input) {
+                        Ok(silence) => {
+                            self.set_error(format!("Silenced {}", silence.describe()));
+                            self.silences.push(silence);
+                            self.save_preferences();
+                        }
+                        Err(e) => {
+                            // Keep what was typed so it can be fixed
+                            self.set_error(format!("{:#}", e));
+                            self.silence_input = Some(input);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        AppAction::Continue
+    }
+
+    /// Configured and added silences that haven't lifted
+    pub fn active_silences(&self) -> Vec<&Silence> {
+        self.config.silences.iter().chain(&self.silences).filter(|s| !s.expired()).collect()
+    }
+
+    /// Whether alerts of `severity` for `site_name` go unnotified
+    pub fn silenced(&self, site_name: &str, severity: &AlertSeverity) -> bool {
+        self.active_silences().iter().any(|s| s.matches(site_name, severity))
+    }
+
+    /// Lift the silences added with 'm'; configured ones stay until the config changes
+    fn lift_silences(&mut self) {
+        self.silences.retain(|s| !s.expired());
+        let count = self.silences.len();
+        self.silences.clear();
+        self.save_preferences();
+        self.set_error(format!("Lifted {} silences", count));
+    }
+
+    /// Answer the open confirmation: y or Enter goes ahead, n or ESC backs out
+    fn handle_confirmation_key(&mut self, key: KeyEvent) -> AppAction {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => AppAction::Quit,
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter => match self.confirmation.take() {
+                Some(confirmation) => self.run_confirmed(confirmation.action),
+                None => AppAction::Continue,
+            },
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+                self.confirmation = None;
+                AppAction::Continue
+            }
+            _ => AppAction::Continue,
+        }
+    }
+
+    /// Type into the command palette: Enter runs the highlighted command, ESC closes it
+    fn handle_palette_key(&mut self, key: KeyEvent) -> AppAction {
+        let shown = self.palette.as_ref().map_or(0, |p| p.matches(self).len());
+        let Some(palette) = &mut self.palette else {
+            return AppAction::Continue;
+        };
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('c') if control => return AppAction::Quit,
+            KeyCode::Up => palette.selected = palette.selected.saturating_sub(1),
+            KeyCode::Char('p') if control => palette.selected = palette.selected.saturating_sub(1),
+            KeyCode::Down => palette.selected = (palette.selected + 1).min(shown.saturating_sub(1)),
+            KeyCode::Char('n') if control => palette.selected = (palette.selected + 1).min(shown.saturating_sub(1)),
+            KeyCode::Char(c) if !control => {
+                palette.query.push(c);
+                palette.selected = 0;
+            }
+            KeyCode::Backspace => {
+                palette.query.pop();
+                palette.selected = 0;
+            }
+            KeyCode::Esc => self.palette = None,
+            KeyCode::Enter => {
+                if let Some(palette) = self.palette.take()
+                    && let Some(command) = palette.matches(self).get(palette.selected)
+                {
+                    return command.run(self);
+                }
+            }
+            _ => {}
+        }
+        AppAction::Continue
+    }
+
+    /// Switch to a view from anywhere, as ESC does from most of them
+    pub fn show_view(&mut self, view: View) -> AppAction {
+        self.selected_index = None;
+        self.alert_selected_index = None;
+        self.current_view = view;
+        AppAction::Continue
+    }
+
+    /// Site the current view is about: the dashboard's selection or the detail view's
+    pub fn current_site(&self) -> Option<&str> {
+        match &self.current_view {
+            View::Dashboard => self.selected_site().map(|(name, _)| name.as_str()),
+            View::Detail(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Open the current site's URL with the desktop's default handler
+    fn open_site_url(&mut self) {
+        let Some(site) = self.current_site().and_then(|name| self.config.site(name)) else {
+            return;
+        };
+        if !matches!(site.kind, SiteKind::Http | SiteKind::Graphql) {
+            self.set_error(format!("'{}' has no URL to open", site.name));
+            return;
+        }
+        let opener = if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
+        let spawned = std::process::Command::new(opener)
+            .arg(&site.url)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+        if let Err(e) = spawned {
+            self.set_error(format!("Couldn't run {} to open {}: {}", opener, site.url, e));
+        }
+    }
+
+    /// Put the current view on the clipboard as text, reporting the outcome in
+    /// the message bar
+    fn copy_view(&mut self) {
+        let Some(text) = clipboard::view_text(self) else {
+            self.set_error("Nothing to copy in this view".to_string());
+            return;
+        };
+        let message = match clipboard::copy(&text) {
+            Ok(via) => format!("Copied {} lines via {}", text.lines().count(), via),
+            Err(e) => format!("Copy failed: {:#}", e),
+        };
+        self.set_error(message);
+    }
+
+    /// Signal for a site's checker task to check it right away
+    pub fn refresh_signal(&mut self, site_name: &str) -> Arc<Notify> {
+        Arc::clone(self.site_refresh.entry(site_name.to_string()).or_default())
+    }
+
+    /// Check the marked sites, or else the dashboard's selected site or the
+    /// detail view's, right away; a composite's members are checked in its place
+    fn refresh_selected(&mut self) {
+        let targets = match self.marked_targets() {
+            Some(marked) => marked,
+            None => self.current_site().map(str::to_string).into_iter().collect(),
+        };
+        if targets.is_empty() {
+            return;
+        }
+        if self.paused_since.is_some() {
+            self.set_error("Checks are paused; press P to resume".to_string());
+            return;
+        }
+        for name in self.checked_sites(&targets) {
+            if let Some(refresh) = self.site_refresh.get(&name) {
+                refresh.notify_one();
+            }
+        }
+    }
+
+    /// Sites marked on the dashboard, in dashboard order, while there and
+    /// any are marked
+    fn marked_targets(&self) -> Option<Vec<String>> {
+        if self.current_view != View::Dashboard || self.marked.is_empty() {
+            return None;
+        }
+        Some(self.sites.keys().filter(|name| self.marked.contains(*name)).cloned().collect())
+    }
+
+    /// The sites that are actually checked for `names`: composites stand for
+    /// their members
+    fn checked_sites(&self, names: &[String]) -> Vec<String> {
+        let mut checked: Vec<String> = Vec::new();
+        for name in names {
+            let members = match self.config.site(name) {
+                Some(site) if site.kind == SiteKind::Composite => site.members.clone(),
+                _ => vec![name.clone()],
+            };
+            for member in members {
+                if !checked.contains(&member) {
+                    checked.push(member);
+                }
+            }
+        }
+        checked
+    }
+
+    /// Pause checks of the marked sites, or else the selected one (or the
+    /// detail view's), or resume them when they're all paused
+    fn toggle_sites_paused(&mut self) {
+        let targets = match self.marked_targets() {
+            Some(marked) => marked,
+            None => self.current_site().map(str::to_string).into_iter().collect(),
+        };
+        if targets.is_empty() {
+            return;
+        }
+        let sites = self.checked_sites(&targets);
+        let pause = !sites.iter().all(|name| self.paused_sites.contains(name));
+        for name in &sites {
+            if pause {
+                self.paused_sites.insert(name.clone());
+            } else {
+                self.paused_sites.remove(name);
+                // Results stopped on purpose; staleness counts again from the next one
+                self.stale_at.remove(name);
+            }
+        }
+        self.update_pause_signals();
+        let verb = if pause { "Paused" } else { "Resumed" };
+        match sites.len() {
+            1 => self.set_error(format!("{} checks of {}", verb, sites[0])),
+            n => self.set_error(format!("{} checks of {} sites", verb, n)),
+        }
+    }
+
+    /// How many sites are paused on their own
+    pub fn paused_site_count(&self) -> usize {
+        self.paused_sites.len()
+    }
+
+    /// Whether the site's checks are paused on their own; a composite's when
+    /// all its members' are
+    pub fn site_paused(&self, site_name: &str) -> bool {
+        match self.config.site(site_name) {
+            Some(site) if site.kind == SiteKind::Composite => {
+                !site.members.is_empty() && site.members.iter().all(|m| self.paused_sites.contains(m))
+            }
+            _ => self.paused_sites.contains(site_name),
+        }
+    }
+
+    /// The site's configured tags followed by those added with 'T'
+    pub fn site_tags(&self, site_name: &str) -> Vec<String> {
+        let mut tags: Vec<String> = self.config.site(site_name).map(|s| s.tags.clone()).unwrap_or_default();
+        for tag in self.added_tags.get(site_name).into_iter().flatten() {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        tags
+    }
+
+    /// Sites a tag typed after 'T' goes on: the marked ones, or else the
+    /// selected one (or the detail view's)
+    pub fn tag_targets(&self) -> Vec<String> {
+        match self.marked_targets() {
+            Some(marked) => marked,
+            None => self.current_site().map(str::to_string).into_iter().collect(),
+        }
+    }
+
+    /// Edit the tags being typed: Enter adds them (or removes those starting
+    /// with '-'), ESC discards them
+    fn handle_tag_key(&mut self, key: KeyEvent) -> AppAction {
+        let Some(input) = &mut self.tag_input else {
+            return AppAction::Continue;
+        };
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return AppAction::Quit,
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.tag_input = None,
+            KeyCode::Enter => {
+                if let Some(input) = self.tag_input.take() {
+                    self.apply_tags(&input);
+                }
+            }
+            _ => {}
+        }
+        AppAction::Continue
+    }
+
+    /// Add each word of `input` as a tag of the tag targets, or remove it
+    /// when it starts with '-'; only added tags can be removed
+    fn apply_tags(&mut self, input: &str) {
+        let targets = self.tag_targets();
+        if input.split_whitespace().next().is_none() || targets.is_empty() {
+            return;
+        }
+        for name in &targets {
+            let tags = self.added_tags.entry(name.clone()).or_default();
+            for word in input.split_whitespace() {
+                match word.strip_prefix('-') {
+                    Some(tag) => tags.retain(|t| t != tag),
+                    None if !tags.iter().any(|t| t == word) => tags.push(word.to_string()),
+                    None => {}
+                }
+            }
+            if tags.is_empty() {
+                self.added_tags.remove(name);
+            }
+        }
+        self.save_preferences();
+        match targets.len() {
+            1 => self.set_error(format!("Tags of {}: {}", targets[0], self.site_tags(&targets[0]).join(", "))),
+            n => self.set_error(format!("Updated the tags of {} sites", n)),
+        }
+    }
+
+    /// Receiver for checker tasks to learn when checking is paused
+    pub fn pause_signal(&mut self, site_name: &str) -> watch::Receiver<bool> {
+        let paused = self.paused_since.is_some() || self.paused_sites.contains(site_name);
+        self.pause_tx
+            .entry(site_name.to_string())
+            .or_insert_with(|| watch::Sender::new(paused))
+            .subscribe()
+    }
+
+    /// Receiver for checker tasks to learn of intervals set with +/-
+    pub fn interval_signal(&mut self, site_name: &str) -> watch::Receiver<Option<Duration>> {
+        self.interval_tx
+            .entry(site_name.to_string())
+            .or_insert_with(|| watch::Sender::new(None))
+            .subscribe()
+    }
+
+    /// The site's check interval from the config; None for cron-scheduled sites
+    pub fn configured_interval(&self, site_name: &str) -> Option<Duration> {
+        let site = self.config.site(site_name)?;
+        if site.schedule.is_some() {
+            return None;
+        }
+        Some(Duration::from_secs(site.check_interval.unwrap_or(self.config.settings.refresh_interval)))
+    }
+
+    /// The interval the site is checked on, and whether it was set with +/-;
+    /// backoff and adaptive intervals vary from it until it's set
+    pub fn site_interval(&self, site_name: &str) -> Option<(Duration, bool)> {
+        match self.interval_tx.get(site_name).and_then(|tx| *tx.borrow()) {
+            Some(interval) => Some((interval, true)),
+            None => Some((self.configured_interval(site_name)?, false)),
+        }
+    }
+
+    /// Move the check interval of the marked sites, or else the selected one
+    /// (or the detail view's), a step longer or shorter; stepping back to
+    /// the configured interval hands scheduling back to the config
+    fn step_interval(&mut self, longer: bool) {
+        let targets = match self.marked_targets() {
+            Some(marked) => marked,
+            None => self.current_site().map(str::to_string).into_iter().collect(),
+        };
+        if targets.is_empty() {
+            return;
+        }
+        let sites: Vec<String> = self
+            .checked_sites(&targets)
+            .into_iter()
+            .filter(|name| self.configured_interval(name).is_some())
+            .collect();
+        if sites.is_empty() {
+            self.set_error("Sites checked on a cron schedule keep it".to_string());
+            return;
+        }
+        for name in &sites {
+            let (Some((current, _)), Some(configured)) = (self.site_interval(name), self.configured_interval(name)) else {
+                continue;
+            };
+            let secs = current.as_secs();
+            let step = if longer {
+                INTERVAL_STEPS.iter().copied().find(|&step| step > secs)
+            } else {
+                INTERVAL_STEPS.iter().rev().copied().find(|&step| step < secs)
+            };
+            let interval = Duration::from_secs(step.unwrap_or(secs));
+            let set = (interval != configured).then_some(interval);
+            if let Some(tx) = self.interval_tx.get(name) {
+                tx.send_if_modified(|held| std::mem::replace(held, set) != set);
+            }
+            // The result due under the old interval may now come later
+            self.stale_at.remove(name);
+        }
+        match sites.as_slice() {
+            [name] => {
+                let (interval, set) = self.site_interval(name).unwrap_or_default();
+                let note = if set { "" } else { " (as configured)" };
+                self.set_error(format!("Checking {} every {}s{}", name, interval.as_secs(), note));
+            }
+            _ => {
+                let direction = if longer { "less" } else { "more" };
+                self.set_error(format!("Checking {} sites {} often", sites.len(), direction));
+            }
+        }
+    }
+
+    /// Tell each checker task whether its checks are paused now
+    fn update_pause_signals(&self) {
+        for (name, tx) in &self.pause_tx {
+            let paused = self.paused_since.is_some() || self.paused_sites.contains(name);
+            tx.send_if_modified(|held| std::mem::replace(held, paused) != paused);
+        }
+    }
+
+    /// Pause or resume checking; resuming checks every site right away
+    pub fn set_paused(&mut self, paused: bool) {
+        if paused == self.paused_since.is_some() {
+            return;
+        }
+        if paused {
+            self.paused_since = Some(Local::now());
+        } else {
+            self.paused_since = None;
+            // Results stopped on purpose; staleness counts again from the next ones
+            self.stale_at.clear();
+            // Wakes tasks still sleeping; those waiting on the pause check as it lifts
+            let _ = self.force_refresh_tx.send(());
+        }
+        self.update_pause_signals();
+    }
+
+    /// Carry out a command from the control socket, returning the reply
+    pub fn handle_control(&mut self, command: ControlCommand) -> String {
+        match command {
+            ControlCommand::Pause => {
+                self.set_paused(true);
+                "paused".to_string()
+            }
+            ControlCommand::Resume => {
+                self.set_paused(false);
+                "resumed".to_string()
+            }
+            ControlCommand::Report => match self.send_report(false) {
+                Ok(to) => format!("sending report to {}", to),
+                Err(e) => format!("error: {:#}", e),
+            },
+            ControlCommand::ClearAlerts => format!("cleared {} alerts", self.clear_alerts(false)),
+            ControlCommand::ClearRecoveries => format!("cleared {} recovery alerts", self.clear_alerts(true)),
+            ControlCommand::Status => serde_json::to_string(&self.status_summary())
+                .unwrap_or_else(|e| format!("error: {}", e)),
+        }
+    }
+
+    /// Counts by status and the sites that need attention, for `monitor-tui status`
+    fn status_summary(&self) -> StatusSummary {
+        let (up, down, warning, unknown) = self.status_counts();
+        let mut summary = StatusSummary {
+            up,
+            down,
+            warning,
+            unknown,
+            paused: self.paused_since.is_some(),
+            ..Default::default()
+        };
+        for (name, history) in &self.sites {
+            match history.latest().map(|r| &r.status) {
+                Some(Status::Down) => summary.down_sites.push(name.clone()),
+                Some(Status::Warning | Status::RateLimited) => summary.warning_sites.push(name.clone()),
+                _ => {}
+            }
+        }
+        summary
+    }
+
+    /// Alerts as the alerts view lists them: most recent first, without the
+    /// acknowledged ones while they're hidden
+    pub fn listed_alerts(&self) -> Vec<&Alert> {
+        self.alert_history
+            .all_alerts()
+            .iter()
+            .rev()
+            .filter(|alert| !(self.hide_acknowledged && alert.acknowledged))
+            .collect()
+    }
+
+    /// Acknowledge the selected alert, or the alert detail view's, or take
+    /// the acknowledgement back. An alert acknowledged from its detail view
+    /// has been dealt with, so it's back to the list.
+    fn toggle_acknowledged(&mut self) {
+        let index = match self.current_view {
+            View::AlertDetail(index) => Some(index),
+            _ => self.alert_selected_index,
+        };
+        let Some(alert) = index.and_then(|index| self.listed_alerts().get(index).copied()) else {
+            return;
+        };
+        let (timestamp, site_name, acknowledged) = (alert.timestamp, alert.site_name.clone(), !alert.acknowledged);
+        self.alert_history.set_acknowledged(timestamp, &site_name, acknowledged);
+        if acknowledged && matches!(self.current_view, View::AlertDetail(_)) {
+            self.current_view = View::Alerts;
+        }
+        // A hidden alert leaves the list; keep the selection within it
+        let count = self.listed_alerts().len();
+        self.alert_selected_index = self.alert_selected_index.filter(|_| count > 0).map(|i| i.min(count - 1));
+        let verb = if acknowledged { "Acknowledged" } else { "Unacknowledged" };
+        self.set_error(format!("{} alert for {}", verb, site_name));
+    }
+
+    /// Empty the alert history, or drop just its recovery alerts; returns how
+    /// many went
+    pub fn clear_alerts(&mut self, recoveries_only: bool) -> usize {
+        let cleared = if recoveries_only {
+            self.alert_history.clear_recoveries()
+        } else {
+            let count = self.alert_history.len();
+            self.alert_history.clear();
+            count
+        };
+        // Positions in the list no longer point at the same alerts
+        self.alert_selected_index = None;
+        self.alert_scroll.set(0);
+        if matches!(self.current_view, View::AlertDetail(_)) {
+            self.current_view = View::Alerts;
+        }
+        cleared
+    }
+
+    /// Email the availability report in the background, returning who it's
+    /// going to. At the end of a period the next report starts counting afresh;
+    /// failures show with the notification failures.
+    pub fn send_report(&mut self, end_of_period: bool) -> anyhow::Result<String> {
+        let Some(smtp) = self.config.smtp.clone() else {
+            anyhow::bail!("no [smtp] section configured");
+        };
+        let (subject, body) = report::compose(self, &self.report_stats, Local::now());
+        if end_of_period {
+            self.report_stats = SessionStats::new();
+        }
+        let stats = Arc::clone(&self.notification_stats);
+        tokio::spawn(async move {
+            if let Err(e) = report::send(&smtp, subject, body).await {
+                stats.record_failure("report email", format!("{:#}", e));
+            }
+        });
+        Ok(self.config.smtp.as_ref().map(|s| s.to.join(", ")).unwrap_or_default())
+    }
+
+    /// Cycle through the detail views, starting at the selected site
+    pub fn start_carousel(&mut self) {
+        let start = match &self.current_view {
+            View::Detail(name) => Some(name.clone()),
+            _ => self
+                .selected_site()
+                .map(|(name, _)| name.clone())
+                .or_else(|| self.carousel_sites().into_iter().next()),
+        };
+        let Some(start) = start else {
+            self.set_error("No sites to show".to_string());
+            return;
+        };
+        self.selected_index = None;
+        self.current_view = View::Detail(start);
+        self.carousel = Some(Carousel {
+            next_at: Some(Instant::now() + Duration::from_secs(self.config.settings.carousel_interval)),
+        });
+    }
+
+    /// Show the next site when its turn is up; leaving the detail view stops the carousel
+    pub fn advance_carousel(&mut self) {
+        let Some(carousel) = &self.carousel else {
+            return;
+        };
+        if !matches!(self.current_view, View::Detail(_)) {
+            self.carousel = None;
+        } else if carousel.next_at.is_some_and(|at| at <= Instant::now()) {
+            self.step_carousel(true);
+        }
+    }
+
+    /// Show the next or previous site, restarting its time unless held
+    fn step_carousel(&mut self, forward: bool) {
+        let View::Detail(name) = &self.current_view else {
+            return;
+        };
+        if let Some(next) = self.carousel_neighbor(name, forward) {
+            self.current_view = View::Detail(next);
+        }
+        let interval = Duration::from_secs(self.config.settings.carousel_interval);
+        if let Some(carousel) = &mut self.carousel
+            && carousel.next_at.is_some()
+        {
+            carousel.next_at = Some(Instant::now() + interval);
+        }
+    }
+
+    /// Sites the carousel shows, in dashboard order
+    fn carousel_sites(&self) -> Vec<String> {
+        self.dashboard_order()
+            .into_iter()
+            .filter_map(|(index, _)| self.sites.get_index(index).map(|(name, _)| name.clone()))
+            .collect()
+    }
+
+    /// Site after (or before) `name` in the carousel, wrapping around
+    fn carousel_neighbor(&self, name: &str, forward: bool) -> Option<String> {
+        let sites = self.carousel_sites();
+        let position = sites.iter().position(|n| n == name).unwrap_or(0);
+        let next = if forward { position + 1 } else { position + sites.len().saturating_sub(1) };
+        sites.get(next % sites.len().max(1)).cloned()
+    }
+
+    /// Position of the shown site in the carousel and how many it cycles through
+    pub fn carousel_position(&self) -> Option<(usize, usize)> {
+        let View::Detail(name) = &self.current_view else {
+            return None;
+        };
+        self.carousel.as_ref()?;
+        let sites = self.carousel_sites();
+        Some((sites.iter().position(|n| n == name)? + 1, sites.len()))
+    }
+
+    /// Write the detail view's site history to an image file, reporting the
+    /// outcome in the message bar
+    fn export_chart(&mut self, format: ChartFormat) {
+        let View::Detail(name) = &self.current_view else {
+            return;
+        };
+        let Some(history) = self.sites.get(name) else {
+            return;
+        };
+        let dir = self.config.settings.export_dir.as_deref().unwrap_or(Path::new("."));
+        let message = match export::export_chart(name, history, dir, format) {
+            Ok(path) => format!("Exported chart to {}", path.display()),
+            Err(e) => format!("Chart export failed: {:#}", e),
+        };
+        self.set_error(message);
+    }
+
     /// Cycle to the next theme
     pub fn cycle_theme(&mut self) {
         self.theme_name = self.theme_name.next();
         self.theme = Theme::from_name(self.theme_name);
+        self.save_preferences();
+    }
+
+    /// Swap the selected site with the dashboard row above or below it. Only
+    /// top-level rows move, and pinned sites stay above unpinned ones.
+    fn move_selected(&mut self, up: bool) {
+        let Some(selected) = self.selected_index else {
+            return;
+        };
+        if self.sort_by_health {
+            self.set_error("Sites are sorted by health score - press H to reorder them by hand".to_string());
+            return;
+        }
+        let rows: Vec<usize> = (0..self.sites.len())
+            .filter(|&i| self.sites.get_index(i).is_some_and(|(name, _)| !self.composites_of.contains_key(name)))
+            .collect();
+        let Some(position) = rows.iter().position(|&i| i == selected) else {
+            return;
+        };
+        let neighbor = if up { position.checked_sub(1) } else { Some(position + 1) };
+        let Some(&target) = neighbor.and_then(|p| rows.get(p)) else {
+            return;
+        };
+        let is_pinned = |i: usize| self.sites.get_index(i).is_some_and(|(name, _)| self.pinned.contains(name));
+        if is_pinned(selected) != is_pinned(target) {
+            return;
+        }
+        self.sites.move_index(selected, target);
+        self.selected_index = Some(target);
+        self.save_preferences();
+    }
+
+    /// Put pinned sites ahead of the rest, otherwise keeping their order
+    fn sort_pinned_first(&mut self) {
+        let selected = self.selected_site().map(|(name, _)| name.clone());
+        let pinned = &self.pinned;
+        self.sites.sort_by_cached_key(|name, _| !pinned.contains(name));
+        self.selected_index = selected.and_then(|name| self.sites.get_index_of(&name));
+    }
+
+    /// Save runtime UI choices, if preferences persist
+    fn save_preferences(&mut self) {
+        let Some(path) = &self.preferences_path else {
+            return;
+        };
+        let mut expanded: Vec<String> = self.expanded.iter().cloned().collect();
+        expanded.sort();
+        let mut pinned: Vec<String> = self.pinned.iter().cloned().collect();
+        pinned.sort();
+        let preferences = Preferences {
+            theme: Some(self.theme_name),
+            expanded,
+            order: self.sites.keys().cloned().collect(),
+            pinned,
+            sort_by_health: self.sort_by_health,
+            silences: self.silences.clone(),
+            tags: self.added_tags.clone(),
+            hide_acknowledged: self.hide_acknowledged,
+        };
+        if let Err(e) = preferences.save(path) {
+            self.set_error(format!("Couldn't save preferences: {:#}", e));
+        }
+    }
+
+    /// Dashboard rows in display order as (site index, depth): sites that aren't
+    /// composite members, with the members of expanded composites under them
+    pub fn dashboard_order(&self) -> Vec<(usize, usize)> {
+        let mut top: Vec<usize> = self
+            .sites
+            .keys()
+            .enumerate()
+            .filter(|(_, name)| !self.composites_of.contains_key(*name))
+            .map(|(index, _)| index)
+            .collect();
+        // Worst first, with pinned sites still on top and unchecked ones last
+        if self.sort_by_health {
+            top.sort_by_cached_key(|&index| {
+                let name = self.sites.get_index(index).map(|(name, _)| name.as_str()).unwrap_or_default();
+                (!self.pinned.contains(name), self.health_score(name).unwrap_or(u8::MAX))
+            });
+        }
+
+        let mut order = Vec::with_capacity(self.sites.len());
+        for index in top {
+            self.push_dashboard_row(&mut order, index, 0);
+        }
+        order
+    }
+
+    /// The site's 0-100 health score, once it has been checked
+    pub fn health_score(&self, site_name: &str) -> Option<u8> {
+        let history = self.sites.get(site_name)?;
+        let threshold = self
+            .config
+            .site(site_name)
+            .and_then(|s| s.warning_threshold_ms)
+            .or(self.config.settings.warning_threshold_ms);
+        let alerts = self.alert_history.by_site(site_name);
+        score::health_score(history, self.uptime_window, threshold, &alerts, Utc::now())
+    }
+
+    fn push_dashboard_row(&self, order: &mut Vec<(usize, usize)>, index: usize, depth: usize) {
+        order.push((index, depth));
+        let Some((name, _)) = self.sites.get_index(index) else {
+            return;
+        };
+        if self.expanded.contains(name)
+            && let Some(site) = self.config.site(name)
+        {
+            for member in &site.members {
+                if let Some(member_index) = self.sites.get_index_of(member) {
+                    self.push_dashboard_row(order, member_index, depth + 1);
+                }
+            }
+        }
+    }
+
+    /// Row of the selected site within `order`
+    pub fn dashboard_position(&self, order: &[(usize, usize)]) -> Option<usize> {
+        let selected = self.selected_index?;
+        order.iter().position(|&(index, _)| index == selected)
+    }
+
+    /// Re-aggregate every composite containing `member` from each member's
+    /// latest result, once all of them have reported. Skipped and rate-limited
+    /// members say nothing about health, so they are left out of the vote.
+    pub fn composite_results(&self, member: &str) -> Vec<(String, CheckResult)> {
+        let Some(composites) = self.composites_of.get(member) else {
+            return Vec::new();
+        };
+
+        composites
+            .iter()
+            .filter_map(|name| {
+                let site = self.config.site(name)?;
+                let latest: Vec<&CheckResult> = site
+                    .members
+                    .iter()
+                    .map(|m| self.sites.get(m).and_then(|h| h.latest()))
+                    .collect::<Option<_>>()?;
+                let voting: Vec<&CheckResult> = latest.into_iter().filter(|r| r.status.is_conclusive()).collect();
+                if voting.is_empty() {
+                    return None;
+                }
+
+                let up = voting.iter().filter(|r| r.status == Status::Up).count();
+                let healthy = voting.iter().filter(|r| r.status != Status::Down).count();
+                let required = site.required_members().min(voting.len());
+                let mut result = if up >= required {
+                    CheckResult::new_up()
+                } else {
+                    let message = format!("{}/{} members up, {} required", up, voting.len(), required);
+                    let mut result = CheckResult::new_down(message);
+                    if healthy >= required {
+                        result.status = Status::Warning;
+                    }
+                    result
+                };
+                // The slowest member bounds how long the service takes to answer
+                result.response_time_ms = voting.iter().filter_map(|r| r.response_time_ms).max();
+
+                Some((name.clone(), result))
+            })
+            .collect()
     }
 
     /// Get the currently selected site
     pub fn selected_site(&self) -> Option<(&String, &SiteHistory)> {
-        self.selected_index.and_then(|idx| self.sites.iter().nth(idx))
+        self.selected_index.and_then(|idx| self.sites.get_index(idx))
     }
 
-    /// Set an error message (reserved for future use)
-    #[allow(dead_code)]
+    /// Show a message in the message bar until it is dismissed
     pub fn set_error(&mut self, message: String) {
+        if let Some(log) = &self.result_log {
+            log.message(&message);
+        }
         self.error_message = Some(message);
-        self.error_timestamp = Some(Utc::now());
+        self.error_timestamp = Some(Instant::now());
     }
 
     /// Clear the error message
@@ -321,13 +1840,106 @@ impl App {
 
     /// Check if error should be auto-dismissed (after 5 seconds)
     pub fn check_error_dismissal(&mut self) {
-        if let Some(timestamp) = self.error_timestamp {
-            let now = Utc::now();
-            let elapsed = now.signed_duration_since(timestamp);
-            if elapsed.num_seconds() >= 5 {
-                self.clear_error();
+        if let Some(timestamp) = self.error_timestamp
+            && timestamp.elapsed() >= Duration::from_secs(5)
+        {
+            self.clear_error();
+        }
+    }
+
+    /// Note that a site's check has gone out
+    pub fn mark_checking(&mut self, site_name: String) {
+        if self.sites.contains_key(&site_name) {
+            self.checking.insert(site_name, Instant::now());
+        }
+    }
+
+    /// When the site's pending check went out, if one is
+    pub fn checking_since(&self, site_name: &str) -> Option<Instant> {
+        self.checking.get(site_name).copied()
+    }
+
+    /// Note that the site's checker task stopped; a check it had out won't
+    /// come back
+    pub fn mark_stalled(&mut self, site_name: &str) {
+        self.checking.remove(site_name);
+        if !self.stalled.contains_key(site_name) {
+            let since = self
+                .sites
+                .get(site_name)
+                .and_then(|h| h.latest())
+                .map_or_else(Local::now, |r| r.timestamp.with_timezone(&Local));
+            self.stalled.insert(site_name.to_string(), since);
+        }
+    }
+
+    /// When the last result of a site whose checker task stopped came in
+    pub fn stalled_since(&self, site_name: &str) -> Option<DateTime<Local>> {
+        self.stalled.get(site_name).copied()
+    }
+
+    /// Note when a site's checker task will report again, `next_check` from now
+    pub fn expect_result(&mut self, site_name: &str, next_check: Duration) {
+        let Some(site) = self.config.site(site_name) else {
+            return;
+        };
+        let timeout = Duration::from_secs(site.request_timeout.unwrap_or(self.config.settings.request_timeout));
+        let grace = (next_check * 2).max(next_check + timeout);
+        self.stale_at.insert(site_name.to_string(), Instant::now() + grace);
+    }
+
+    /// When the last result of a site whose next one is overdue came in; old
+    /// data shouldn't pass for the site's current status
+    pub fn stale_since(&self, site_name: &str) -> Option<DateTime<Local>> {
+        if self.paused_since.is_some()
+            || self.paused_sites.contains(site_name)
+            || self.stale_at.get(site_name).is_none_or(|at| *at > Instant::now())
+        {
+            return None;
+        }
+        let latest = self.sites.get(site_name)?.latest()?;
+        Some(latest.timestamp.with_timezone(&Local))
+    }
+
+    /// Alerts for sites that went stale since the last call, where the
+    /// stale transition is turned on
+    pub fn check_stale(&mut self) -> Vec<Alert> {
+        let newly_stale: Vec<String> = self
+            .stale_at
+            .keys()
+            .filter(|name| !self.stale_alerted.contains(*name) && self.stale_since(name).is_some())
+            .cloned()
+            .collect();
+        let mut alerts = Vec::new();
+        for site_name in newly_stale {
+            self.stale_alerted.insert(site_name.clone());
+            if !self.alert_detector.alerts_on_stale(&site_name) {
+                continue;
             }
+            let Some(status) = self.sites.get(&site_name).and_then(|h| h.latest()).map(|r| r.status.clone()) else {
+                continue;
+            };
+            let transition = StatusTransition::Stale;
+            let severity = self.config.alert_severity(&site_name, &transition);
+            let mut alert = Alert::new(site_name, transition, severity, status.clone(), status);
+            alert.silenced = self.silenced(&alert.site_name, &alert.severity);
+            if let Some(site) = self.config.site(&alert.site_name) {
+                alert.notes = site.notes();
+            }
+            self.session.record_alert(&alert.site_name, &alert.severity);
+            self.report_stats.record_alert(&alert.site_name, &alert.severity);
+            self.alert_history.add_alert(alert.clone());
+            if let Some(log) = &self.result_log {
+                log.alert(&alert);
+            }
+            alerts.push(alert);
         }
+        alerts
+    }
+
+    /// Number of sites with a check out
+    pub fn checking_count(&self) -> usize {
+        self.checking.len()
     }
 
     /// Get counts of sites by status
@@ -341,76 +1953,55 @@ impl App {
             match history.latest().map(|r| &r.status) {
                 Some(Status::Up) => up += 1,
                 Some(Status::Down) => down += 1,
-                Some(Status::Warning) => warn += 1,
-                None => unknown += 1,
+                Some(Status::Warning | Status::RateLimited) => warn += 1,
+                Some(Status::Skipped) | None => unknown += 1,
             }
         }
 
         (up, down, warn, unknown)
     }
 
-    /// Handle mouse input
-    pub fn handle_mouse_event(&mut self, mouse: MouseEvent, frame_size: Rect) {
-        // Only handle left click down events
-        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
-            return;
-        }
-
-        let row = mouse.row;
-        let col = mouse.column;
-
-        match self.current_view {
-            View::Dashboard => {
-                self.handle_dashboard_click(row, col, frame_size);
-            }
-            View::Alerts => {
-                self.handle_alerts_click(row, col, frame_size);
-            }
-            _ => {}
-        }
+    /// Record a clickable area for the frame being drawn
+    pub fn register_click(&self, area: Rect, target: ClickTarget) {
+        self.click_regions.borrow_mut().push((area, target));
     }
 
-    fn handle_dashboard_click(&mut self, row: u16, _col: u16, frame_size: Rect) {
-        // Layout: Header(3) + Content(variable) + StatusBar(1) + optional Error(1) + Footer(1)
-        let header_height = 3;
-        let footer_height = if self.error_message.is_some() { 3 } else { 2 }; // status + footer + optional error
-        let content_start = header_height;
-        let content_end = frame_size.height.saturating_sub(footer_height);
+    /// Forget the previous frame's clickable areas before drawing a new one
+    pub fn clear_click_regions(&self) {
+        self.click_regions.borrow_mut().clear();
+    }
 
-        // Check if click is in the content area
-        if row < content_start || row >= content_end {
-            return;
+    /// Handle mouse input. Returns true if it changed anything on screen.
+    pub fn handle_mouse_event(&mut self, mouse: MouseEvent) -> bool {
+        // Only handle left click down events
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return false;
         }
 
-        // Calculate item index based on click position
-        let responsive = ResponsiveLayout::new(frame_size.width);
-        let lines_per_item = responsive.lines_per_site_item();
-        let content_row = row - content_start - 1; // -1 for top border
-        let clicked_index = (content_row / lines_per_item) as usize;
+        let position = Position::new(mouse.column, mouse.row);
+        let target = self
+            .click_regions
+            .borrow()
+            .iter()
+            .find(|(area, _)| area.contains(position))
+            .map(|(_, target)| *target);
 
-        if clicked_index < self.sites.len() {
-            self.selected_index = Some(clicked_index);
+        match target {
+            Some(ClickTarget::Site(index)) => self.selected_index = Some(index),
+            Some(ClickTarget::Alert(index)) => self.alert_selected_index = Some(index),
+            None => return false,
         }
+        true
     }
+}
 
-    fn handle_alerts_click(&mut self, row: u16, _col: u16, frame_size: Rect) {
-        // Layout: Header(3) + Alert list(variable) + Summary(3) + StatusBar(1) + Footer(1)
-        let header_height = 3;
-        let footer_height = 5; // summary(3) + status(1) + footer(1)
-        let content_start = header_height;
-        let content_end = frame_size.height.saturating_sub(footer_height);
-
-        if row < content_start || row >= content_end {
-            return;
-        }
-
-        // Each alert takes 2 lines
-        let lines_per_item = 2;
-        let content_row = row - content_start - 1;
-        let clicked_index = (content_row / lines_per_item) as usize;
-
-        if clicked_index < self.alert_history.len() {
-            self.alert_selected_index = Some(clicked_index);
+/// Composite sites by the name of each of their members
+fn composites_of(config: &Config) -> HashMap<String, Vec<String>> {
+    let mut composites_of: HashMap<String, Vec<String>> = HashMap::new();
+    for site in config.sites.iter().filter(|s| s.kind == SiteKind::Composite) {
+        for member in &site.members {
+            composites_of.entry(member.clone()).or_default().push(site.name.clone());
         }
     }
+    composites_of
 }
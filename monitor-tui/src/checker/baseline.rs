@@ -0,0 +1,62 @@
+use super::types::{CheckResult, Status};
+use crate::config::{Settings, SiteConfig};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Samples needed before the baseline is trusted
+const MIN_SAMPLES: usize = 10;
+
+/// Trailing response times for one site, for judging a check against the
+/// site's own normal latency rather than a fixed threshold
+pub struct LatencyBaseline {
+    factor: f64,
+    window: Duration,
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl LatencyBaseline {
+    /// Baseline for a site, if it or the global settings enable one
+    pub fn for_site(site: &SiteConfig, settings: &Settings) -> Option<Self> {
+        let factor = site.baseline_factor.or(settings.baseline_factor)?;
+        Some(Self {
+            factor,
+            window: Duration::from_secs(settings.baseline_window),
+            samples: VecDeque::new(),
+        })
+    }
+
+    /// Downgrade an Up result to Warning when it is more than `factor` times
+    /// the trailing median, then add it to the window
+    pub fn apply(&mut self, result: &mut CheckResult) {
+        let Some(ms) = result.response_time_ms else {
+            return;
+        };
+
+        let now = Instant::now();
+        while self.samples.front().is_some_and(|&(at, _)| now.duration_since(at) > self.window) {
+            self.samples.pop_front();
+        }
+
+        if result.status == Status::Up
+            && let Some(median) = self.median()
+            && ms as f64 > median as f64 * self.factor
+        {
+            result.status = Status::Warning;
+            result.error_message = Some(format!(
+                "Response {}ms is over {}x the usual {}ms",
+                ms, self.factor, median
+            ));
+        }
+
+        self.samples.push_back((now, ms));
+    }
+
+    fn median(&self) -> Option<u64> {
+        if self.samples.len() < MIN_SAMPLES {
+            return None;
+        }
+        let mut times: Vec<u64> = self.samples.iter().map(|&(_, ms)| ms).collect();
+        let middle = times.len() / 2;
+        Some(*times.select_nth_unstable(middle).1)
+    }
+}
@@ -0,0 +1,50 @@
+use crate::config::Blackout;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Local, NaiveTime, Weekday};
+
+/// A parsed blackout period: a daily local-time window, optionally limited to
+/// some days of the week
+#[derive(Debug, Clone)]
+pub struct BlackoutWindow {
+    start: NaiveTime,
+    end: NaiveTime,
+    /// Days the window starts on; empty means every day
+    days: Vec<Weekday>,
+}
+
+impl BlackoutWindow {
+    pub fn parse(blackout: &Blackout) -> Result<Self> {
+        let time = |value: &str| {
+            NaiveTime::parse_from_str(value, "%H:%M")
+                .with_context(|| format!("Invalid blackout time '{}' - expected HH:MM", value))
+        };
+        let days = blackout
+            .days
+            .iter()
+            .map(|day| {
+                day.parse::<Weekday>()
+                    .map_err(|_| anyhow::anyhow!("Invalid blackout day '{}' - expected e.g. \"mon\"", day))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            start: time(&blackout.start)?,
+            end: time(&blackout.end)?,
+            days,
+        })
+    }
+
+    /// Whether `now` falls inside the window; a window whose end is before its
+    /// start runs past midnight into the next day
+    pub fn contains(&self, now: DateTime<Local>) -> bool {
+        let time = now.time();
+        let today = now.weekday();
+        let starts_on = |day: Weekday| self.days.is_empty() || self.days.contains(&day);
+
+        if self.start <= self.end {
+            starts_on(today) && time >= self.start && time < self.end
+        } else {
+            (starts_on(today) && time >= self.start) || (starts_on(today.pred()) && time < self.end)
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::config::SiteConfig;
+use super::types::{CheckResult, FailureKind};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Fabricates plausible check results without touching the network
+pub struct DemoChecker {
+    rng_state: u64,
+    base_latency_ms: u64,
+    outage_remaining: u32,
+}
+
+impl DemoChecker {
+    pub fn new(site_name: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        site_name.hash(&mut hasher);
+        let name_hash = hasher.finish();
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+
+        Self {
+            // xorshift state must never be zero
+            rng_state: (name_hash ^ nanos) | 1,
+            // Give each site a stable "personality" between 40ms and 400ms
+            base_latency_ms: 40 + name_hash % 360,
+            outage_remaining: 0,
+        }
+    }
+
+    pub async fn check(&mut self, site: &SiteConfig, warning_threshold_ms: Option<u64>) -> CheckResult {
+        // Ongoing outage: keep failing until it runs its course
+        if self.outage_remaining > 0 {
+            self.outage_remaining -= 1;
+            return CheckResult::new_failed(FailureKind::Connection, "Connection failed: connection refused (demo)".to_string());
+        }
+
+        let roll = self.next_percent();
+
+        // ~2% chance to start an outage lasting 2-6 checks
+        if roll < 2 {
+            self.outage_remaining = 1 + (self.next_u64() % 5) as u32;
+            return CheckResult::new_timeout("Request timeout".to_string());
+        }
+
+        // +/-30% jitter around the base latency, with occasional slow spikes
+        let jitter = 70 + self.next_u64() % 61;
+        let mut latency_ms = self.base_latency_ms * jitter / 100;
+        if roll < 7 {
+            latency_ms *= 4;
+        }
+
+        tokio::time::sleep(Duration::from_millis(latency_ms)).await;
+
+        // ~3% chance the endpoint answers with an unexpected status
+        let http_status = if (7..10).contains(&roll) {
+            503
+        } else {
+            site.expected_status
+        };
+
+        CheckResult::new_success(latency_ms, http_status, site.code_status(http_status), warning_threshold_ms)
+    }
+
+    /// xorshift64 - good enough for fake data, no extra dependency needed
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.rng_state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.rng_state = x;
+        x
+    }
+
+    fn next_percent(&mut self) -> u64 {
+        self.next_u64() % 100
+    }
+}
@@ -0,0 +1,144 @@
+use super::ntp::NTP_PORT;
+use super::tls;
+use super::unix::{self, UnixTarget};
+use std::net::SocketAddr;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio::process::Command;
+use url::Url;
+
+/// Hops traced before giving up on the route
+const MAX_HOPS: &str = "20";
+
+/// Upper bound on the route trace, which can take far longer than a request
+const ROUTE_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Probe a site that just went Down layer by layer (DNS, TCP, TLS, route), so
+/// the first alert already says where it breaks. One line per finding.
+pub async fn run(url: &str, timeout: Duration) -> Vec<String> {
+    // A local daemon's socket has no DNS, route or TLS to look at
+    if let Ok(Some(target)) = UnixTarget::parse(url) {
+        return unix::diagnose(&target, timeout).await;
+    }
+    let url = match Url::parse(url) {
+        Ok(url) => url,
+        Err(e) => return vec![format!("URL: {}", e)],
+    };
+    let Some(host) = url.host_str() else {
+        return vec!["URL: no host to diagnose".to_string()];
+    };
+    let ntp = url.scheme() == "ntp";
+    let port = url.port().or(ntp.then_some(NTP_PORT)).or(url.port_or_known_default()).unwrap_or(80);
+
+    let mut findings = Vec::new();
+
+    let addrs = match tokio::time::timeout(timeout, tokio::net::lookup_host((host, port))).await {
+        Ok(Ok(addrs)) => addrs.collect::<Vec<SocketAddr>>(),
+        Ok(Err(e)) => {
+            findings.push(format!("DNS: {} failed to resolve: {}", host, e));
+            return findings;
+        }
+        Err(_) => {
+            findings.push(format!("DNS: {} timed out", host));
+            return findings;
+        }
+    };
+    let shown: Vec<String> = addrs.iter().take(3).map(|a| a.ip().to_string()).collect();
+    findings.push(format!("DNS: {} -> {}", host, shown.join(", ")));
+
+    let Some(&addr) = addrs.first() else {
+        return findings;
+    };
+    // NTP is UDP: no connection to try short of the query that already failed,
+    // and a port sweep has just tried its connections
+    if ntp || url.scheme() == "tcp" {
+        findings.push(trace_route(&addr.ip().to_string()).await);
+        return findings;
+    }
+    let start = Instant::now();
+    let connected = match tokio::time::timeout(timeout, TcpStream::connect(addr)).await {
+        Ok(Ok(_)) => {
+            findings.push(format!("TCP: connected to {} in {}ms", addr, start.elapsed().as_millis()));
+            true
+        }
+        Ok(Err(e)) => {
+            findings.push(format!("TCP: {} failed: {}", addr, e));
+            false
+        }
+        Err(_) => {
+            findings.push(format!("TCP: {} timed out", addr));
+            false
+        }
+    };
+
+    if connected && url.scheme() == "https" {
+        match tls::inspect(url.as_str(), timeout).await {
+            Ok((info, _)) => findings.push(format!("TLS: handshake ok ({}, {})", info.version, info.cipher)),
+            Err(e) => findings.push(format!("TLS: handshake failed: {:#}", e)),
+        }
+    }
+
+    findings.push(trace_route(&addr.ip().to_string()).await);
+    findings
+}
+
+/// Trace the route with mtr or traceroute, whichever is installed, and
+/// summarise how far it got
+async fn trace_route(ip: &str) -> String {
+    let attempts = [
+        ("mtr", vec!["--report", "--report-cycles", "1", "--no-dns", "--max-ttl", MAX_HOPS, ip]),
+        ("traceroute", vec!["-n", "-q", "1", "-w", "1", "-m", MAX_HOPS, ip]),
+    ];
+
+    for (program, args) in attempts {
+        let output = Command::new(program)
+            .args(&args)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .output();
+        match tokio::time::timeout(ROUTE_TIMEOUT, output).await {
+            Ok(Ok(output)) if output.status.success() => {
+                return summarise_route(program, &String::from_utf8_lossy(&output.stdout), ip);
+            }
+            Ok(Ok(_)) => return format!("Route: {} failed", program),
+            Ok(Err(e)) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Ok(Err(e)) => return format!("Route: {} failed: {}", program, e),
+            Err(_) => return format!("Route: {} timed out", program),
+        }
+    }
+
+    "Route: install mtr or traceroute to trace routes".to_string()
+}
+
+/// Reduce trace output to the hop count and the last hop that replied
+fn summarise_route(program: &str, output: &str, target: &str) -> String {
+    // Hop lines start with the hop number ("3.|--" for mtr, "3" for traceroute)
+    let hops: Vec<Vec<&str>> = output
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .filter(|fields| {
+            fields
+                .first()
+                .is_some_and(|hop| hop.trim_end_matches("|--").trim_end_matches('.').parse::<u32>().is_ok())
+        })
+        .collect();
+
+    let last_reply = hops
+        .iter()
+        .rev()
+        .find_map(|fields| fields.get(1).filter(|&&field| field != "*" && field != "???"));
+
+    match last_reply {
+        Some(&hop) if hop == target => format!("Route: reached {} in {} hops ({})", target, hops.len(), program),
+        Some(hop) => format!(
+            "Route: last reply from {} of {} hops, {} never answered ({})",
+            hop,
+            hops.len(),
+            target,
+            program
+        ),
+        None => format!("Route: no hop replied ({})", program),
+    }
+}
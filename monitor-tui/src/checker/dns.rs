@@ -0,0 +1,91 @@
+use crate::config::{DnsConfig, DnsResolverKind};
+use anyhow::{Context, Result};
+use hickory_resolver::config::{NameServerConfigGroup, ResolverConfig, ResolverOpts};
+use hickory_resolver::{system_conf, TokioAsyncResolver};
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+/// Resolver for HTTP checks, configured by the `[dns]` section
+pub struct DnsResolver {
+    resolver: TokioAsyncResolver,
+}
+
+impl DnsResolver {
+    /// Build a resolver; `bypass_cache` disables answer caching entirely
+    pub fn new(dns: &DnsConfig, bypass_cache: bool) -> Result<Self> {
+        let (config, mut opts) = match dns.resolver {
+            DnsResolverKind::System => {
+                system_conf::read_system_conf().context("Failed to read system DNS configuration")?
+            }
+            DnsResolverKind::Custom => {
+                let mut servers = NameServerConfigGroup::new();
+                for server in &dns.servers {
+                    let addr = parse_server(server, 53)?;
+                    servers.merge(NameServerConfigGroup::from_ips_clear(&[addr.ip()], addr.port(), true));
+                }
+                (ResolverConfig::from_parts(None, vec![], servers), ResolverOpts::default())
+            }
+            DnsResolverKind::Doh => {
+                let config = if dns.servers.is_empty() {
+                    ResolverConfig::cloudflare_https()
+                } else {
+                    let tls_name = dns.tls_name.clone().unwrap_or_default();
+                    let mut servers = NameServerConfigGroup::new();
+                    for server in &dns.servers {
+                        let addr = parse_server(server, 443)?;
+                        servers.merge(NameServerConfigGroup::from_ips_https(
+                            &[addr.ip()],
+                            addr.port(),
+                            tls_name.clone(),
+                            true,
+                        ));
+                    }
+                    ResolverConfig::from_parts(None, vec![], servers)
+                };
+                (config, ResolverOpts::default())
+            }
+        };
+
+        match dns.cache_ttl {
+            _ if bypass_cache => opts.cache_size = 0,
+            Some(0) => opts.cache_size = 0,
+            // Pin every cached answer to the same lifetime regardless of the record's TTL
+            Some(ttl) => {
+                let ttl = Duration::from_secs(ttl);
+                opts.positive_min_ttl = Some(ttl);
+                opts.positive_max_ttl = Some(ttl);
+                opts.negative_min_ttl = Some(ttl);
+                opts.negative_max_ttl = Some(ttl);
+            }
+            None => {}
+        }
+
+        Ok(Self {
+            resolver: TokioAsyncResolver::tokio(config, opts),
+        })
+    }
+}
+
+impl Resolve for DnsResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = self.resolver.clone();
+        Box::pin(async move {
+            let lookup = resolver.lookup_ip(name.as_str()).await?;
+            // reqwest fills in the port from the URL
+            let addrs: Addrs = Box::new(lookup.into_iter().map(|ip| SocketAddr::new(ip, 0)));
+            Ok(addrs)
+        })
+    }
+}
+
+/// Parse "1.1.1.1", "1.1.1.1:5353" or "[2606:4700::1111]:53"
+pub fn parse_server(server: &str, default_port: u16) -> Result<SocketAddr> {
+    if let Ok(ip) = server.parse::<IpAddr>() {
+        return Ok(SocketAddr::new(ip, default_port));
+    }
+    server
+        .parse::<SocketAddr>()
+        .with_context(|| format!("Invalid DNS server '{}' - expected an IP address with optional port", server))
+}
@@ -0,0 +1,85 @@
+use crate::config::SiteConfig;
+use super::http::{request_failure, with_site_headers};
+use super::types::CheckResult;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+/// Posts a GraphQL query and fails the check on resolver errors, which many
+/// gateways report with a 200 status
+pub struct GraphqlChecker {
+    client: Client,
+    timeout: Duration,
+}
+
+impl GraphqlChecker {
+    pub fn new(client: Client, timeout_secs: u64) -> Self {
+        Self {
+            client,
+            timeout: Duration::from_secs(timeout_secs),
+        }
+    }
+
+    pub async fn check(&self, site: &SiteConfig, warning_threshold_ms: Option<u64>) -> CheckResult {
+        let start = Instant::now();
+        let body = json!({
+            "query": site.graphql_query.as_deref().unwrap_or_default(),
+            "variables": site.graphql_variables.clone().unwrap_or(Value::Null),
+        });
+
+        let response = match with_site_headers(self.client.post(&site.url), site).json(&body).timeout(self.timeout).send().await {
+            Ok(response) => response,
+            Err(e) => return request_failure(&e),
+        };
+        let status_code = response.status().as_u16();
+
+        let payload: Value = match response.json().await {
+            Ok(payload) => payload,
+            Err(e) if e.is_timeout() => return request_failure(&e),
+            Err(e) => return CheckResult::new_down(format!("Invalid GraphQL response: {}", e)),
+        };
+        let elapsed = start.elapsed();
+
+        if let Some(error) = first_error(&payload) {
+            return CheckResult::new_down(format!("GraphQL error: {}", error));
+        }
+
+        for (path, expected) in &site.graphql_expect {
+            let actual = lookup(&payload["data"], path);
+            if actual != Some(expected) {
+                let actual = actual.map(Value::to_string).unwrap_or_else(|| "missing".to_string());
+                return CheckResult::new_down(format!(
+                    "GraphQL data.{} is {}, expected {}",
+                    path, actual, expected
+                ));
+            }
+        }
+
+        CheckResult::new_success(
+            elapsed.as_millis() as u64,
+            status_code,
+            site.code_status(status_code),
+            warning_threshold_ms,
+        )
+    }
+}
+
+/// Message of the first entry in a non-empty `errors` array
+fn first_error(payload: &Value) -> Option<String> {
+    let error = payload.get("errors")?.as_array()?.first()?;
+    Some(
+        error
+            .get("message")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string()),
+    )
+}
+
+/// Follow a dotted path ("health.checks.0.ok") through objects and arrays
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |value, key| match value {
+        Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+        _ => value.get(key),
+    })
+}
@@ -0,0 +1,99 @@
+use crate::config::{Config, SiteConfig, SiteKind};
+use super::types::CheckResult;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Last ping per heartbeat token, shared by the HTTP server and the checkers.
+/// Times are monotonic so wall-clock jumps can't expire or extend a grace period.
+#[derive(Clone)]
+pub struct HeartbeatRegistry {
+    pings: Arc<Mutex<HashMap<String, Pings>>>,
+}
+
+/// When a token started being waited on, and when it was last pinged
+#[derive(Clone, Copy)]
+struct Pings {
+    since: Instant,
+    last: Option<Instant>,
+}
+
+impl HeartbeatRegistry {
+    pub fn new(config: &Config) -> Self {
+        let registry = Self {
+            pings: Arc::new(Mutex::new(HashMap::new())),
+        };
+        registry.sync(config);
+        registry
+    }
+
+    /// Wait on the heartbeat sites of a reloaded config, keeping the pings of
+    /// those already known; new ones get a grace period from now
+    pub fn sync(&self, config: &Config) {
+        let mut pings = self.pings.lock().unwrap();
+        let now = Instant::now();
+        let tokens: HashMap<String, Pings> = config
+            .sites
+            .iter()
+            .filter(|site| site.kind == SiteKind::Heartbeat)
+            .map(|site| {
+                let token = site.heartbeat_token();
+                let known = pings.get(&token).copied().unwrap_or(Pings { since: now, last: None });
+                (token, known)
+            })
+            .collect();
+        *pings = tokens;
+    }
+
+    /// Record a ping; returns false for tokens no site is waiting on
+    pub fn record_ping(&self, token: &str) -> bool {
+        let mut pings = self.pings.lock().unwrap();
+        match pings.get_mut(token) {
+            Some(known) => {
+                known.last = Some(Instant::now());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn pings(&self, token: &str) -> Option<Pings> {
+        self.pings.lock().unwrap().get(token).copied()
+    }
+}
+
+/// Passive check: Up while pings keep arriving within the grace period
+pub struct HeartbeatChecker {
+    registry: HeartbeatRegistry,
+    token: String,
+    grace_period_secs: u64,
+}
+
+impl HeartbeatChecker {
+    pub fn new(site: &SiteConfig, registry: HeartbeatRegistry) -> Self {
+        Self {
+            registry,
+            token: site.heartbeat_token(),
+            grace_period_secs: site.grace_period.unwrap_or_default(),
+        }
+    }
+
+    pub fn check(&self) -> CheckResult {
+        let pings = self.registry.pings(&self.token);
+        let last_ping = pings.and_then(|p| p.last);
+        // Before the first ping, give the job one grace period from when
+        // the site started being watched
+        let reference = last_ping.or(pings.map(|p| p.since)).unwrap_or_else(Instant::now);
+        let silent_for = reference.elapsed().as_secs();
+
+        if silent_for <= self.grace_period_secs {
+            return CheckResult::new_up();
+        }
+
+        let error = match last_ping {
+            Some(_) => format!("No heartbeat for {}s (grace period {}s)", silent_for, self.grace_period_secs),
+            None => format!("No heartbeat received since monitoring started ({}s ago)", silent_for),
+        };
+        CheckResult::new_down(error)
+    }
+}
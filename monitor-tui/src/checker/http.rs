@@ -1,49 +1,498 @@
-use crate::config::SiteConfig;
-use super::types::CheckResult;
-use reqwest::Client;
+use crate::config::{Config, SiteConfig, DIRECT_EGRESS};
+use super::dns::DnsResolver;
+use super::revocation::RevocationChecker;
+use super::tls;
+use super::tunnel::{SshTunnel, TunnelError};
+use super::types::{CheckResult, FailureKind, Revocation, Status};
+use super::unix::{self, UnixTarget};
+use super::xml;
+use anyhow::Result;
+use chrono::{DateTime, TimeDelta, Utc};
+use reqwest::header::USER_AGENT;
+use regex::Regex;
+use reqwest::{Client, Proxy, RequestBuilder, StatusCode};
+use url::Url;
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+/// Characters of an unexpected response's body kept with the result
+const BODY_SNIPPET_CHARS: usize = 200;
+
+/// User-Agent sent unless the config sets one
+pub const DEFAULT_USER_AGENT: &str = concat!("monitor-tui/", env!("CARGO_PKG_VERSION"));
+
+/// Clients shared across checkers, one per connection/DNS/egress policy in use.
+/// Clones are cheap handles to the same pool, so sites with the same policy
+/// reuse connections and DNS lookups.
+pub struct HttpClients {
+    config: Arc<Config>,
+    clients: HashMap<ClientPolicy, Client>,
+    /// SSH tunnels by jump host, shared by every site going through it
+    tunnels: HashMap<String, Arc<SshTunnel>>,
+}
+
+/// How a client treats connections and DNS answers, which egress profile it
+/// uses, and which jump host it tunnels through
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ClientPolicy {
+    pooled: bool,
+    cached_dns: bool,
+    egress: Option<String>,
+    via: Option<String>,
+}
+
+impl HttpClients {
+    pub fn new(config: Arc<Config>) -> Self {
+        Self {
+            config,
+            clients: HashMap::new(),
+            tunnels: HashMap::new(),
+        }
+    }
+
+    /// Fresh clients for a reloaded config, going through the same SSH
+    /// tunnels, so checks kept across the reload still reach their jump hosts
+    pub fn reloaded(&self, config: Arc<Config>) -> Self {
+        Self {
+            config,
+            clients: HashMap::new(),
+            tunnels: self.tunnels.clone(),
+        }
+    }
+
+    /// Stop the tunnels no site in `config` goes through any more
+    pub fn close_unused_tunnels(&mut self, config: &Config) {
+        self.tunnels
+            .retain(|host, _| config.sites.iter().any(|site| site.via.as_deref() == Some(host.as_str())));
+    }
+
+    /// The client matching this site's pooling and DNS settings, going out
+    /// through `egress` when given, or through the site's SSH jump host
+    pub fn for_site(&mut self, site: &SiteConfig, egress: Option<&str>) -> Result<Client> {
+        // A pooled connection would skip the lookup entirely, so bypassing the
+        // DNS cache also means fresh connections
+        let policy = ClientPolicy {
+            pooled: !site.disable_pooling && !site.dns_bypass_cache,
+            cached_dns: !site.dns_bypass_cache,
+            egress: egress.map(str::to_string),
+            via: site.via.clone(),
+        };
+        if let Some(client) = self.clients.get(&policy) {
+            return Ok(client.clone());
+        }
+        let tunnel = match &policy.via {
+            Some(host) => Some(self.tunnel(host)?.proxy_url()),
+            None => None,
+        };
+        let client = Self::build(&self.config, &policy, tunnel.as_deref())?;
+        self.clients.insert(policy, client.clone());
+        Ok(client)
+    }
+
+    /// Why the site's SSH tunnel last failed, when it goes through one
+    pub fn tunnel_error(&self, site: &SiteConfig) -> Option<TunnelError> {
+        self.tunnels.get(site.via.as_deref()?).map(|tunnel| tunnel.error())
+    }
+
+    /// The tunnel through `host`, opened the first time it's needed
+    fn tunnel(&mut self, host: &str) -> Result<&SshTunnel> {
+        if !self.tunnels.contains_key(host) {
+            let tunnel = SshTunnel::open(host)?;
+            self.tunnels.insert(host.to_string(), Arc::new(tunnel));
+        }
+        Ok(&self.tunnels[host])
+    }
+
+    fn build(config: &Config, policy: &ClientPolicy, tunnel: Option<&str>) -> Result<Client> {
+        let settings = &config.settings;
+        let user_agent = settings.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT);
+        let mut builder = Client::builder().user_agent(user_agent);
+
+        if let Some(dns) = &config.dns {
+            builder = builder.dns_resolver(Arc::new(DnsResolver::new(dns, !policy.cached_dns)?));
+        }
+        if let Some(secs) = settings.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(Duration::from_secs(secs));
+        }
+        if let Some(max) = settings.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(secs) = settings.tcp_keepalive {
+            builder = builder.tcp_keepalive(Duration::from_secs(secs));
+        }
+        if !policy.pooled {
+            builder = builder.pool_max_idle_per_host(0);
+        }
+        match policy.egress.as_deref() {
+            Some(DIRECT_EGRESS) => builder = builder.no_proxy(),
+            Some(name) => {
+                if let Some(profile) = config.egress.get(name) {
+                    builder = builder.proxy(Proxy::all(&profile.proxy)?);
+                }
+            }
+            None => {}
+        }
+        // The jump host does the DNS lookups for tunnelled sites
+        if let Some(proxy) = tunnel {
+            builder = builder.proxy(Proxy::all(proxy)?);
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+/// Sends the site's own User-Agent, if it has one, in place of the client's,
+/// and the site's headers
+pub fn with_site_headers(mut request: RequestBuilder, site: &SiteConfig) -> RequestBuilder {
+    if let Some(agent) = &site.user_agent {
+        request = request.header(USER_AGENT, agent);
+    }
+    for (name, value) in &site.headers {
+        request = request.header(name, value);
+    }
+    request
+}
+
 pub struct HttpChecker {
     client: Client,
+    /// Sent over unix sockets, which don't go through the client
+    user_agent: String,
+    timeout: Duration,
+    /// Probe HTTPS sites for their negotiated TLS version and cipher
+    inspect_tls: bool,
+    /// Ask whether the certificate was revoked; implies inspecting TLS
+    revocation: Option<RevocationChecker>,
+    /// The site's expected_body_regex, compiled once rather than every check
+    body_regex: Option<Regex>,
+    /// XPaths of the site's xml_assertions, compiled once too
+    xpaths: Vec<xml::CompiledXPath>,
 }
 
 impl HttpChecker {
-    pub fn new(timeout_secs: u64) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(timeout_secs))
-            .user_agent("monitor-tui/0.1.0")
-            .build()
-            .expect("Failed to build HTTP client");
+    pub fn new(
+        client: Client,
+        user_agent: &str,
+        timeout_secs: u64,
+        inspect_tls: bool,
+        check_revocation: bool,
+        body_regex: Option<Regex>,
+        xpaths: Vec<xml::CompiledXPath>,
+    ) -> Self {
+        Self {
+            client,
+            user_agent: user_agent.to_string(),
+            timeout: Duration::from_secs(timeout_secs),
+            inspect_tls: inspect_tls || check_revocation,
+            revocation: check_revocation.then(RevocationChecker::default),
+            body_regex,
+            xpaths,
+        }
+    }
+
+    pub async fn check(&mut self, site: &SiteConfig, warning_threshold_ms: Option<u64>) -> CheckResult {
+        // The TLS probe runs alongside the request so it doesn't add to the response time
+        let inspect = self.inspect_tls && site.url.starts_with("https://");
+        let (mut result, handshake) = tokio::join!(self.request(site, warning_threshold_ms), async {
+            if inspect { tls::inspect(&site.url, self.timeout).await.ok() } else { None }
+        });
+        let tls = match (handshake, &mut self.revocation) {
+            (Some((mut info, chain)), Some(revocation)) => {
+                info.revocation = Some(revocation.check(&self.client, &chain, self.timeout).await);
+                Some(info)
+            }
+            (handshake, _) => handshake.map(|(info, _)| info),
+        };
 
-        Self { client }
+        if let Some(info) = &tls
+            && result.status == Status::Up
+            && let Some(min) = site.min_tls_version.as_deref()
+            && tls::version_rank(&info.version) < tls::version_rank(min)
+        {
+            result.status = Status::Warning;
+            result.error_message = Some(format!("Negotiated {}, below minimum TLS {}", info.version, min));
+        }
+        // A revoked certificate, or one whose status can't be had, is worth a look
+        if let Some(revocation) = tls.as_ref().and_then(|info| info.revocation.as_ref())
+            && result.status == Status::Up
+            && !matches!(revocation, Revocation::Good { .. })
+        {
+            result.status = Status::Warning;
+            result.error_message = Some(format!("Certificate {}", revocation.describe()));
+            result.failure = Some(FailureKind::Certificate);
+        }
+        result.tls = tls;
+
+        // Assets only matter once the page itself is fine
+        if result.status == Status::Up
+            && let Some(failure) = self.check_assets(site).await
+        {
+            result.status = Status::Warning;
+            result.error_message = Some(failure);
+        }
+        result
+    }
+
+    /// Request each of the site's assets at once, describing the first that's missing
+    async fn check_assets(&self, site: &SiteConfig) -> Option<String> {
+        let base = Url::parse(&site.url).ok()?;
+        let base = &base;
+        let checks = site.assets.iter().map(|asset| async move {
+            let url = base.join(asset).ok()?;
+            // Not every server implements HEAD; fall back to GET
+            let mut response = with_site_headers(self.client.head(url.clone()), site).timeout(self.timeout).send().await;
+            if response.as_ref().is_ok_and(|r| r.status() == StatusCode::METHOD_NOT_ALLOWED) {
+                response = with_site_headers(self.client.get(url), site).timeout(self.timeout).send().await;
+            }
+            match response {
+                Ok(r) if r.status().is_client_error() || r.status().is_server_error() => {
+                    Some(format!("Asset {} returned {}", asset, r.status().as_u16()))
+                }
+                Ok(_) => None,
+                Err(e) => Some(format!("Asset {}: {}", asset, describe_error(&e))),
+            }
+        });
+        futures::future::join_all(checks).await.into_iter().flatten().next()
     }
 
-    pub async fn check(&self, site: &SiteConfig, warning_threshold_ms: Option<u64>) -> CheckResult {
+    async fn request(&self, site: &SiteConfig, warning_threshold_ms: Option<u64>) -> CheckResult {
         let start = Instant::now();
 
-        match self.client.get(&site.url).send().await {
+        let socket = match UnixTarget::parse(&site.url) {
+            Ok(socket) => socket,
+            Err(e) => return CheckResult::new_down(format!("Invalid URL: {:#}", e)),
+        };
+        let url = socket.as_ref().map_or_else(|| site.url.clone(), UnixTarget::request_url);
+        let mut request = with_site_headers(self.client.request(site.http_method(), url), site);
+        if let Some(body) = &site.body {
+            request = request.body(body.clone());
+        }
+
+        let sent = match &socket {
+            Some(target) => match request.build() {
+                Ok(request) => unix::send(target, request, &self.user_agent, self.timeout).await,
+                Err(e) => Err(request_failure(&e)),
+            },
+            None => request.timeout(self.timeout).send().await.map_err(|e| request_failure(&e)),
+        };
+
+        match sent {
             Ok(response) => {
                 let elapsed = start.elapsed();
                 let status_code = response.status().as_u16();
+                let final_url = response.url().clone();
+                let retry_after = match status_code {
+                    429 | 503 => response
+                        .headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(parse_retry_after),
+                    _ => None,
+                };
+
+                let last_modified = response
+                    .headers()
+                    .get(reqwest::header::LAST_MODIFIED)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+                    .map(|date| date.with_timezone(&Utc));
 
-                CheckResult::new_success(
+                let content_type = response
+                    .headers()
+                    .get(reqwest::header::CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string);
+
+                // An unexpected status's body often says what went wrong
+                let needs_body = !site.xml_assertions.is_empty()
+                    || site.max_content_age.is_some()
+                    || site.expected_body_contains.is_some()
+                    || site.expected_body_regex.is_some();
+                let unexpected = site.code_status(status_code) != Status::Up;
+                let body = if needs_body || unexpected {
+                    match response.text().await {
+                        Ok(body) => body,
+                        Err(e) if needs_body => return request_failure(&e),
+                        Err(_) => String::new(),
+                    }
+                } else {
+                    String::new()
+                };
+                let mut result = CheckResult::new_success(
                     elapsed.as_millis() as u64,
                     status_code,
-                    site.expected_status,
+                    site.code_status(status_code),
                     warning_threshold_ms,
-                )
+                );
+                result.retry_after = retry_after;
+                if unexpected {
+                    result.body_snippet = body_snippet(&body);
+                }
+                if let Some(outside) = check_redirect_domain(site, &final_url) {
+                    result.status = Status::Down;
+                    result.error_message = Some(outside);
+                }
+                // Assertions are about the expected document, not an error page
+                if !unexpected
+                    && result.error_message.is_none()
+                    && !site.xml_assertions.is_empty()
+                    && let Err(failure) = xml::check_assertions(site, &self.xpaths, &body)
+                {
+                    result.status = Status::Down;
+                    result.error_message = Some(failure);
+                    result.body_snippet = body_snippet(&body);
+                }
+                if result.status == Status::Up
+                    && let Some(expected) = &site.expected_content_type
+                    && let Some(mismatch) = check_content_type(expected, content_type.as_deref())
+                {
+                    result.status = Status::Warning;
+                    result.error_message = Some(mismatch);
+                }
+                if result.status == Status::Up
+                    && let Some(mismatch) = check_body(site, self.body_regex.as_ref(), &body)
+                {
+                    result.status = Status::Warning;
+                    result.error_message = Some(mismatch);
+                    result.failure = Some(FailureKind::BodyMismatch);
+                    result.body_snippet = body_snippet(&body);
+                }
+                if result.status == Status::Up
+                    && let Some(hours) = site.max_content_age
+                    && let Some(stale) = check_freshness(hours, &body, last_modified)
+                {
+                    result.status = Status::Warning;
+                    result.error_message = Some(stale);
+                }
+                result
             }
-            Err(e) => {
-                let error_msg = if e.is_timeout() {
-                    "Request timeout".to_string()
-                } else if e.is_connect() {
-                    format!("Connection failed: {}", e)
-                } else {
-                    format!("Request failed: {}", e)
-                };
+            Err(failure) => failure,
+        }
+    }
+}
+
+/// Describe content older than `max_hours`, or content that can't be dated
+fn check_freshness(max_hours: u64, body: &str, last_modified: Option<DateTime<Utc>>) -> Option<String> {
+    let Some(updated) = xml::newest_feed_entry(body).or(last_modified) else {
+        return Some("No feed entry dates or Last-Modified header to judge freshness by".to_string());
+    };
+    let age = Utc::now() - updated;
+    (age > TimeDelta::hours(max_hours as i64))
+        .then(|| format!("Content last updated {}h ago, over the {}h limit", age.num_hours(), max_hours))
+}
+
+/// Describe a body without the site's expected text or regex match
+fn check_body(site: &SiteConfig, regex: Option<&Regex>, body: &str) -> Option<String> {
+    if let Some(text) = &site.expected_body_contains
+        && !body.contains(text.as_str())
+    {
+        return Some(format!("Body doesn't contain {:?}", text));
+    }
+    if let Some(regex) = regex
+        && !regex.is_match(body)
+    {
+        return Some(format!("Body doesn't match /{}/", regex.as_str()));
+    }
+    None
+}
 
-                CheckResult::new_down(error_msg)
+/// The body's first characters with whitespace collapsed, if it has any
+fn body_snippet(body: &str) -> Option<String> {
+    let collapsed = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    let snippet: String = collapsed.chars().take(BODY_SNIPPET_CHARS).collect();
+    (!snippet.is_empty()).then_some(snippet)
+}
+
+/// Describe redirects that ended on a host outside the site's allowed
+/// domains; the host of the configured URL is always allowed
+fn check_redirect_domain(site: &SiteConfig, final_url: &Url) -> Option<String> {
+    if site.allowed_redirect_domains.is_empty() {
+        return None;
+    }
+    let host = final_url.host_str().unwrap_or_default().to_ascii_lowercase();
+    let own_host = Url::parse(&site.url).ok().and_then(|url| url.host_str().map(str::to_ascii_lowercase));
+    let allowed = own_host.as_deref() == Some(host.as_str())
+        || site.allowed_redirect_domains.iter().any(|domain| {
+            let domain = domain.to_ascii_lowercase();
+            match domain.strip_prefix("*.") {
+                Some(parent) => host.strip_suffix(parent).is_some_and(|sub| sub.ends_with('.')),
+                None => host == domain,
             }
-        }
+        });
+    (!allowed).then(|| format!("Redirected to {}, outside the allowed domains", final_url))
+}
+
+/// Describe a Content-Type that isn't the expected media type; "type/*"
+/// accepts any subtype
+fn check_content_type(expected: &str, actual: Option<&str>) -> Option<String> {
+    let Some(actual) = actual else {
+        return Some(format!("No Content-Type header, expected {}", expected));
+    };
+    let media_type = actual.split(';').next().unwrap_or_default().trim();
+    let matches = match expected.strip_suffix("/*") {
+        Some(kind) => media_type.split('/').next().is_some_and(|k| k.eq_ignore_ascii_case(kind)),
+        None => media_type.eq_ignore_ascii_case(expected.trim()),
+    };
+    (!matches).then(|| format!("Content-Type {}, expected {}", media_type, expected))
+}
+
+/// Wait asked for by a Retry-After header: delay seconds or an HTTP date
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(secs) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value.trim()).ok()?;
+    Some((at.with_timezone(&Utc) - Utc::now()).to_std().unwrap_or_default())
+}
+
+/// Down result for a request that failed, marked with how it failed
+pub fn request_failure(e: &reqwest::Error) -> CheckResult {
+    let kind = if e.is_timeout() {
+        FailureKind::Timeout
+    } else if e.is_connect() {
+        FailureKind::Connection
+    } else {
+        FailureKind::Request
+    };
+    CheckResult::new_failed(kind, describe_error(e))
+}
+
+/// Short description of why a request failed
+pub fn describe_error(e: &reqwest::Error) -> String {
+    if e.is_timeout() {
+        "Request timeout".to_string()
+    } else if e.is_connect() {
+        format!("Connection failed: {}", e)
+    } else {
+        format!("Request failed: {}", e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[tokio::test]
+    async fn kept_via_site_resolves_its_tunnel_after_reload() {
+        let contents = r#"
+            [settings]
+
+            [[sites]]
+            name = "internal"
+            url = "http://intranet.lan/"
+            via = "bastion"
+        "#;
+        let config = Arc::new(Config::parse(contents, Path::new("sites.toml")).unwrap());
+        let site = &config.sites[0];
+        let mut clients = HttpClients::new(Arc::clone(&config));
+        clients.for_site(site, None).unwrap();
+        let proxy = clients.tunnels["bastion"].proxy_url();
+
+        let mut reloaded = clients.reloaded(Arc::clone(&config));
+        drop(clients);
+        reloaded.for_site(site, None).unwrap();
+        reloaded.close_unused_tunnels(&config);
+        assert_eq!(reloaded.tunnels["bastion"].proxy_url(), proxy);
     }
 }
@@ -1,48 +1,459 @@
+pub mod demo;
+pub mod diagnostics;
+pub mod dns;
+pub mod graphql;
+pub mod heartbeat;
 pub mod http;
+pub mod ntp;
+pub mod ports;
+pub mod baseline;
+pub mod blackout;
+pub mod revocation;
+pub mod tls;
+pub mod tunnel;
 pub mod types;
+pub mod unix;
+pub mod xml;
 
-use crate::config::SiteConfig;
+use crate::config::{ResultOverflow, Settings, SiteConfig, TimeoutStatus};
+use baseline::LatencyBaseline;
+use blackout::BlackoutWindow;
+use demo::DemoChecker;
+use graphql::GraphqlChecker;
+use heartbeat::HeartbeatChecker;
 use http::HttpChecker;
-use tokio::sync::{broadcast, mpsc, watch};
+use ntp::NtpChecker;
+use ports::PortChecker;
+use tunnel::TunnelError;
+use chrono::{DateTime, Local, Utc};
+use croner::Cron;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{broadcast, mpsc, watch, Notify};
 use tokio::time::Duration;
 pub use types::{CheckResult, Status};
 
+/// Longest a Retry-After header can put off a site's next check
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(3600);
+
+/// Source of check results for a site
+pub enum Checker {
+    Http(HttpChecker),
+    Graphql(GraphqlChecker),
+    Demo(DemoChecker),
+    Heartbeat(HeartbeatChecker),
+    Ntp(NtpChecker),
+    Ports(PortChecker),
+    /// The same check through several egress profiles at once
+    Egress(Vec<(String, Checker)>),
+    /// A check through an SSH jump host, whose errors it reports
+    Via(Box<Checker>, TunnelError),
+}
+
+impl Checker {
+    /// Check the site once. With `respect_retry_after`, a location turned
+    /// away by rate limiting is left out of a multi-location verdict.
+    pub async fn check(
+        &mut self,
+        site: &SiteConfig,
+        warning_threshold_ms: Option<u64>,
+        respect_retry_after: bool,
+    ) -> CheckResult {
+        match self {
+            Checker::Http(checker) => checker.check(site, warning_threshold_ms).await,
+            Checker::Graphql(checker) => checker.check(site, warning_threshold_ms).await,
+            Checker::Demo(checker) => checker.check(site, warning_threshold_ms).await,
+            Checker::Heartbeat(checker) => checker.check(),
+            Checker::Ntp(checker) => checker.check(site, warning_threshold_ms).await,
+            Checker::Ports(checker) => checker.check(site, warning_threshold_ms).await,
+            Checker::Egress(locations) => {
+                let checks = locations.iter_mut().map(|(name, checker)| async move {
+                    let mut result = Box::pin(checker.check(site, warning_threshold_ms, respect_retry_after)).await;
+                    if respect_retry_after && result.is_rate_limited() {
+                        result.status = Status::RateLimited;
+                    }
+                    (name.clone(), result)
+                });
+                CheckResult::from_locations(futures::future::join_all(checks).await)
+            }
+            Checker::Via(checker, tunnel) => {
+                let mut result = Box::pin(checker.check(site, warning_threshold_ms, respect_retry_after)).await;
+                tunnel.explain(&mut result);
+                result
+            }
+        }
+    }
+}
+
+/// When a site's checks run
+#[derive(Debug, Clone)]
+pub struct CheckSchedule {
+    pub interval: Duration,
+    /// Delay before the first check, so sites don't all fire at once on startup
+    pub initial_delay: Duration,
+    /// Each sleep is randomized by up to +/- this percentage of the interval
+    pub jitter_percent: u64,
+    /// Longest interval to back off to while the site stays Down
+    pub backoff_cap: Option<Duration>,
+    /// Consecutive Down checks before backoff starts
+    pub backoff_after: u32,
+    /// Fastest and slowest interval when adapting to stability
+    pub adaptive: Option<(Duration, Duration)>,
+    /// Cron expression that replaces the interval entirely
+    pub cron: Option<Cron>,
+    /// Windows during which checks are skipped instead of run
+    pub blackouts: Vec<BlackoutWindow>,
+    /// Interval set while running, in place of the interval, backoff and adaptation
+    pub pinned: Option<Duration>,
+}
+
+/// Checks right after a status change that run at the fastest adaptive interval
+const ADAPTIVE_SETTLE_CHECKS: u32 = 5;
+
+/// Stable checks it takes for the adaptive interval to double
+const ADAPTIVE_DOUBLING_CHECKS: f64 = 10.0;
+
+/// How long a site has held its current status
+#[derive(Debug, Default)]
+struct Stability {
+    status: Option<Status>,
+    /// Checks in a row with the current status
+    streak: u32,
+    /// Whether the current streak began with a status change (not the first check)
+    changed: bool,
+}
+
+impl Stability {
+    fn record(&mut self, status: &Status) {
+        if self.status.as_ref() == Some(status) {
+            self.streak = self.streak.saturating_add(1);
+        } else {
+            self.changed = self.status.is_some();
+            self.status = Some(status.clone());
+            self.streak = 1;
+        }
+    }
+
+    fn consecutive_down(&self) -> u32 {
+        if self.status == Some(Status::Down) { self.streak } else { 0 }
+    }
+}
+
+impl CheckSchedule {
+    /// Schedule for the `index`-th of `total` sites
+    pub fn for_site(site: &SiteConfig, settings: &Settings, index: usize, total: usize) -> Self {
+        let interval = Duration::from_secs(site.check_interval.unwrap_or(settings.refresh_interval));
+
+        let cron = site.schedule.as_deref().and_then(|expr| parse_cron(expr).ok());
+
+        // Spread first checks evenly across the first interval; cron sites wait for their first slot
+        let initial_delay = if let Some(cron) = &cron {
+            until_next(cron).unwrap_or(interval)
+        } else if settings.stagger_start && total > 1 {
+            interval.mul_f64(index as f64 / total as f64)
+        } else {
+            Duration::ZERO
+        };
+
+        let backoff_cap = site
+            .backoff_max_interval
+            .or(settings.backoff_max_interval)
+            .filter(|&secs| secs > 0)
+            .map(Duration::from_secs);
+
+        let adaptive = site
+            .adaptive_intervals
+            .unwrap_or(settings.adaptive_intervals)
+            .then(|| {
+                let min = settings
+                    .adaptive_min_interval
+                    .map(Duration::from_secs)
+                    .unwrap_or_else(|| (interval / 4).max(Duration::from_secs(1)));
+                let max = settings
+                    .adaptive_max_interval
+                    .map(Duration::from_secs)
+                    .unwrap_or(interval * 4);
+                (min.min(max), max)
+            });
+
+        Self {
+            interval,
+            initial_delay,
+            jitter_percent: settings.interval_jitter_percent,
+            backoff_cap,
+            backoff_after: settings.backoff_after_failures.max(1),
+            adaptive,
+            cron,
+            blackouts: site.blackouts.iter().filter_map(|b| BlackoutWindow::parse(b).ok()).collect(),
+            pinned: None,
+        }
+    }
+
+    /// Interval after `consecutive_down` Down checks in a row: doubles for each
+    /// failure past the threshold, up to the cap
+    fn backed_off_interval(&self, consecutive_down: u32) -> Duration {
+        match self.backoff_cap {
+            Some(cap) if consecutive_down >= self.backoff_after => {
+                let doublings = (consecutive_down - self.backoff_after + 1).min(16);
+                self.interval.saturating_mul(1 << doublings).min(cap.max(self.interval))
+            }
+            _ => self.interval,
+        }
+    }
+
+    /// Interval from stability alone: fastest right after a change, then
+    /// growing from the normal interval the longer the status holds
+    fn adaptive_interval(&self, stability: &Stability) -> Duration {
+        let Some((min, max)) = self.adaptive else {
+            return self.interval;
+        };
+        if stability.changed && stability.streak <= ADAPTIVE_SETTLE_CHECKS {
+            return min;
+        }
+        let stable_checks = stability.streak.saturating_sub(ADAPTIVE_SETTLE_CHECKS) as f64;
+        let growth = 2f64.powf(stable_checks / ADAPTIVE_DOUBLING_CHECKS).min(1e6);
+        self.interval.mul_f64(growth).clamp(min, max)
+    }
+
+    /// Whether checks are currently blacked out
+    fn in_blackout(&self) -> bool {
+        let now = Local::now();
+        self.blackouts.iter().any(|window| window.contains(now))
+    }
+
+    /// Sleep before the next check, with backoff, adaptation and jitter applied
+    fn next_delay(&self, stability: &Stability) -> Duration {
+        if let Some(cron) = &self.cron {
+            return until_next(cron).unwrap_or(self.interval);
+        }
+        let consecutive_down = stability.consecutive_down();
+        let interval = if let Some(pinned) = self.pinned {
+            pinned
+        } else if self.backoff_cap.is_some() && consecutive_down >= self.backoff_after {
+            self.backed_off_interval(consecutive_down)
+        } else {
+            self.adaptive_interval(stability)
+        };
+        if self.jitter_percent == 0 {
+            return interval;
+        }
+        let spread = interval.mul_f64(self.jitter_percent as f64 / 100.0);
+        interval - spread + spread.mul_f64(fastrand::f64() * 2.0)
+    }
+}
+
+/// Parse a five-field (or six, with seconds) cron expression
+pub fn parse_cron(expr: &str) -> anyhow::Result<Cron> {
+    Cron::new(expr)
+        .with_seconds_optional()
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid cron schedule '{}': {}", expr, e))
+}
+
+/// Time until the expression next fires, in local time
+fn until_next(cron: &Cron) -> Option<Duration> {
+    let now = Local::now();
+    let next = cron.find_next_occurrence(&now, false).ok()?;
+    (next - now).to_std().ok()
+}
+
+/// Counters for results that didn't go straight through the queue
+#[derive(Debug, Default)]
+pub struct QueueStats {
+    /// Results discarded because the queue was full
+    pub dropped: AtomicU64,
+    /// Sends that had to wait for room
+    pub delayed: AtomicU64,
+}
+
+/// What a checker task reports about its site
+#[derive(Debug)]
+pub enum CheckEvent {
+    /// A check has gone out and its result is pending
+    Started,
+    /// A check has finished, and the next one is due after the delay
+    Finished(Box<CheckResult>, Duration),
+    /// Diagnostics findings for the Down result checked at that time, sent
+    /// after it so they don't hold up its alert
+    Diagnosed(DateTime<Utc>, Vec<String>),
+}
+
+/// Sending half of the result queue, applying the configured overflow policy
+#[derive(Clone)]
+pub struct ResultSender {
+    tx: mpsc::Sender<(String, CheckEvent)>,
+    overflow: ResultOverflow,
+    stats: Arc<QueueStats>,
+}
+
+impl ResultSender {
+    pub fn new(tx: mpsc::Sender<(String, CheckEvent)>, overflow: ResultOverflow, stats: Arc<QueueStats>) -> Self {
+        Self { tx, overflow, stats }
+    }
+
+    /// Announce a check; with the queue full it isn't worth waiting for
+    fn started(&self, site_name: String) {
+        let _ = self.tx.try_send((site_name, CheckEvent::Started));
+    }
+
+    async fn send(&self, site_name: String, result: CheckResult, next_check: Duration) {
+        self.deliver(site_name, CheckEvent::Finished(Box::new(result), next_check)).await;
+    }
+
+    async fn diagnosed(&self, site_name: String, checked_at: DateTime<Utc>, findings: Vec<String>) {
+        self.deliver(site_name, CheckEvent::Diagnosed(checked_at, findings)).await;
+    }
+
+    async fn deliver(&self, site_name: String, event: CheckEvent) {
+        // A closed channel means the UI is shutting down; nothing to report
+        match self.tx.try_send((site_name, event)) {
+            Ok(()) | Err(TrySendError::Closed(_)) => {}
+            Err(TrySendError::Full(item)) => match self.overflow {
+                ResultOverflow::Drop => {
+                    self.stats.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                ResultOverflow::Block => {
+                    self.stats.delayed.fetch_add(1, Ordering::Relaxed);
+                    let _ = self.tx.send(item).await;
+                }
+            },
+        }
+    }
+}
+
+/// What the main loop tells every checker task
+pub struct TaskSignals {
+    pub shutdown: watch::Receiver<bool>,
+    /// Check now instead of waiting out the interval
+    pub force_refresh: broadcast::Receiver<()>,
+    /// Check this site now; unlike `force_refresh`, for this site alone
+    pub refresh: Arc<Notify>,
+    /// While true, no checks are made
+    pub paused: watch::Receiver<bool>,
+    /// Interval set while running; None follows the schedule
+    pub interval: watch::Receiver<Option<Duration>>,
+}
+
 /// Spawn a background task that continuously checks a site
 pub fn spawn_checker_task(
     site: SiteConfig,
-    tx: mpsc::Sender<(String, CheckResult)>,
-    mut shutdown: watch::Receiver<bool>,
-    mut force_refresh: broadcast::Receiver<()>,
-    timeout_secs: u64,
-    default_interval: u64,
-    global_warning_threshold_ms: Option<u64>,
+    mut checker: Checker,
+    tx: ResultSender,
+    signals: TaskSignals,
+    mut schedule: CheckSchedule,
+    settings: &Settings,
 ) -> tokio::task::JoinHandle<()> {
+    let TaskSignals { mut shutdown, mut force_refresh, refresh, mut paused, mut interval } = signals;
     // Resolve: site override takes precedence over global setting
-    let warning_threshold_ms = site.warning_threshold_ms.or(global_warning_threshold_ms);
+    let warning_threshold_ms = site.warning_threshold_ms.or(settings.warning_threshold_ms);
+    let mut baseline = LatencyBaseline::for_site(&site, settings);
+    // Diagnostics run from here, which says nothing about a site behind a
+    // jump host or checked through egress proxies
+    let diagnose = site.diagnostics.unwrap_or(settings.diagnostics)
+        && site.via.is_none()
+        && matches!(checker, Checker::Http(_) | Checker::Graphql(_));
+    let request_timeout = Duration::from_secs(site.request_timeout.unwrap_or(settings.request_timeout));
+    let respect_retry_after = site.respect_retry_after.unwrap_or(settings.respect_retry_after);
+    let timeout_status = site.timeout_status.unwrap_or(settings.timeout_status);
 
     tokio::spawn(async move {
-        let checker = HttpChecker::new(timeout_secs);
-        let interval = Duration::from_secs(site.check_interval.unwrap_or(default_interval));
+        // Staggered start: a force refresh cuts the wait short
+        if !schedule.initial_delay.is_zero() {
+            tokio::select! {
+                _ = tokio::time::sleep(schedule.initial_delay) => {}
+                _ = force_refresh.recv() => {}
+                _ = refresh.notified() => {}
+                _ = shutdown.changed() => return,
+            }
+        }
+
+        // Status streak, for backoff and adaptive intervals
+        let mut stability = Stability::default();
+        schedule.pinned = *interval.borrow_and_update();
 
-        loop {
-            // Perform check
-            let result = checker.check(&site, warning_threshold_ms).await;
+        'checks: loop {
+            // Hold off while paused; the refresh sent on resume is already
+            // covered by checking as soon as the pause lifts
+            if *paused.borrow_and_update() {
+                tokio::select! {
+                    _ = paused.wait_for(|paused| !paused) => force_refresh = force_refresh.resubscribe(),
+                    _ = shutdown.changed() => break,
+                }
+            }
+
+            // Perform check; shutdown abandons a request still in flight
+            let mut result = if schedule.in_blackout() {
+                CheckResult::new_skipped()
+            } else {
+                tx.started(site.name.clone());
+                tokio::select! {
+                    result = checker.check(&site, warning_threshold_ms, respect_retry_after) => result,
+                    _ = shutdown.changed() => break,
+                }
+            };
+            if timeout_status == TimeoutStatus::Warning && result.status == Status::Down && result.timed_out() {
+                result.status = Status::Warning;
+            }
+            if respect_retry_after && result.is_rate_limited() {
+                result.status = Status::RateLimited;
+            } else if let Some(baseline) = &mut baseline {
+                baseline.apply(&mut result);
+            }
 
-            // Send result (ignore if channel closed)
-            let _ = tx.send((site.name.clone(), result)).await;
+            // Diagnose only the check that goes Down, not every one after it
+            let diagnose_now = diagnose && result.status == Status::Down && stability.consecutive_down() == 0;
+            let checked_at = result.timestamp;
+            // Being turned away says nothing about whether the site is failing
+            if result.status != Status::RateLimited {
+                stability.record(&result.status);
+            }
+            let mut delay = schedule.next_delay(&stability);
+            if result.status == Status::RateLimited
+                && let Some(retry_after) = result.retry_after
+            {
+                delay = delay.max(retry_after.min(MAX_RETRY_AFTER));
+            }
 
-            // Sleep, force refresh, or shutdown
+            // A blocking send must not hold up shutdown either
             tokio::select! {
-                _ = tokio::time::sleep(interval) => continue,
-                _ = force_refresh.recv() => {
-                    // Force refresh received, skip sleep and check immediately
-                    continue;
+                _ = tx.send(site.name.clone(), result, delay) => {}
+                _ = shutdown.changed() => break,
+            }
+            let slept_from = tokio::time::Instant::now();
+
+            // The result is out, so diagnostics can take their time; the
+            // findings follow it, within the wait for the next check
+            if diagnose_now {
+                let findings = tokio::select! {
+                    findings = diagnostics::run(&site.url, request_timeout) => findings,
+                    _ = shutdown.changed() => break,
+                };
+                tokio::select! {
+                    _ = tx.diagnosed(site.name.clone(), checked_at, findings) => {}
+                    _ = shutdown.changed() => break,
                 }
-                _ = shutdown.changed() => {
-                    println!("Checker task for '{}' shutting down", site.name);
-                    break;
+            }
+
+            // Sleep, force refresh, or shutdown; a new interval moves the
+            // wake-up, counted from when the result went out
+            loop {
+                tokio::select! {
+                    _ = tokio::time::sleep_until(slept_from + delay) => break,
+                    _ = force_refresh.recv() => {
+                        // Force refresh received, skip sleep and check immediately
+                        break;
+                    }
+                    _ = refresh.notified() => break,
+                    Ok(()) = interval.changed() => {
+                        schedule.pinned = *interval.borrow_and_update();
+                        delay = schedule.next_delay(&stability);
+                    }
+                    _ = shutdown.changed() => break 'checks,
                 }
             }
         }
+
+        println!("Checker task for '{}' shutting down", site.name);
     })
 }
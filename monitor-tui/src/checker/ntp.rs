@@ -0,0 +1,144 @@
+use crate::config::SiteConfig;
+use super::types::{CheckResult, FailureKind, Status};
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeDelta, Utc};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+use url::Url;
+
+/// Port NTP servers answer on unless the URL gives another
+pub const NTP_PORT: u16 = 123;
+
+/// Clock offset allowed when the site doesn't set max_clock_drift_ms
+pub const DEFAULT_MAX_DRIFT_MS: u64 = 1000;
+
+/// Seconds from the NTP epoch (1900) to the Unix epoch (1970)
+const UNIX_EPOCH_NTP_SECS: i64 = 2_208_988_800;
+
+/// Asks an NTP server for the time and warns when the local clock has
+/// drifted from it, which quietly breaks TLS and token expiry
+pub struct NtpChecker {
+    timeout: Duration,
+}
+
+/// What one exchange with the server measured
+struct Sample {
+    /// How far the server's clock is ahead of the local one
+    offset: TimeDelta,
+    /// Round trip, less the time the server took to answer
+    delay: TimeDelta,
+}
+
+impl NtpChecker {
+    pub fn new(timeout_secs: u64) -> Self {
+        Self { timeout: Duration::from_secs(timeout_secs) }
+    }
+
+    pub async fn check(&self, site: &SiteConfig, warning_threshold_ms: Option<u64>) -> CheckResult {
+        let url = match Url::parse(&site.url) {
+            Ok(url) => url,
+            Err(e) => return CheckResult::new_down(format!("Invalid URL: {}", e)),
+        };
+        let host = url.host_str().unwrap_or_default();
+        let port = url.port().unwrap_or(NTP_PORT);
+        let sample = match tokio::time::timeout(self.timeout, query(host, port)).await {
+            Ok(Ok(sample)) => sample,
+            Ok(Err(e)) => return CheckResult::new_down(format!("NTP query failed: {:#}", e)),
+            Err(_) => return CheckResult::new_timeout("Request timeout".to_string()),
+        };
+
+        let offset_ms = sample.offset.num_milliseconds();
+        let delay_ms = sample.delay.num_milliseconds().max(0) as u64;
+        let mut result = CheckResult::new_up();
+        result.response_time_ms = Some(delay_ms);
+        result.clock_offset_ms = Some(offset_ms);
+
+        let max_drift = site.max_clock_drift_ms.unwrap_or(DEFAULT_MAX_DRIFT_MS);
+        if offset_ms.unsigned_abs() > max_drift {
+            // A positive offset means the server is ahead, so the local clock is behind
+            let direction = if offset_ms > 0 { "behind" } else { "ahead of" };
+            result.status = Status::Warning;
+            result.failure = Some(FailureKind::ClockDrift);
+            result.error_message = Some(format!(
+                "Clock {}ms {} {}, over the {}ms limit",
+                offset_ms.unsigned_abs(),
+                direction,
+                host,
+                max_drift
+            ));
+        } else if warning_threshold_ms.filter(|&t| t > 0).is_some_and(|t| delay_ms > t) {
+            result.status = Status::Warning;
+        }
+        result
+    }
+}
+
+/// One SNTP exchange (RFC 4330): send our time, and from the server's
+/// receive and transmit times work out the offset and network delay
+async fn query(host: &str, port: u16) -> Result<Sample> {
+    let addr = tokio::net::lookup_host((host, port))
+        .await
+        .with_context(|| format!("{} failed to resolve", host))?
+        .next()
+        .with_context(|| format!("{} has no addresses", host))?;
+    let local = if addr.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+    let socket = UdpSocket::bind(local).await?;
+    socket.connect(addr).await?;
+
+    // Version 4, client mode; the transmit time comes back as the originate time
+    let mut request = [0u8; 48];
+    request[0] = 0b00_100_011;
+    let sent = Utc::now();
+    request[40..48].copy_from_slice(&to_ntp(sent));
+    socket.send(&request).await?;
+
+    let mut response = [0u8; 48];
+    loop {
+        let len = socket.recv(&mut response).await?;
+        let received = Utc::now();
+        // Anything else is a stray or spoofed packet; keep waiting for ours
+        if len < 48 || response[24..32] != request[40..48] {
+            continue;
+        }
+        let (leap, mode, stratum) = (response[0] >> 6, response[0] & 0b111, response[1]);
+        if mode != 4 {
+            anyhow::bail!("{} didn't answer as an NTP server", host);
+        }
+        if stratum == 0 {
+            let code = String::from_utf8_lossy(&response[12..16]).trim_end_matches('\0').to_string();
+            anyhow::bail!("{} refused with kiss code {}", host, code);
+        }
+        if leap == 3 || stratum >= 16 {
+            anyhow::bail!("{} isn't synchronized itself", host);
+        }
+
+        let server_received = from_ntp(&response[32..40], sent);
+        let server_sent = from_ntp(&response[40..48], sent);
+        return Ok(Sample {
+            offset: ((server_received - sent) + (server_sent - received)) / 2,
+            delay: (received - sent) - (server_sent - server_received),
+        });
+    }
+}
+
+/// NTP timestamp: seconds since 1900 (wrapping every 136 years) and a
+/// binary fraction of a second
+fn to_ntp(time: DateTime<Utc>) -> [u8; 8] {
+    let secs = (time.timestamp() + UNIX_EPOCH_NTP_SECS) as u32;
+    let fraction = ((time.timestamp_subsec_nanos() as u64) << 32) / 1_000_000_000;
+    let mut bytes = [0u8; 8];
+    bytes[..4].copy_from_slice(&secs.to_be_bytes());
+    bytes[4..].copy_from_slice(&(fraction as u32).to_be_bytes());
+    bytes
+}
+
+/// Read an NTP timestamp, taking the 136-year era closest to `near`
+fn from_ntp(bytes: &[u8], near: DateTime<Utc>) -> DateTime<Utc> {
+    let secs = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as i64;
+    let fraction = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as u64;
+    let era = 1i64 << 32;
+    let near_ntp = near.timestamp() + UNIX_EPOCH_NTP_SECS;
+    let secs = secs + (near_ntp - secs + era / 2).div_euclid(era) * era;
+    let nanos = ((fraction * 1_000_000_000) >> 32) as u32;
+    DateTime::from_timestamp(secs - UNIX_EPOCH_NTP_SECS, nanos).unwrap_or(near)
+}
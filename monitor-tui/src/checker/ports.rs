@@ -0,0 +1,142 @@
+use crate::config::{PortSpec, SiteConfig};
+use super::types::{CheckResult, FailureKind, Status};
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use std::collections::BTreeSet;
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use url::Url;
+
+/// Most ports one site may sweep
+pub const MAX_PORTS: usize = 1024;
+
+/// Connections a sweep has open at once
+const CONCURRENT_CONNECTS: usize = 64;
+
+/// Connects to a host's ports, expecting some to take connections and others
+/// not to: what must be reachable, and what must not be exposed
+pub struct PortChecker {
+    timeout: Duration,
+}
+
+impl PortChecker {
+    pub fn new(timeout_secs: u64) -> Self {
+        Self { timeout: Duration::from_secs(timeout_secs) }
+    }
+
+    pub async fn check(&self, site: &SiteConfig, warning_threshold_ms: Option<u64>) -> CheckResult {
+        let host = match Url::parse(&site.url) {
+            Ok(url) => url.host_str().unwrap_or_default().to_string(),
+            Err(e) => return CheckResult::new_down(format!("Invalid URL: {}", e)),
+        };
+        let (open, closed) = match (expand(&site.open_ports), expand(&site.closed_ports)) {
+            (Ok(open), Ok(closed)) => (open, closed),
+            (Err(e), _) | (_, Err(e)) => return CheckResult::new_down(format!("Invalid ports: {:#}", e)),
+        };
+        // Look the host up once rather than once per port
+        let ip = match tokio::time::timeout(self.timeout, resolve(&host)).await {
+            Ok(Ok(ip)) => ip,
+            Ok(Err(e)) => return CheckResult::new_failed(FailureKind::Connection, format!("Connection failed: {:#}", e)),
+            Err(_) => return CheckResult::new_timeout(format!("Request timeout resolving {}", host)),
+        };
+
+        let start = Instant::now();
+        let timeout = self.timeout;
+        let expected: Vec<(u16, bool)> =
+            open.iter().map(|&port| (port, true)).chain(closed.iter().map(|&port| (port, false))).collect();
+        let swept: Vec<(u16, bool, bool)> = futures::stream::iter(expected)
+            .map(move |(port, expect_open)| async move {
+                (port, expect_open, accepts(SocketAddr::new(ip, port), timeout).await)
+            })
+            .buffer_unordered(CONCURRENT_CONNECTS)
+            .collect()
+            .await;
+        let elapsed = start.elapsed().as_millis() as u64;
+
+        let mut unreachable: Vec<u16> = swept.iter().filter(|(_, expect, open)| *expect && !open).map(|s| s.0).collect();
+        let mut exposed: Vec<u16> = swept.iter().filter(|(_, expect, open)| !expect && *open).map(|s| s.0).collect();
+        unreachable.sort_unstable();
+        exposed.sort_unstable();
+
+        let mut result = CheckResult::new_up();
+        result.response_time_ms = Some(elapsed);
+        let mut problems = Vec::new();
+        if !unreachable.is_empty() {
+            result.status = Status::Down;
+            result.failure = Some(FailureKind::Connection);
+            problems.push(format!("Connection failed: {} not reachable", describe(&unreachable)));
+        }
+        if !exposed.is_empty() {
+            if result.status == Status::Up {
+                result.status = Status::Warning;
+                result.failure = Some(FailureKind::ExposedPort);
+            }
+            problems.push(format!("Exposed: {} open", describe(&exposed)));
+        }
+        if !problems.is_empty() {
+            result.error_message = Some(problems.join("; "));
+        } else if warning_threshold_ms.filter(|&t| t > 0).is_some_and(|t| elapsed > t) {
+            result.status = Status::Warning;
+        }
+        result
+    }
+}
+
+/// Every port the specs name, in order and without repeats
+pub fn expand(specs: &[PortSpec]) -> Result<Vec<u16>> {
+    let mut ports = BTreeSet::new();
+    for spec in specs {
+        let (first, last) = match spec {
+            PortSpec::Port(port) => {
+                let port = u16::try_from(*port).with_context(|| format!("{} isn't a port (1-65535)", port))?;
+                (port, port)
+            }
+            PortSpec::Range(range) => {
+                let parse = |text: &str| {
+                    text.trim().parse::<u16>().with_context(|| format!("'{}' isn't a port or a range like 8000-8100", range))
+                };
+                match range.split_once('-') {
+                    Some((first, last)) => (parse(first)?, parse(last)?),
+                    None => (parse(range)?, parse(range)?),
+                }
+            }
+        };
+        if first == 0 || first > last {
+            anyhow::bail!("{} isn't a valid port or range (1-65535)", spec);
+        }
+        ports.extend(first..=last);
+    }
+    Ok(ports.into_iter().collect())
+}
+
+/// e.g. "port 22" or "ports 80, 443, 8000-8100"
+fn describe(ports: &[u16]) -> String {
+    let mut runs: Vec<(u16, u16)> = Vec::new();
+    for &port in ports {
+        match runs.last_mut() {
+            Some((_, last)) if *last + 1 == port => *last = port,
+            _ => runs.push((port, port)),
+        }
+    }
+    let runs: Vec<String> = runs
+        .iter()
+        .map(|&(first, last)| if first == last { first.to_string() } else { format!("{}-{}", first, last) })
+        .collect();
+    let noun = if ports.len() == 1 { "port" } else { "ports" };
+    format!("{} {}", noun, runs.join(", "))
+}
+
+async fn resolve(host: &str) -> Result<IpAddr> {
+    tokio::net::lookup_host((host, 0))
+        .await
+        .with_context(|| format!("{} failed to resolve", host))?
+        .next()
+        .map(|addr| addr.ip())
+        .with_context(|| format!("{} has no addresses", host))
+}
+
+/// Whether the port takes a TCP connection; refused and unanswered both count as closed
+async fn accepts(addr: SocketAddr, timeout: Duration) -> bool {
+    matches!(tokio::time::timeout(timeout, TcpStream::connect(addr)).await, Ok(Ok(_)))
+}
@@ -0,0 +1,171 @@
+use super::http::describe_error;
+use super::types::Revocation;
+use anyhow::{anyhow, Context, Result};
+use openssl::asn1::Asn1Time;
+use openssl::hash::MessageDigest;
+use openssl::ocsp::{OcspCertId, OcspCertStatus, OcspFlag, OcspRequest, OcspResponse, OcspResponseStatus, OcspRevokedStatus};
+use openssl::stack::Stack;
+use openssl::x509::store::X509StoreBuilder;
+use openssl::x509::{CrlStatus, X509, X509Crl};
+use reqwest::Client;
+use std::time::{Duration, Instant};
+
+/// How long an answer about a certificate is reused before asking again
+const RECHECK_AFTER: Duration = Duration::from_secs(3600);
+
+/// How long to wait before asking again when no answer could be had
+const RETRY_AFTER: Duration = Duration::from_secs(300);
+
+/// Slack allowed on an OCSP response's validity period, in seconds
+const CLOCK_SKEW_SECS: u32 = 300;
+
+/// Revocation status of a site's certificate, asked of the issuer's OCSP
+/// responder, or of its CRL when it lists no responder. The answer is kept
+/// for the certificate rather than asked for on every check.
+#[derive(Default)]
+pub struct RevocationChecker {
+    /// SHA-256 of the certificate, when it was asked about, and the answer
+    cached: Option<(Vec<u8>, Instant, Revocation)>,
+}
+
+impl RevocationChecker {
+    /// Status of the leaf of `chain` (leaf first, as the server sent it)
+    pub async fn check(&mut self, client: &Client, chain: &[X509], timeout: Duration) -> Revocation {
+        let Some(leaf) = chain.first() else {
+            return Revocation::Unverifiable("the server sent no certificate".to_string());
+        };
+        let fingerprint = leaf.digest(MessageDigest::sha256()).map(|d| d.to_vec()).unwrap_or_default();
+        if let Some((cached, at, revocation)) = &self.cached
+            && *cached == fingerprint
+        {
+            let keep = if matches!(revocation, Revocation::Unverifiable(_)) { RETRY_AFTER } else { RECHECK_AFTER };
+            if at.elapsed() < keep {
+                return revocation.clone();
+            }
+        }
+
+        let revocation = lookup(client, chain, timeout)
+            .await
+            .unwrap_or_else(|e| Revocation::Unverifiable(format!("{:#}", e)));
+        self.cached = Some((fingerprint, Instant::now(), revocation.clone()));
+        revocation
+    }
+}
+
+async fn lookup(client: &Client, chain: &[X509], timeout: Duration) -> Result<Revocation> {
+    let leaf = &chain[0];
+    let issuer = chain.get(1).context("the server didn't send the issuing certificate")?;
+    // OpenSSL reports a certificate without responders as an error
+    let responders = leaf.ocsp_responders().ok();
+    if let Some(responder) = responders.as_ref().and_then(|r| r.iter().next()) {
+        return ocsp(client, chain, responder, timeout).await;
+    }
+    let url = crl_url(leaf).context("the certificate lists no OCSP responder or CRL")?;
+    crl(client, leaf, issuer, &url, timeout).await
+}
+
+/// Ask the OCSP responder, trusting its answer only when it's signed for the issuer
+async fn ocsp(client: &Client, chain: &[X509], responder: &str, timeout: Duration) -> Result<Revocation> {
+    let (leaf, issuer) = (&chain[0], &chain[1]);
+    let mut request = OcspRequest::new()?;
+    request.add_id(OcspCertId::from_cert(MessageDigest::sha1(), leaf, issuer)?)?;
+    let body = client
+        .post(responder)
+        .header("Content-Type", "application/ocsp-request")
+        .body(request.to_der()?)
+        .timeout(timeout)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| anyhow!("OCSP responder {} didn't answer: {}", responder, describe_error(&e)))?
+        .bytes()
+        .await?;
+
+    let response = OcspResponse::from_der(&body).context("the OCSP response didn't parse")?;
+    if response.status() != OcspResponseStatus::SUCCESSFUL {
+        anyhow::bail!("the OCSP responder refused the request");
+    }
+    let basic = response.basic()?;
+    let mut untrusted = Stack::new()?;
+    for cert in &chain[1..] {
+        untrusted.push(cert.clone())?;
+    }
+    let mut store = X509StoreBuilder::new()?;
+    store.set_default_paths()?;
+    basic
+        .verify(&untrusted, &store.build(), OcspFlag::empty())
+        .context("the OCSP response's signature doesn't check out")?;
+
+    let id = OcspCertId::from_cert(MessageDigest::sha1(), leaf, issuer)?;
+    let status = basic.find_status(&id).context("the OCSP response doesn't cover the certificate")?;
+    status
+        .check_validity(CLOCK_SKEW_SECS, None)
+        .context("the OCSP response is out of date")?;
+    let source = "OCSP".to_string();
+    Ok(match status.status {
+        OcspCertStatus::GOOD => Revocation::Good { source },
+        OcspCertStatus::REVOKED => {
+            let mut detail = reason(status.reason).unwrap_or_default().to_string();
+            if let Some(at) = status.revocation_time {
+                detail = if detail.is_empty() { format!("since {}", at) } else { format!("{}, since {}", detail, at) };
+            }
+            Revocation::Revoked { source, detail }
+        }
+        _ => Revocation::Unverifiable("the OCSP responder doesn't know the certificate".to_string()),
+    })
+}
+
+/// Look the certificate up in the issuer's revocation list
+async fn crl(client: &Client, leaf: &X509, issuer: &X509, url: &str, timeout: Duration) -> Result<Revocation> {
+    let body = client
+        .get(url)
+        .timeout(timeout)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| anyhow!("CRL {} couldn't be fetched: {}", url, describe_error(&e)))?
+        .bytes()
+        .await?;
+    let crl = X509Crl::from_der(&body)
+        .or_else(|_| X509Crl::from_pem(&body))
+        .context("the CRL didn't parse")?;
+    let key = issuer.public_key()?;
+    if !crl.verify(&key)? {
+        anyhow::bail!("the CRL isn't signed by the issuer");
+    }
+    if let Some(next_update) = crl.next_update()
+        && next_update < Asn1Time::days_from_now(0)?
+    {
+        anyhow::bail!("the CRL is out of date");
+    }
+    let source = "CRL".to_string();
+    Ok(match crl.get_by_cert(leaf) {
+        CrlStatus::Revoked(entry) => Revocation::Revoked { source, detail: format!("since {}", entry.revocation_date()) },
+        CrlStatus::NotRevoked | CrlStatus::RemoveFromCrl(_) => Revocation::Good { source },
+    })
+}
+
+/// The first http(s) CRL distribution point of the certificate
+fn crl_url(cert: &X509) -> Option<String> {
+    let points = cert.crl_distribution_points()?;
+    points
+        .iter()
+        .filter_map(|point| point.distpoint()?.fullname())
+        .flat_map(|names| names.iter())
+        .filter_map(|name| name.uri())
+        .find(|uri| uri.starts_with("http://") || uri.starts_with("https://"))
+        .map(str::to_string)
+}
+
+/// Why a certificate was revoked, when the responder said
+fn reason(reason: OcspRevokedStatus) -> Option<&'static str> {
+    Some(match reason {
+        OcspRevokedStatus::KEY_COMPROMISE => "key compromise",
+        OcspRevokedStatus::CA_COMPROMISE => "CA compromise",
+        OcspRevokedStatus::AFFILIATION_CHANGED => "affiliation changed",
+        OcspRevokedStatus::STATUS_SUPERSEDED => "superseded",
+        OcspRevokedStatus::STATUS_CESSATION_OF_OPERATION => "cessation of operation",
+        OcspRevokedStatus::STATUS_CERTIFICATE_HOLD => "on hold",
+        _ => return None,
+    })
+}
@@ -0,0 +1,52 @@
+use super::types::TlsInfo;
+use anyhow::{Context, Result};
+use openssl::ssl::{Ssl, SslConnector, SslMethod, SslVerifyMode, SslVersion};
+use openssl::x509::X509;
+use std::pin::Pin;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_openssl::SslStream;
+use url::Url;
+
+/// Handshake with the site's host and report what was negotiated, along with
+/// the certificate chain it sent (leaf first). Uses the same TLS library as
+/// the HTTP checks, but accepts legacy protocols and skips certificate
+/// verification so downgrades can be seen rather than just fail.
+pub async fn inspect(url: &str, timeout: Duration) -> Result<(TlsInfo, Vec<X509>)> {
+    let url = Url::parse(url)?;
+    let host = url.host_str().context("URL has no host")?;
+    let port = url.port_or_known_default().unwrap_or(443);
+
+    let mut builder = SslConnector::builder(SslMethod::tls_client())?;
+    builder.set_min_proto_version(Some(SslVersion::TLS1))?;
+    builder.set_cipher_list("ALL:@SECLEVEL=0")?;
+    builder.set_verify(SslVerifyMode::NONE);
+    let ssl: Ssl = builder.build().configure()?.verify_hostname(false).into_ssl(host)?;
+
+    tokio::time::timeout(timeout, async {
+        let tcp = TcpStream::connect((host, port)).await?;
+        let mut stream = SslStream::new(ssl, tcp)?;
+        Pin::new(&mut stream).connect().await?;
+        let ssl = stream.ssl();
+        let info = TlsInfo {
+            version: ssl.version_str().to_string(),
+            cipher: ssl.current_cipher().map(|c| c.name().to_string()).unwrap_or_default(),
+            revocation: None,
+        };
+        let chain = ssl.peer_cert_chain().map(|chain| chain.iter().map(|cert| cert.to_owned()).collect());
+        Ok::<_, anyhow::Error>((info, chain.unwrap_or_default()))
+    })
+    .await
+    .context("TLS handshake timed out")?
+}
+
+/// Rank a TLS version as reported ("TLSv1.2") or configured ("1.2"); higher is newer
+pub fn version_rank(version: &str) -> Option<u8> {
+    match version.trim_start_matches("TLSv") {
+        "1" | "1.0" => Some(10),
+        "1.1" => Some(11),
+        "1.2" => Some(12),
+        "1.3" => Some(13),
+        _ => None,
+    }
+}
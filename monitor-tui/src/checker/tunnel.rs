@@ -0,0 +1,134 @@
+use super::types::{CheckResult, FailureKind};
+use anyhow::{Context, Result};
+use std::net::TcpListener;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
+
+/// Wait before starting ssh again after it exits or can't be started. ssh
+/// that stays up this long has connected, and its last error no longer applies.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// An `ssh -D` SOCKS proxy to a jump host, for checking hosts only it can
+/// reach. ssh is restarted whenever it exits, on the same local port, so
+/// clients pointed at the proxy keep working; it's stopped on drop.
+pub struct SshTunnel {
+    port: u16,
+    error: TunnelError,
+    task: JoinHandle<()>,
+}
+
+/// Why a tunnel's ssh last exited or couldn't start, shared with the checks
+/// going through it
+#[derive(Clone, Default)]
+pub struct TunnelError(Arc<Mutex<Option<String>>>);
+
+impl TunnelError {
+    fn set(&self, error: Option<String>) {
+        *self.0.lock().unwrap() = error;
+    }
+
+    /// Add the ssh error to a request that failed through the tunnel, which
+    /// by itself only sees the local proxy refusing or timing out
+    pub fn explain(&self, result: &mut CheckResult) {
+        let request_failed = matches!(
+            result.failure,
+            Some(FailureKind::Timeout | FailureKind::Connection | FailureKind::Request)
+        );
+        if let (true, Some(error), Some(message)) =
+            (request_failed, self.0.lock().unwrap().as_ref(), result.error_message.as_mut())
+        {
+            message.push_str(&format!(" (ssh: {})", error));
+        }
+    }
+}
+
+impl SshTunnel {
+    /// Start tunnelling through `host`, which can be anything `ssh` accepts,
+    /// including a `Host` alias from ~/.ssh/config
+    pub fn open(host: &str) -> Result<Self> {
+        // Let the OS pick a free port, then hand it to ssh
+        let port = TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .context("Couldn't find a free local port for the SSH tunnel")?
+            .port();
+        let host = host.to_string();
+        let error = TunnelError::default();
+
+        let shared = error.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                // BatchMode: never prompt for a password on the TUI's terminal
+                let child = Command::new("ssh")
+                    .args(["-N", "-D", &format!("127.0.0.1:{}", port)])
+                    .args(["-o", "BatchMode=yes", "-o", "ExitOnForwardFailure=yes"])
+                    .args(["-o", "ServerAliveInterval=15", "-o", "ServerAliveCountMax=3"])
+                    .arg("--")
+                    .arg(&host)
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::piped())
+                    .kill_on_drop(true)
+                    .spawn();
+                match child {
+                    Ok(mut child) => {
+                        let said = child.stderr.take().map(|stderr| tokio::spawn(last_line(stderr)));
+                        let status = match tokio::time::timeout(RECONNECT_DELAY, child.wait()).await {
+                            Ok(status) => status,
+                            Err(_) => {
+                                shared.set(None);
+                                child.wait().await
+                            }
+                        };
+                        let said = match said {
+                            Some(reader) => reader.await.ok().flatten(),
+                            None => None,
+                        };
+                        shared.set(Some(match (said, status) {
+                            (Some(line), _) => line,
+                            (None, Ok(status)) => format!("exited with {}", status),
+                            (None, Err(e)) => format!("failed: {}", e),
+                        }));
+                    }
+                    Err(e) => shared.set(Some(format!("couldn't be started: {}", e))),
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        });
+
+        Ok(Self { port, error, task })
+    }
+
+    /// Proxy URL for clients; names are resolved by the jump host, so private
+    /// DNS names work too
+    pub fn proxy_url(&self) -> String {
+        format!("socks5h://127.0.0.1:{}", self.port)
+    }
+
+    /// Handle to why ssh last failed, for the checks using the tunnel
+    pub fn error(&self) -> TunnelError {
+        self.error.clone()
+    }
+}
+
+impl Drop for SshTunnel {
+    fn drop(&mut self) {
+        // Dropping the task drops the child, which kills ssh
+        self.task.abort();
+    }
+}
+
+/// Last non-empty line ssh wrote, e.g. "Permission denied (publickey)."
+async fn last_line(stderr: impl AsyncRead + Unpin) -> Option<String> {
+    let mut lines = BufReader::new(stderr).lines();
+    let mut last = None;
+    while let Ok(Some(line)) = lines.next_line().await {
+        if !line.trim().is_empty() {
+            last = Some(line.trim().to_string());
+        }
+    }
+    last
+}
@@ -1,19 +1,142 @@
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Status {
     Up,      // HTTP status matches expected
     Down,    // Request failed or timeout
     Warning, // HTTP success but unexpected status code
+    Skipped, // Not checked: inside a blackout period
+    RateLimited, // Answered 429 (or 503 with Retry-After) while respect_retry_after is on
 }
 
-#[derive(Debug, Clone)]
+impl Status {
+    /// Whether the result says anything about the site's health; skipped and
+    /// rate-limited checks don't, so they count toward neither uptime nor alerts
+    pub fn is_conclusive(&self) -> bool {
+        !matches!(self, Status::Skipped | Status::RateLimited)
+    }
+
+    /// Name of the status in plain text
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Up => "UP",
+            Status::Down => "DOWN",
+            Status::Warning => "WARNING",
+            Status::Skipped => "SKIPPED",
+            Status::RateLimited => "RATE LIMITED",
+        }
+    }
+
+    /// Name of the status for other programs, e.g. "rate_limited"
+    pub fn key(&self) -> &'static str {
+        match self {
+            Status::Up => "up",
+            Status::Down => "down",
+            Status::Warning => "warning",
+            Status::Skipped => "skipped",
+            Status::RateLimited => "rate_limited",
+        }
+    }
+}
+
+/// Empty parts are left out when stored, to keep the history file small
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckResult {
     pub timestamp: DateTime<Utc>,
     pub status: Status,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub response_time_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub http_status: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
+    /// Results per egress profile when a site is checked from several locations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<(String, CheckResult)>,
+    /// Negotiated TLS parameters, when TLS inspection is on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsInfo>,
+    /// Findings of the diagnostics run when the site went Down
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<String>,
+    /// How long a 429 or 503 response asked us to wait before the next request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<Duration>,
+    /// Start of the response body when the status code wasn't the expected one
+    /// or the body didn't match, e.g. the message of an error page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_snippet: Option<String>,
+    /// How far an NTP server's clock is ahead of the local one, in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock_offset_ms: Option<i64>,
+    /// Why the check failed, where the checker could tell
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<FailureKind>,
+}
+
+/// Broad cause of a failed check, set where the failure is found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    /// Gave up waiting, as opposed to being refused or failing outright
+    Timeout,
+    Connection,
+    Request,
+    Certificate,
+    BodyMismatch,
+    ClockDrift,
+    ExposedPort,
+}
+
+impl FailureKind {
+    /// Name of the kind in plain text, e.g. "Body mismatch"
+    pub fn label(self) -> &'static str {
+        match self {
+            FailureKind::Timeout => "Timeout",
+            FailureKind::Connection => "Connection",
+            FailureKind::Request => "Request error",
+            FailureKind::Certificate => "Certificate",
+            FailureKind::BodyMismatch => "Body mismatch",
+            FailureKind::ClockDrift => "Clock drift",
+            FailureKind::ExposedPort => "Exposed port",
+        }
+    }
+}
+
+/// What an HTTPS connection negotiated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsInfo {
+    /// Protocol as OpenSSL names it, e.g. "TLSv1.3"
+    pub version: String,
+    pub cipher: String,
+    /// Whether the certificate was revoked, when revocation checking is on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revocation: Option<Revocation>,
+}
+
+/// What the certificate's issuer says about it, per OCSP or its CRL
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Revocation {
+    /// Not revoked; `source` is "OCSP" or "CRL"
+    Good { source: String },
+    /// Revoked, with the reason and date when given
+    Revoked { source: String, detail: String },
+    /// No trustworthy answer, e.g. no responder listed or none reachable
+    Unverifiable(String),
+}
+
+impl Revocation {
+    /// e.g. "not revoked (OCSP)" or "REVOKED (CRL): key compromise"
+    pub fn describe(&self) -> String {
+        match self {
+            Revocation::Good { source } => format!("not revoked ({})", source),
+            Revocation::Revoked { source, detail } if detail.is_empty() => format!("REVOKED ({})", source),
+            Revocation::Revoked { source, detail } => format!("REVOKED ({}): {}", source, detail),
+            Revocation::Unverifiable(reason) => format!("revocation unverifiable: {}", reason),
+        }
+    }
 }
 
 impl CheckResult {
@@ -24,24 +147,106 @@ impl CheckResult {
             response_time_ms: None,
             http_status: None,
             error_message: Some(error),
+            locations: Vec::new(),
+            tls: None,
+            diagnostics: Vec::new(),
+            retry_after: None,
+            body_snippet: None,
+            clock_offset_ms: None,
+            failure: None,
+        }
+    }
+
+    /// Down result for a failure of a known kind
+    pub fn new_failed(kind: FailureKind, error: String) -> Self {
+        let mut result = Self::new_down(error);
+        result.failure = Some(kind);
+        result
+    }
+
+    /// Down result for a check that gave up waiting
+    pub fn new_timeout(error: String) -> Self {
+        Self::new_failed(FailureKind::Timeout, error)
+    }
+
+    /// Whether the check gave up waiting
+    pub fn timed_out(&self) -> bool {
+        self.failure == Some(FailureKind::Timeout)
+    }
+
+    /// Broad cause of a Down or Warning result, for tallying failures by kind
+    pub fn failure_kind(&self, expected_status: u16) -> Option<&'static str> {
+        if self.status == Status::Up || !self.status.is_conclusive() {
+            return None;
+        }
+        let kind = match (self.failure, &self.error_message, self.http_status) {
+            (Some(failure), _, _) => failure.label(),
+            (None, Some(_), _) => "Check failed",
+            (None, None, Some(code)) if code != expected_status => "Unexpected status",
+            (None, None, Some(_)) => "Slow response",
+            (None, None, None) => "Check failed",
+        };
+        Some(kind)
+    }
+
+    /// Whether the server was turning requests away for now: 429, or 503 with Retry-After
+    pub fn is_rate_limited(&self) -> bool {
+        match self.http_status {
+            Some(429) => true,
+            Some(503) => self.retry_after.is_some(),
+            _ => false,
+        }
+    }
+
+    pub fn new_skipped() -> Self {
+        Self {
+            timestamp: Utc::now(),
+            status: Status::Skipped,
+            response_time_ms: None,
+            http_status: None,
+            error_message: None,
+            locations: Vec::new(),
+            tls: None,
+            diagnostics: Vec::new(),
+            retry_after: None,
+            body_snippet: None,
+            clock_offset_ms: None,
+            failure: None,
+        }
+    }
+
+    pub fn new_up() -> Self {
+        Self {
+            timestamp: Utc::now(),
+            status: Status::Up,
+            response_time_ms: None,
+            http_status: None,
+            error_message: None,
+            locations: Vec::new(),
+            tls: None,
+            diagnostics: Vec::new(),
+            retry_after: None,
+            body_snippet: None,
+            clock_offset_ms: None,
+            failure: None,
         }
     }
 
+    /// Result of a request that got a response; `code_status` is what the
+    /// site's status code mapping makes of `http_status`
     pub fn new_success(
         response_time_ms: u64,
         http_status: u16,
-        expected_status: u16,
+        code_status: Status,
         warning_threshold_ms: Option<u64>,
     ) -> Self {
-        let status_mismatch = http_status != expected_status;
         let slow_response = warning_threshold_ms
             .filter(|&t| t > 0)
             .is_some_and(|t| response_time_ms > t);
 
-        let status = if status_mismatch || slow_response {
-            Status::Warning
-        } else {
-            Status::Up
+        let status = match code_status {
+            Status::Up if slow_response => Status::Warning,
+            status => status,
         };
 
         Self {
@@ -50,6 +255,66 @@ impl CheckResult {
             response_time_ms: Some(response_time_ms),
             http_status: Some(http_status),
             error_message: None,
+            locations: Vec::new(),
+            tls: None,
+            diagnostics: Vec::new(),
+            retry_after: None,
+            body_snippet: None,
+            clock_offset_ms: None,
+            failure: None,
+        }
+    }
+
+    /// Combine per-location results: Up or Down when every location agrees,
+    /// Warning when only some locations see the site down. Skipped and
+    /// rate-limited locations say nothing about the site, so only the others
+    /// are counted.
+    pub fn from_locations(locations: Vec<(String, CheckResult)>) -> Self {
+        let voting: Vec<&(String, CheckResult)> = locations.iter().filter(|(_, r)| r.status.is_conclusive()).collect();
+        let failing: Vec<&str> = voting
+            .iter()
+            .filter(|(_, r)| r.status != Status::Up)
+            .map(|(name, _)| name.as_str())
+            .collect();
+
+        let mut result = if voting.is_empty() {
+            // Nowhere had an answer; wait as long as the longest Retry-After
+            let mut result = Self::new_skipped();
+            if locations.iter().any(|(_, r)| r.status == Status::RateLimited) {
+                result.status = Status::RateLimited;
+                result.retry_after = locations.iter().filter_map(|(_, r)| r.retry_after).max();
+            }
+            result
+        } else if failing.is_empty() {
+            Self::new_up()
+        } else if failing.len() == voting.len() {
+            let mut result = Self::new_down("Failing from every location".to_string());
+            if voting.iter().all(|(_, r)| r.timed_out()) {
+                result.failure = Some(FailureKind::Timeout);
+            }
+            // Every location agreeing on a non-Down problem is that problem
+            if voting.iter().all(|(_, r)| r.status == Status::Warning) {
+                result.status = Status::Warning;
+            }
+            result
+        } else {
+            let mut result = Self::new_down(format!(
+                "Failing from {} ({}/{} locations)",
+                failing.join(", "),
+                failing.len(),
+                voting.len()
+            ));
+            result.status = Status::Warning;
+            result
+        };
+
+        // Report the slowest location, and a status code when all agree
+        result.response_time_ms = locations.iter().filter_map(|(_, r)| r.response_time_ms).max();
+        let codes: Vec<Option<u16>> = locations.iter().map(|(_, r)| r.http_status).collect();
+        if codes.windows(2).all(|pair| pair[0] == pair[1]) {
+            result.http_status = codes.first().copied().flatten();
         }
+        result.locations = locations;
+        result
     }
 }
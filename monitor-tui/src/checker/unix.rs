@@ -0,0 +1,170 @@
+use super::types::CheckResult;
+#[cfg(unix)]
+use super::types::FailureKind;
+use anyhow::Result;
+#[cfg(unix)]
+use hyper::client::conn;
+#[cfg(unix)]
+use hyper::{Body, Request};
+#[cfg(unix)]
+use reqwest::header::{HOST, USER_AGENT};
+use std::path::PathBuf;
+use std::time::Duration;
+#[cfg(unix)]
+use std::time::Instant;
+#[cfg(unix)]
+use tokio::net::UnixStream;
+
+/// Prefix of URLs checked over a unix domain socket
+pub const PREFIX: &str = "http+unix://";
+
+/// Endings that mark where the socket's path ends in an http+unix URL
+const SOCKET_SUFFIXES: [&str; 2] = [".sock", ".socket"];
+
+/// A local daemon's socket and the path requested through it
+#[derive(Debug, Clone)]
+pub struct UnixTarget {
+    pub socket: PathBuf,
+    /// Path and query sent in the request line, e.g. "/health?full=1"
+    pub path: String,
+}
+
+impl UnixTarget {
+    /// Split an http+unix URL like "http+unix:///run/app.sock/health" into
+    /// the socket (up to the first segment named *.sock or *.socket) and the
+    /// request path after it. None for any other kind of URL.
+    pub fn parse(url: &str) -> Result<Option<Self>> {
+        let Some(rest) = url.strip_prefix(PREFIX) else {
+            return Ok(None);
+        };
+        let rest = rest.split('#').next().unwrap_or_default();
+        let (path, query) = match rest.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (rest, None),
+        };
+        if !path.starts_with('/') {
+            anyhow::bail!("the socket needs an absolute path, like {}/run/app.sock/health", PREFIX);
+        }
+
+        let mut end = None;
+        let mut offset = 0;
+        for segment in path.split('/') {
+            offset += segment.len();
+            if SOCKET_SUFFIXES.iter().any(|suffix| segment.len() > suffix.len() && segment.ends_with(suffix)) {
+                end = Some(offset);
+                break;
+            }
+            offset += 1;
+        }
+        let Some(end) = end else {
+            anyhow::bail!("no path segment ending in .sock or .socket marks where the socket's path ends");
+        };
+
+        let mut request_path = match &path[end..] {
+            "" => "/".to_string(),
+            rest => rest.to_string(),
+        };
+        if let Some(query) = query {
+            request_path = format!("{}?{}", request_path, query);
+        }
+        Ok(Some(Self { socket: PathBuf::from(&path[..end]), path: request_path }))
+    }
+
+    /// URL to build the request with; the client won't take an http+unix
+    /// one, and only the headers and body it gathers get sent
+    pub fn request_url(&self) -> String {
+        format!("http://localhost{}", self.path)
+    }
+}
+
+/// Send `request` (as built for its headers and body) through the socket and
+/// read the whole response within `timeout`. Failures read like request_failure's.
+#[cfg(unix)]
+pub async fn send(
+    target: &UnixTarget,
+    request: reqwest::Request,
+    user_agent: &str,
+    timeout: Duration,
+) -> Result<reqwest::Response, CheckResult> {
+    match tokio::time::timeout(timeout, exchange(target, request, user_agent)).await {
+        Ok(response) => response,
+        Err(_) => Err(CheckResult::new_timeout("Request timeout".to_string())),
+    }
+}
+
+/// Config validation turns http+unix sites away where there are no unix sockets
+#[cfg(not(unix))]
+pub async fn send(
+    _target: &UnixTarget,
+    _request: reqwest::Request,
+    _user_agent: &str,
+    _timeout: Duration,
+) -> Result<reqwest::Response, CheckResult> {
+    Err(CheckResult::new_down("Unix sockets aren't available on this platform".to_string()))
+}
+
+#[cfg(unix)]
+async fn exchange(target: &UnixTarget, request: reqwest::Request, user_agent: &str) -> Result<reqwest::Response, CheckResult> {
+    let stream = UnixStream::connect(&target.socket)
+        .await
+        .map_err(|e| connection_failed(format!("{}: {}", target.socket.display(), e)))?;
+    let (mut sender, connection) = conn::handshake(stream).await.map_err(connection_failed)?;
+    tokio::spawn(async move {
+        let _ = connection.await;
+    });
+
+    let mut builder = Request::builder().method(request.method().clone()).uri(target.path.as_str());
+    if let Some(headers) = builder.headers_mut() {
+        headers.extend(request.headers().clone());
+        // HTTP/1.1 requires a Host, though a socket has no name to give
+        headers.entry(HOST).or_insert_with(|| "localhost".parse().expect("valid header value"));
+        if !headers.contains_key(USER_AGENT)
+            && let Ok(agent) = user_agent.parse()
+        {
+            headers.insert(USER_AGENT, agent);
+        }
+    }
+    let body = request.body().and_then(|body| body.as_bytes()).map_or_else(Body::empty, |bytes| Body::from(bytes.to_vec()));
+    let request = builder.body(body).map_err(request_failed)?;
+
+    let response = sender.send_request(request).await.map_err(request_failed)?;
+    let (parts, body) = response.into_parts();
+    let body = hyper::body::to_bytes(body).await.map_err(request_failed)?;
+    Ok(hyper::Response::from_parts(parts, body).into())
+}
+
+#[cfg(unix)]
+fn connection_failed(error: impl std::fmt::Display) -> CheckResult {
+    CheckResult::new_failed(FailureKind::Connection, format!("Connection failed: {}", error))
+}
+
+#[cfg(unix)]
+fn request_failed(error: impl std::fmt::Display) -> CheckResult {
+    CheckResult::new_failed(FailureKind::Request, format!("Request failed: {}", error))
+}
+
+/// What a site that just went Down finds at its socket: whether the file is
+/// there, is a socket, and takes connections
+#[cfg(unix)]
+pub async fn diagnose(target: &UnixTarget, timeout: Duration) -> Vec<String> {
+    use std::os::unix::fs::FileTypeExt;
+
+    let socket = target.socket.display();
+    match std::fs::metadata(&target.socket) {
+        Ok(metadata) if !metadata.file_type().is_socket() => return vec![format!("Socket: {} isn't a socket", socket)],
+        Ok(_) => {}
+        Err(e) => return vec![format!("Socket: {}: {}", socket, e)],
+    }
+    let start = Instant::now();
+    let finding = match tokio::time::timeout(timeout, UnixStream::connect(&target.socket)).await {
+        Ok(Ok(_)) => format!("Socket: connected to {} in {}ms", socket, start.elapsed().as_millis()),
+        Ok(Err(e)) => format!("Socket: connecting to {} failed: {}", socket, e),
+        Err(_) => format!("Socket: connecting to {} timed out", socket),
+    };
+    vec![finding]
+}
+
+#[cfg(not(unix))]
+pub async fn diagnose(_target: &UnixTarget, _timeout: Duration) -> Vec<String> {
+    Vec::new()
+}
@@ -0,0 +1,86 @@
+use crate::config::{SiteConfig, XmlAssertion};
+use anyhow::{Context as _, Result};
+use chrono::{DateTime, Utc};
+use sxd_document::parser;
+use sxd_xpath::{Context, Factory, Value, XPath};
+
+/// Compile an XPath expression, rejecting empty ones
+pub fn compile(xpath: &str) -> Result<XPath> {
+    Factory::new()
+        .build(xpath)
+        .with_context(|| format!("Invalid XPath '{}'", xpath))?
+        .with_context(|| format!("Invalid XPath '{}' - expression is empty", xpath))
+}
+
+/// An XPath compiled once, when the checker is built, for checks to share
+pub struct CompiledXPath(XPath);
+
+// SAFETY: an XPath is a tree of owned expression nodes with no shared or
+// interior mutability; it's only neither Send nor Sync because the crate boxes
+// them as trait objects without those bounds.
+unsafe impl Send for CompiledXPath {}
+unsafe impl Sync for CompiledXPath {}
+
+/// Compile the XPaths of the site's XML assertions, in order
+pub fn compile_assertions(site: &SiteConfig) -> Result<Vec<CompiledXPath>> {
+    site.xml_assertions
+        .iter()
+        .map(|assertion| compile(&assertion.xpath).map(CompiledXPath))
+        .collect()
+}
+
+/// Run the site's XML assertions, compiled by `compile_assertions`, against a
+/// response body, returning the first failure
+pub fn check_assertions(site: &SiteConfig, xpaths: &[CompiledXPath], body: &str) -> Result<(), String> {
+    let package = parser::parse(body).map_err(|e| format!("Response is not valid XML: {:?}", e))?;
+    let document = package.as_document();
+
+    let mut context = Context::new();
+    for (prefix, uri) in &site.xml_namespaces {
+        context.set_namespace(prefix, uri);
+    }
+
+    for (assertion, CompiledXPath(xpath)) in site.xml_assertions.iter().zip(xpaths) {
+        let value = xpath
+            .evaluate(&context, document.root())
+            .map_err(|e| format!("XPath '{}' failed: {}", assertion.xpath, e))?;
+        check_value(assertion, &value)?;
+    }
+
+    Ok(())
+}
+
+/// Date of the newest entry if the body is an RSS or Atom feed
+pub fn newest_feed_entry(body: &str) -> Option<DateTime<Utc>> {
+    const ENTRY_DATES: &str = "//*[local-name()='item']/*[local-name()='pubDate'] \
+        | //*[local-name()='entry']/*[local-name()='updated' or local-name()='published']";
+
+    let package = parser::parse(body).ok()?;
+    let document = package.as_document();
+    let value = compile(ENTRY_DATES).ok()?.evaluate(&Context::new(), document.root()).ok()?;
+    let Value::Nodeset(nodes) = value else { return None };
+    nodes
+        .iter()
+        .filter_map(|node| {
+            let text = node.string_value();
+            let text = text.trim();
+            DateTime::parse_from_rfc3339(text)
+                .or_else(|_| DateTime::parse_from_rfc2822(text))
+                .ok()
+        })
+        .map(|date| date.with_timezone(&Utc))
+        .max()
+}
+
+fn check_value(assertion: &XmlAssertion, value: &Value) -> Result<(), String> {
+    match &assertion.equals {
+        None if !value.boolean() => Err(format!("XPath '{}' matched nothing", assertion.xpath)),
+        Some(expected) if value.string().trim() != expected => Err(format!(
+            "XPath '{}' is '{}', expected '{}'",
+            assertion.xpath,
+            value.string().trim(),
+            expected
+        )),
+        _ => Ok(()),
+    }
+}
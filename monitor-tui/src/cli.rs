@@ -0,0 +1,174 @@
+use crate::control::{ControlCommand, StatusFormat};
+use crate::import::{ImportArgs, ImportFormat};
+use anyhow::Result;
+use std::path::PathBuf;
+
+const USAGE: &str = "\
+Usage: monitor-tui [OPTIONS]
+       monitor-tui import [--format <FORMAT>] [--append] <FILE>
+       monitor-tui ctl <COMMAND>
+       monitor-tui status [--format <FORMAT>]
+       monitor-tui strings
+
+Commands:
+  import            Print sites.toml entries converted from another tool's
+                    configuration (formats: urls (default), uptime-kuma,
+                    caddy, nginx); a FILE of - reads standard input.
+                    --append adds them to the config file instead
+  ctl               Send a command to the instance monitoring this config:
+                    pause (stop all checks), resume (check everything now)
+                    report (email the availability report so far),
+                    clear-alerts (empty the alert history),
+                    clear-recoveries (drop its recovery alerts) or
+                    status (print site counts as JSON)
+  status            Print how the running instance's sites are doing, for
+                    status bars (formats: text (default), json, waybar, tmux)
+  strings           Print every string of the interface as a locale file to
+                    translate (see `locale` in sites.toml.example)
+
+Options:
+  --demo            Show synthetic check results instead of making network calls
+  --demo-sites <N>  Add N synthetic sites for load testing (implies --demo)
+  --force           Start even if another instance is using the same config
+  --carousel        Cycle through each site's detail view (for unattended displays)
+  --headless        Run checks, alerts and notifications without the TUI, logging
+                    results, alerts and messages a line each (e.g. under systemd)
+  --log-file <FILE> Append that log to FILE instead of printing it (implies --headless)
+  -h, --help        Print this help message";
+
+/// Command-line options
+#[derive(Debug, Default)]
+pub struct Cli {
+    pub demo: bool,
+    pub demo_sites: usize,
+    pub force: bool,
+    /// Start in carousel mode whatever the config says
+    pub carousel: bool,
+    /// Run without the TUI, logging instead
+    pub headless: bool,
+    /// Where the headless log goes instead of stdout
+    pub log_file: Option<PathBuf>,
+    /// `import` subcommand: convert a file instead of starting the TUI
+    pub import: Option<ImportArgs>,
+    /// `ctl` subcommand: a command for the running instance
+    pub ctl: Option<String>,
+    /// `status` subcommand: how to print the running instance's summary
+    pub status: Option<StatusFormat>,
+    /// `strings` subcommand: print the translation template
+    pub strings: bool,
+}
+
+impl Cli {
+    /// Parse options from the process arguments
+    pub fn parse() -> Result<Self> {
+        let mut cli = Cli::default();
+
+        let mut args = std::env::args().skip(1).peekable();
+        if args.peek().is_some_and(|arg| arg == "import") {
+            args.next();
+            cli.import = Some(Self::parse_import(args)?);
+            return Ok(cli);
+        }
+        if args.peek().is_some_and(|arg| arg == "ctl") {
+            args.next();
+            let command = args.next().unwrap_or_default();
+            if ControlCommand::parse(&command).is_none() || args.next().is_some() {
+                anyhow::bail!("ctl expects one of: {}\n\n{}", ControlCommand::NAMES, USAGE);
+            }
+            cli.ctl = Some(command);
+            return Ok(cli);
+        }
+        if args.peek().is_some_and(|arg| arg == "status") {
+            args.next();
+            let mut format = StatusFormat::Text;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--format" => {
+                        let value = args.next().unwrap_or_default();
+                        format = StatusFormat::parse(&value).ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "Unknown status format '{}' - expected one of: {}\n\n{}",
+                                value,
+                                StatusFormat::NAMES,
+                                USAGE
+                            )
+                        })?;
+                    }
+                    other => anyhow::bail!("Unknown argument '{}'\n\n{}", other, USAGE),
+                }
+            }
+            cli.status = Some(format);
+            return Ok(cli);
+        }
+        if args.peek().is_some_and(|arg| arg == "strings") {
+            cli.strings = true;
+            return Ok(cli);
+        }
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--demo" => cli.demo = true,
+                "--demo-sites" => {
+                    cli.demo = true;
+                    let value = args.next().unwrap_or_default();
+                    cli.demo_sites = value.parse().map_err(|_| {
+                        anyhow::anyhow!("--demo-sites expects a number, got '{}'\n\n{}", value, USAGE)
+                    })?;
+                }
+                "--force" => cli.force = true,
+                "--carousel" => cli.carousel = true,
+                "--headless" => cli.headless = true,
+                "--log-file" => {
+                    cli.headless = true;
+                    let value = args.next().filter(|v| !v.is_empty()).ok_or_else(|| {
+                        anyhow::anyhow!("--log-file expects a file path\n\n{}", USAGE)
+                    })?;
+                    cli.log_file = Some(PathBuf::from(value));
+                }
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    std::process::exit(0);
+                }
+                other => anyhow::bail!("Unknown argument '{}'\n\n{}", other, USAGE),
+            }
+        }
+
+        Ok(cli)
+    }
+
+    /// Arguments of `import`: the file to convert and how
+    fn parse_import(mut args: impl Iterator<Item = String>) -> Result<ImportArgs> {
+        let mut format = ImportFormat::Urls;
+        let mut append = false;
+        let mut file = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => {
+                    let value = args.next().unwrap_or_default();
+                    format = ImportFormat::parse(&value).ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Unknown import format '{}' - expected one of: {}\n\n{}",
+                            value,
+                            ImportFormat::NAMES,
+                            USAGE
+                        )
+                    })?;
+                }
+                "--append" => append = true,
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    std::process::exit(0);
+                }
+                other if (other != "-" && other.starts_with('-')) || file.is_some() => {
+                    anyhow::bail!("Unknown argument '{}'\n\n{}", other, USAGE)
+                }
+                other => file = Some(PathBuf::from(other)),
+            }
+        }
+
+        match file {
+            Some(file) => Ok(ImportArgs { format, file, append }),
+            None => anyhow::bail!("import needs a file to convert\n\n{}", USAGE),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use crate::alerts::Alert;
+use crate::app::{App, View};
+use crate::checker::Status;
+use anyhow::{Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::Local;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Plain text of what the current view shows, for pasting into chat; None
+/// for views with nothing worth copying
+pub fn view_text(app: &App) -> Option<String> {
+    match &app.current_view {
+        View::Dashboard => Some(dashboard_text(app)),
+        View::Detail(name) => detail_text(app, name),
+        View::Alerts => Some(alerts_text(app)),
+        View::AlertDetail(index) => app.listed_alerts().get(*index).map(|alert| alert_text(alert)),
+        _ => None,
+    }
+}
+
+/// The dashboard as an aligned table, in dashboard order
+fn dashboard_text(app: &App) -> String {
+    let dash = || "--".to_string();
+    let rows: Vec<_> = app
+        .dashboard_order()
+        .into_iter()
+        .filter_map(|(index, depth)| {
+            let (name, history) = app.sites.get_index(index)?;
+            // Members of expanded composites are indented under them
+            Some((format!("{}{}", "  ".repeat(depth), name), name, history))
+        })
+        .collect();
+    let width = rows.iter().map(|(row, _, _)| row.chars().count()).max().unwrap_or(0).max(4);
+
+    let mut text = String::new();
+    let _ = writeln!(text, "Sites as of {}", Local::now().format("%Y-%m-%d %H:%M:%S"));
+    let _ = writeln!(
+        text,
+        "{:<width$}  {:<12} {:>9} {:>5} {:>8} {:>6}",
+        "Site", "Status", "Response", "HTTP", "Uptime", "Health"
+    );
+    for (row, name, history) in rows {
+        let latest = history.latest();
+        let status = match latest {
+            Some(result) if result.status != Status::Up && app.blocked_by(name).is_some() => "BLOCKED",
+            Some(result) => result.status.label(),
+            None => "--",
+        };
+        let _ = writeln!(
+            text,
+            "{:<width$}  {:<12} {:>9} {:>5} {:>8} {:>6}",
+            row,
+            status,
+            latest.and_then(|r| r.response_time_ms).map_or_else(dash, |ms| format!("{}ms", ms)),
+            latest.and_then(|r| r.http_status).map_or_else(dash, |code| code.to_string()),
+            history.uptime(app.uptime_window).map_or_else(dash, |pct| format!("{:.1}%", pct)),
+            app.health_score(name).map_or_else(dash, |score| score.to_string()),
+        );
+    }
+    text
+}
+
+/// The detail view's status and statistics for one site
+fn detail_text(app: &App, name: &str) -> Option<String> {
+    let history = app.sites.get(name)?;
+    let site = app.config.site(name)?;
+    let latest = history.latest();
+    let na = || "N/A".to_string();
+    let ms = |ms: Option<u64>| ms.map_or_else(na, |ms| format!("{}ms", ms));
+
+    let mut text = String::new();
+    let _ = writeln!(text, "{} ({})", name, site.target());
+    let _ = writeln!(text, "Status:        {}", latest.map_or("--", |r| r.status.label()));
+    if let Some(result) = latest {
+        let _ = writeln!(
+            text,
+            "Last checked:  {}",
+            result.timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S")
+        );
+        let _ = writeln!(text, "Response:      {}", ms(result.response_time_ms));
+        if let Some(code) = result.http_status {
+            let _ = writeln!(text, "HTTP status:   {}", code);
+        }
+        if let Some(error) = &result.error_message {
+            let _ = writeln!(text, "Error:         {}", error);
+        }
+    }
+    let _ = writeln!(
+        text,
+        "Uptime ({}): {}",
+        app.uptime_window.label(),
+        history.uptime(app.uptime_window).map_or_else(na, |pct| format!("{:.1}%", pct))
+    );
+    let _ = writeln!(
+        text,
+        "Response times over the last {} checks: avg {}, min {}, max {}",
+        history.len(),
+        ms(history.avg_response_time()),
+        ms(history.min_response_time()),
+        ms(history.max_response_time())
+    );
+    for finding in history.current_diagnostics().unwrap_or_default() {
+        let _ = writeln!(text, "Diagnostics:   {}", finding);
+    }
+    Some(text)
+}
+
+/// The alert history, most recent first as listed
+fn alerts_text(app: &App) -> String {
+    let mut text = String::new();
+    let _ = writeln!(text, "Alert history ({} total)", app.alert_history.len());
+    for alert in app.listed_alerts() {
+        let _ = write!(
+            text,
+            "{}  {:<8}  {}",
+            alert.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            alert.severity.label(),
+            alert.message
+        );
+        if alert.silenced {
+            let _ = write!(text, " (silenced)");
+        }
+        if let Some(comment) = &alert.comment {
+            let _ = write!(text, " ({})", comment);
+        }
+        if alert.acknowledged {
+            let _ = write!(text, " (acknowledged)");
+        }
+        let _ = writeln!(text);
+    }
+    text
+}
+
+/// One alert with what was found and noted about it
+fn alert_text(alert: &Alert) -> String {
+    let mut text = String::new();
+    let _ = writeln!(
+        text,
+        "{} {}: {}",
+        alert.timestamp.format("%Y-%m-%d %H:%M:%S"),
+        alert.severity.label(),
+        alert.message
+    );
+    let _ = writeln!(
+        text,
+        "Status: {} -> {}",
+        alert.previous_status.label(),
+        alert.current_status.label()
+    );
+    for line in alert.check_context().iter().chain(&alert.diagnostics).chain(&alert.notes) {
+        let _ = writeln!(text, "{}", line);
+    }
+    if let Some(comment) = &alert.comment {
+        let _ = writeln!(text, "Comment: {}", comment);
+    }
+    text
+}
+
+/// Put `text` on the system clipboard, returning what did it. Uses the
+/// platform's clipboard tool when there's a display to reach, and otherwise
+/// asks the terminal to (OSC 52), which also works over SSH.
+pub fn copy(text: &str) -> Result<&'static str> {
+    let tools: &[(&'static str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("pbcopy", &[])]
+    } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        &[("wl-copy", &[])]
+    } else if std::env::var_os("DISPLAY").is_some() {
+        &[("xclip", &["-selection", "clipboard"]), ("xsel", &["--clipboard", "--input"])]
+    } else {
+        &[]
+    };
+
+    for (program, args) in tools {
+        let Ok(mut child) = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes()).with_context(|| format!("Couldn't write to {}", program))?;
+        }
+        if child.wait()?.success() {
+            return Ok(program);
+        }
+    }
+
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", BASE64.encode(text))?;
+    stdout.flush()?;
+    Ok("the terminal")
+}
@@ -1,18 +1,55 @@
 use anyhow::{Context, Result};
+use chrono::TimeDelta;
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use url::Url;
 
+use crate::alerts::{AlertSeverity, Silence, StatusTransition};
+use crate::checker::Status;
 use crate::ui::theme::ThemeName;
+use ratatui::style::Color;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     pub settings: Settings,
+    #[serde(default)]
     pub sites: Vec<SiteConfig>,
     #[serde(default)]
     pub server_metrics: Option<ServerMetricsConfig>,
+    #[serde(default)]
+    pub mqtt: Option<MqttConfig>,
+    /// Mail server for emailed reports
+    #[serde(default)]
+    pub smtp: Option<SmtpConfig>,
+    /// Scheduled availability report, sent through `smtp`
+    #[serde(default)]
+    pub report: Option<ReportConfig>,
+    #[serde(default)]
+    pub http_server: Option<HttpServerConfig>,
+    #[serde(default)]
+    pub dns: Option<DnsConfig>,
+    /// Named routes out to the internet that sites can be checked through
+    #[serde(default)]
+    pub egress: HashMap<String, EgressProfile>,
+    /// Alerts recorded without notifying, by site glob, severity and end time
+    #[serde(default)]
+    pub silences: Vec<Silence>,
+    /// Sources that add and remove sites while running
+    #[serde(default)]
+    pub discovery: DiscoveryConfig,
+    /// Centrally managed site list, fetched at startup and on an interval
+    #[serde(default)]
+    pub sites_url: Option<RemoteSitesConfig>,
+    /// File this configuration was loaded from
+    #[serde(skip)]
+    pub source_path: PathBuf,
+    /// Position of each site in `sites`, keyed by name
+    #[serde(skip)]
+    site_index: HashMap<String, usize>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct Settings {
     #[serde(default = "default_refresh_interval")]
     pub refresh_interval: u64,
@@ -20,27 +57,537 @@ pub struct Settings {
     pub history_size: usize,
     #[serde(default = "default_request_timeout")]
     pub request_timeout: u64,
+    /// What a check that times out counts as
+    #[serde(default)]
+    pub timeout_status: TimeoutStatus,
     #[serde(default)]
     pub theme: ThemeName,
+    /// Remember runtime UI choices (theme, expanded composites) across sessions
+    #[serde(default)]
+    pub persist_preferences: bool,
+    /// Where preferences are kept (default: the platform state directory)
+    #[serde(default)]
+    pub preferences_file: Option<PathBuf>,
+    /// Apply edits to the config file while running
+    #[serde(default = "default_true")]
+    pub watch_config: bool,
+    /// Keep check results on disk so history and uptime survive restarts
+    #[serde(default)]
+    pub persist_history: bool,
+    /// Where check results are kept (default: the platform data directory)
+    #[serde(default)]
+    pub history_file: Option<PathBuf>,
     #[serde(default)]
     pub alerts: AlertSettings,
     #[serde(default)]
+    pub chart: ChartSettings,
+    #[serde(default)]
     pub warning_threshold_ms: Option<u64>,
+    /// Record the negotiated TLS version and cipher of HTTPS checks
+    #[serde(default)]
+    pub inspect_tls: bool,
+    /// Warn when an HTTPS site's certificate is revoked, per OCSP or its CRL,
+    /// or its status can't be verified
+    #[serde(default)]
+    pub check_revocation: bool,
+    /// Probe DNS, TCP, TLS and the route when a site goes Down
+    #[serde(default)]
+    pub diagnostics: bool,
+    /// Treat 429 (and 503 with Retry-After) as rate limiting: hold off as asked
+    /// and don't count it toward alerts or uptime
+    #[serde(default)]
+    pub respect_retry_after: bool,
+    /// Warn when a check takes more than this many times the site's trailing median
+    #[serde(default)]
+    pub baseline_factor: Option<f64>,
+    /// Seconds of history the baseline median covers
+    #[serde(default = "default_baseline_window")]
+    pub baseline_window: u64,
+    #[serde(default = "default_true")]
+    pub single_instance: bool,
+    /// Accept commands like `monitor-tui ctl pause` on a unix socket
+    #[serde(default = "default_true")]
+    pub control_socket: bool,
+    #[serde(default = "default_true")]
+    pub stagger_start: bool,
+    #[serde(default)]
+    pub interval_jitter_percent: u64,
+    /// Back off checks of a site that stays Down, doubling up to this many seconds
+    #[serde(default)]
+    pub backoff_max_interval: Option<u64>,
+    /// Consecutive Down checks before backoff starts
+    #[serde(default = "default_backoff_after_failures")]
+    pub backoff_after_failures: u32,
+    /// Check stable sites less often and recently changed sites more often
+    #[serde(default)]
+    pub adaptive_intervals: bool,
+    /// Fastest adaptive interval in seconds (default: a quarter of the site's interval)
+    #[serde(default)]
+    pub adaptive_min_interval: Option<u64>,
+    /// Slowest adaptive interval in seconds (default: four times the site's interval)
+    #[serde(default)]
+    pub adaptive_max_interval: Option<u64>,
+    /// Results that can queue for the UI (default: one per site, 100-4096)
+    #[serde(default)]
+    pub result_channel_capacity: Option<usize>,
+    /// What a checker does when the result queue is full
+    #[serde(default)]
+    pub result_overflow: ResultOverflow,
+    /// Seconds an unused pooled connection is kept open (reqwest default: 90)
+    #[serde(default)]
+    pub pool_idle_timeout: Option<u64>,
+    /// Idle connections kept per host (default: unlimited)
+    #[serde(default)]
+    pub pool_max_idle_per_host: Option<usize>,
+    /// TCP keepalive probe interval in seconds (default: off)
+    #[serde(default)]
+    pub tcp_keepalive: Option<u64>,
+    /// Directory exported charts are written to (default: the working directory)
+    #[serde(default)]
+    pub export_dir: Option<PathBuf>,
+    /// Draw the detail chart as an image on terminals with a graphics protocol
+    #[serde(default)]
+    pub chart_graphics: ChartGraphics,
+    /// Start cycling through each site's detail view, for unattended displays
+    #[serde(default)]
+    pub carousel: bool,
+    /// Seconds each site's detail view is shown while cycling
+    #[serde(default = "default_carousel_interval")]
+    pub carousel_interval: u64,
+    /// Ask before destructive one-key actions, like quitting while sites are down
+    #[serde(default = "default_true")]
+    pub confirm_actions: bool,
+    /// Span of time uptime figures cover; 'u' in the detail view cycles it
+    #[serde(default)]
+    pub uptime_window: UptimeWindow,
+    /// Language of help text, footers and alert messages, translated by
+    /// `locales/<locale>.toml` next to the config file (default: English)
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Show a status summary like "monitor: 12/14 UP, 2 DOWN" in the terminal title
+    #[serde(default)]
+    pub terminal_title: bool,
+    /// User-Agent of HTTP checks (default: "monitor-tui/<version>")
+    #[serde(default)]
+    pub user_agent: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Terminal graphics protocol for the detail chart
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartGraphics {
+    /// Always draw the chart with text characters
+    #[default]
+    Off,
+    /// Use the protocol the terminal advertises, falling back to text
+    Auto,
+    Kitty,
+    Iterm2,
+    Sixel,
+}
+
+/// Span of time uptime is calculated over
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+pub enum UptimeWindow {
+    /// Whatever checks the history holds, so it depends on history_size
+    #[default]
+    #[serde(rename = "history")]
+    History,
+    #[serde(rename = "1h")]
+    Hour,
+    #[serde(rename = "24h")]
+    Day,
+    #[serde(rename = "7d")]
+    Week,
+}
+
+impl UptimeWindow {
+    pub fn next(self) -> Self {
+        match self {
+            Self::History => Self::Hour,
+            Self::Hour => Self::Day,
+            Self::Day => Self::Week,
+            Self::Week => Self::History,
+        }
+    }
+
+    /// How far back the window reaches; None for the stored history
+    pub fn duration(self) -> Option<TimeDelta> {
+        match self {
+            Self::History => None,
+            Self::Hour => Some(TimeDelta::hours(1)),
+            Self::Day => Some(TimeDelta::days(1)),
+            Self::Week => Some(TimeDelta::days(7)),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::History => "stored checks",
+            Self::Hour => "last 1h",
+            Self::Day => "last 24h",
+            Self::Week => "last 7d",
+        }
+    }
+}
+
+/// Behavior when checkers produce results faster than the UI consumes them
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultOverflow {
+    /// Wait for room, delaying that site's next check
+    #[default]
+    Block,
+    /// Discard the result and keep the schedule
+    Drop,
+}
+
+/// Status given to a check that times out, since a slow service that's still
+/// alive and a dead one can call for different alerts
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeoutStatus {
+    #[default]
+    Down,
+    Warning,
+}
+
+/// Methods a site's check can use
+const HTTP_METHODS: [&str; 7] = ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"];
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct SiteConfig {
     pub name: String,
+    #[serde(default)]
+    pub kind: SiteKind,
+    #[serde(default)]
     pub url: String,
+    #[serde(default = "default_expected_status")]
     pub expected_status: u16,
     pub check_interval: Option<u64>,
+    /// Cron expression for when to check, e.g. "*/5 8-20 * * MON-FRI" (local time)
+    #[serde(default)]
+    pub schedule: Option<String>,
+    #[serde(default)]
+    pub request_timeout: Option<u64>,
+    /// Override the global timeout_status
+    #[serde(default)]
+    pub timeout_status: Option<TimeoutStatus>,
+    /// Override the global backoff cap; 0 disables backoff for this site
+    #[serde(default)]
+    pub backoff_max_interval: Option<u64>,
+    /// Override the global adaptive_intervals setting
+    #[serde(default)]
+    pub adaptive_intervals: Option<bool>,
     #[serde(default)]
     pub alerts: Option<SiteAlertSettings>,
     #[serde(default)]
     pub warning_threshold_ms: Option<u64>,
+    /// Override the global baseline_factor
+    #[serde(default)]
+    pub baseline_factor: Option<f64>,
+    /// Warn if HTTPS negotiates an older TLS version than this ("1.0"-"1.3");
+    /// turns on TLS inspection for the site
+    #[serde(default)]
+    pub min_tls_version: Option<String>,
+    /// Override the global check_revocation setting
+    #[serde(default)]
+    pub check_revocation: Option<bool>,
+    /// Override the global diagnostics setting
+    #[serde(default)]
+    pub diagnostics: Option<bool>,
+    /// Override the global respect_retry_after setting
+    #[serde(default)]
+    pub respect_retry_after: Option<bool>,
+    /// User-Agent sent with this site's requests, instead of the global one
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// HTTP method of the check (default: GET)
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Headers sent with this site's requests, e.g. Authorization
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Request body, e.g. for an endpoint that only answers POST
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// What the site is, for whoever is looking at it
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Who to contact about the site, e.g. a team or on-call rotation
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Where the runbook for the site lives
+    #[serde(default)]
+    pub runbook_url: Option<String>,
+    /// Monthly availability target as a percentage, e.g. 99.9, for the error budget view
+    #[serde(default)]
+    pub slo_target: Option<f64>,
+    /// Heartbeat sites: seconds without a ping before the site is Down
+    #[serde(default)]
+    pub grace_period: Option<u64>,
+    /// Heartbeat sites: path token for the ping URL (defaults to the slugified name)
+    #[serde(default)]
+    pub heartbeat_token: Option<String>,
+    /// Resolve this site's host fresh on every check (requires `[dns]`)
+    #[serde(default)]
+    pub dns_bypass_cache: bool,
+    /// Open a new connection for every check instead of reusing pooled ones
+    #[serde(default)]
+    pub disable_pooling: bool,
+    /// Egress profiles to check through in parallel, e.g. ["direct", "eu-proxy"]
+    #[serde(default)]
+    pub egress: Vec<String>,
+    /// SSH jump host to check through, for hosts not reachable from here, e.g.
+    /// "bastion.example.com" (users, ports and keys come from ~/.ssh/config)
+    #[serde(default)]
+    pub via: Option<String>,
+    /// Times of day when the site isn't checked at all
+    #[serde(default)]
+    pub blackouts: Vec<Blackout>,
+    /// Sites this one can't work without; while any is Down this site shows as
+    /// BLOCKED and its alerts are suppressed
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Composite sites: names of the sites whose statuses are combined
+    #[serde(default)]
+    pub members: Vec<String>,
+    /// Composite sites: how member statuses combine
+    #[serde(default)]
+    pub aggregate: Aggregate,
+    /// Composite sites with aggregate = "quorum": members that must be Up (default: majority)
+    #[serde(default)]
+    pub quorum: Option<usize>,
+    /// Asset URLs, absolute or relative to `url`, that must not return an error
+    #[serde(default)]
+    pub assets: Vec<String>,
+    /// XPath checks on the XML response body, all of which must pass
+    #[serde(default)]
+    pub xml_assertions: Vec<XmlAssertion>,
+    /// Namespace prefixes usable in xml_assertions, e.g. soap = "http://..."
+    #[serde(default)]
+    pub xml_namespaces: HashMap<String, String>,
+    /// Classification and color of specific HTTP status codes, overriding the
+    /// comparison with expected_status, e.g. 401 = { status = "up" }
+    #[serde(default)]
+    pub status_codes: BTreeMap<String, StatusCodeRule>,
+    /// Warn when the content is older than this many hours, dated by the
+    /// newest RSS/Atom entry or else the Last-Modified header
+    #[serde(default)]
+    pub max_content_age: Option<u64>,
+    /// Warn when the response's media type isn't this, e.g. "application/json"
+    /// (parameters like charset are ignored; "text/*" matches any text type)
+    #[serde(default)]
+    pub expected_content_type: Option<String>,
+    /// Warn when a response with the expected status doesn't contain this
+    /// text, e.g. a 200 that's really an error page
+    #[serde(default)]
+    pub expected_body_contains: Option<String>,
+    /// Warn when a response with the expected status doesn't match this regex
+    #[serde(default)]
+    pub expected_body_regex: Option<String>,
+    /// Go Down when redirects end on a host outside these domains (besides
+    /// the URL's own); "*.example.com" allows any subdomain
+    #[serde(default)]
+    pub allowed_redirect_domains: Vec<String>,
+    /// GraphQL sites: query posted to the URL
+    #[serde(default)]
+    pub graphql_query: Option<String>,
+    /// GraphQL sites: variables sent with the query
+    #[serde(default)]
+    pub graphql_variables: Option<serde_json::Value>,
+    /// GraphQL sites: dotted paths under `data` and the values they must equal
+    #[serde(default)]
+    pub graphql_expect: BTreeMap<String, serde_json::Value>,
+    /// NTP sites: largest offset from the server's clock, in milliseconds,
+    /// before the site warns (default 1000)
+    #[serde(default)]
+    pub max_clock_drift_ms: Option<u64>,
+    /// Port sweep sites: ports that must take connections
+    #[serde(default)]
+    pub open_ports: Vec<PortSpec>,
+    /// Port sweep sites: ports that must not take connections
+    #[serde(default)]
+    pub closed_ports: Vec<PortSpec>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// A port, or a range of them like "8000-8100"
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum PortSpec {
+    /// Wider than a port, so out-of-range numbers get a clear error when checked
+    Port(u64),
+    Range(String),
+}
+
+impl std::fmt::Display for PortSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PortSpec::Port(port) => write!(f, "{}", port),
+            PortSpec::Range(range) => write!(f, "{}", range),
+        }
+    }
+}
+
+/// A daily window, in local time, during which a site isn't checked
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Blackout {
+    /// "HH:MM" the window opens
+    pub start: String,
+    /// "HH:MM" the window closes; earlier than start means it runs past midnight
+    pub end: String,
+    /// Days the window opens on, e.g. ["sat", "sun"] (default: every day)
+    #[serde(default)]
+    pub days: Vec<String>,
+}
+
+/// An XPath that must match the response, optionally with specific text
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct XmlAssertion {
+    pub xpath: String,
+    /// Expected text of the first match (trimmed); without it the XPath only has to match
+    #[serde(default)]
+    pub equals: Option<String>,
+}
+
+/// How one HTTP status code from a site is classified and shown
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatusCodeRule {
+    /// Status the check gets for this code instead of comparing with expected_status
+    #[serde(default)]
+    pub status: Option<CodeStatus>,
+    /// Color of the code in the dashboard and detail view, e.g. "magenta" or "#ff8800"
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Status a mapped HTTP status code stands for
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeStatus {
+    Up,
+    Warning,
+    Down,
+}
+
+/// How a site is monitored
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SiteKind {
+    /// Actively request the URL
+    #[default]
+    Http,
+    /// Post a GraphQL query and check the response for errors
+    Graphql,
+    /// Passively wait for pings on the embedded HTTP server
+    Heartbeat,
+    /// Combine the statuses of other sites
+    Composite,
+    /// Ask an NTP server for the time and compare it with the local clock
+    Ntp,
+    /// Connect to a host's TCP ports, expecting some open and others closed
+    Ports,
+}
+
+/// How a composite site's members combine into one status
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Aggregate {
+    /// Up only while every member is Up
+    #[default]
+    All,
+    /// Up while at least `quorum` members are Up
+    Quorum,
+    /// Up while any member is Up
+    Any,
+}
+
+impl SiteConfig {
+    /// Token in the heartbeat ping URL: `/heartbeat/<token>`
+    pub fn heartbeat_token(&self) -> String {
+        self.heartbeat_token
+            .clone()
+            .unwrap_or_else(|| slugify(&self.name))
+    }
+
+    /// What is being monitored, for display
+    pub fn target(&self) -> String {
+        let url = match &self.via {
+            Some(via) => format!("{} via {}", self.url, via),
+            None => self.url.clone(),
+        };
+        match self.kind {
+            SiteKind::Http => url,
+            SiteKind::Graphql => format!("graphql: {}", url),
+            SiteKind::Heartbeat => format!("heartbeat: /heartbeat/{}", self.heartbeat_token()),
+            SiteKind::Composite => format!("composite: {}", self.members.join(", ")),
+            SiteKind::Ntp | SiteKind::Ports => url,
+        }
+    }
+
+    /// Status an HTTP status code gives a check: the site's mapping for it, else
+    /// Up for expected_status and Warning for anything else
+    pub fn code_status(&self, code: u16) -> Status {
+        match self.status_codes.get(&code.to_string()).and_then(|rule| rule.status) {
+            Some(CodeStatus::Up) => Status::Up,
+            Some(CodeStatus::Warning) => Status::Warning,
+            Some(CodeStatus::Down) => Status::Down,
+            None if code == self.expected_status => Status::Up,
+            None => Status::Warning,
+        }
+    }
+
+    /// Method of the site's check request
+    pub fn http_method(&self) -> reqwest::Method {
+        self.method
+            .as_deref()
+            .and_then(|method| reqwest::Method::from_bytes(method.to_uppercase().as_bytes()).ok())
+            .unwrap_or(reqwest::Method::GET)
+    }
+
+    /// Color configured for an HTTP status code, if any
+    pub fn code_color(&self, code: u16) -> Option<Color> {
+        self.status_codes.get(&code.to_string())?.color.as_deref()?.parse().ok()
+    }
+
+    /// Members that must be Up for a composite site to be Up
+    pub fn required_members(&self) -> usize {
+        match self.aggregate {
+            Aggregate::All => self.members.len(),
+            Aggregate::Quorum => self.quorum.unwrap_or(self.members.len() / 2 + 1),
+            Aggregate::Any => 1,
+        }
+    }
+
+    /// Description, owner and runbook as lines to show with the site's alerts
+    pub fn notes(&self) -> Vec<String> {
+        let mut notes = Vec::new();
+        if let Some(description) = &self.description {
+            notes.push(description.clone());
+        }
+        if let Some(owner) = &self.owner {
+            notes.push(format!("Owner: {}", owner));
+        }
+        if let Some(runbook) = &self.runbook_url {
+            notes.push(format!("Runbook: {}", runbook));
+        }
+        notes
+    }
+}
+
+/// Turn a name into an identifier safe for URLs and topics ("Family Site" -> "family_site")
+pub fn slugify(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct AlertSettings {
     #[serde(default = "default_true")]
     pub enabled: bool,
@@ -56,9 +603,60 @@ pub struct AlertSettings {
     pub cooldown_seconds: u64,
     #[serde(default)]
     pub transitions: TransitionSettings,
+    /// Severity given to each transition's alerts, where not the default
+    #[serde(default)]
+    pub severity: SeveritySettings,
+    /// Give up on a single notification send after this many seconds
+    #[serde(default = "default_notification_timeout_seconds")]
+    pub notification_timeout_seconds: u64,
+    /// URLs each alert is POSTed to as JSON
+    #[serde(default)]
+    pub webhooks: Vec<String>,
+    /// Slack incoming webhook URLs each alert is posted to as a message
+    #[serde(default)]
+    pub slack_webhooks: Vec<String>,
+    /// Discord webhook URLs each alert is posted to as a message
+    #[serde(default)]
+    pub discord_webhooks: Vec<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// How the dashboard sparklines and the detail chart are drawn
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ChartSettings {
+    /// Checks shown in each dashboard sparkline
+    #[serde(default = "default_sparkline_length")]
+    pub sparkline_length: usize,
+    #[serde(default)]
+    pub marker: ChartMarker,
+    #[serde(default)]
+    pub down_checks: DownCheckStyle,
+}
+
+/// Characters the detail chart is drawn with
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartMarker {
+    /// Two dots per cell: the finest line
+    #[default]
+    Braille,
+    Block,
+    Dot,
+}
+
+/// How checks without a response time (Down) appear in charts
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DownCheckStyle {
+    /// Break the line where the site was down
+    #[default]
+    Gap,
+    /// Draw the line down to 0ms
+    Zero,
+    /// Mark outages in a separate series in the Down color
+    Series,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct TransitionSettings {
     #[serde(default = "default_true")]
     pub up_to_down: bool,
@@ -72,6 +670,38 @@ pub struct TransitionSettings {
     pub warn_to_up: bool,
     #[serde(default = "default_false")]
     pub down_to_warn: bool,
+    /// No result for two check intervals
+    #[serde(default = "default_false")]
+    pub stale: bool,
+}
+
+/// Severity per transition: "critical", "warning", "recovery" or "info".
+/// Unset transitions keep the default (down is critical, warn is warning,
+/// up is recovery).
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeveritySettings {
+    pub up_to_down: Option<AlertSeverity>,
+    pub up_to_warn: Option<AlertSeverity>,
+    pub down_to_up: Option<AlertSeverity>,
+    pub warn_to_down: Option<AlertSeverity>,
+    pub warn_to_up: Option<AlertSeverity>,
+    pub down_to_warn: Option<AlertSeverity>,
+    pub stale: Option<AlertSeverity>,
+}
+
+impl SeveritySettings {
+    fn get(&self, transition: &StatusTransition) -> Option<AlertSeverity> {
+        match transition {
+            StatusTransition::UpToDown => self.up_to_down.clone(),
+            StatusTransition::UpToWarn => self.up_to_warn.clone(),
+            StatusTransition::DownToUp => self.down_to_up.clone(),
+            StatusTransition::WarnToDown => self.warn_to_down.clone(),
+            StatusTransition::WarnToUp => self.warn_to_up.clone(),
+            StatusTransition::DownToWarn => self.down_to_warn.clone(),
+            StatusTransition::Stale => self.stale.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -82,12 +712,240 @@ pub struct ServerMetricsConfig {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+pub struct MqttConfig {
+    pub host: String,
+    #[serde(default = "default_mqtt_port")]
+    pub port: u16,
+    #[serde(default = "default_mqtt_client_id")]
+    pub client_id: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    #[serde(default = "default_mqtt_topic_prefix")]
+    pub topic_prefix: String,
+    #[serde(default = "default_true")]
+    pub retain: bool,
+    #[serde(default = "default_false")]
+    pub home_assistant_discovery: bool,
+    #[serde(default = "default_mqtt_discovery_prefix")]
+    pub discovery_prefix: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    /// Default: 587 with starttls, 465 with tls, 25 without
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub security: SmtpSecurity,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Sender address, e.g. "Monitor <monitor@example.com>"
+    pub from: String,
+    /// Recipient addresses
+    pub to: Vec<String>,
+}
+
+/// How the connection to the mail server is secured
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Upgrade a plain connection with STARTTLS, refusing servers without it
+    #[default]
+    Starttls,
+    /// TLS from the start (SMTPS)
+    Tls,
+    /// No encryption, e.g. for a relay on localhost
+    None,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReportConfig {
+    #[serde(default)]
+    pub period: ReportPeriod,
+    /// Local time of day the report goes out, "HH:MM"
+    #[serde(default = "default_report_send_at")]
+    pub send_at: String,
+}
+
+/// How often the availability report is sent, each covering the time since the last
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportPeriod {
+    /// Mondays
+    #[default]
+    Weekly,
+    /// The first of each month
+    Monthly,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HttpServerConfig {
+    #[serde(default = "default_http_server_bind")]
+    pub bind: String,
+}
+
+/// Sources of sites found at runtime rather than listed in the config
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DiscoveryConfig {
+    #[serde(default)]
+    pub docker: Option<DockerDiscoveryConfig>,
+    #[serde(default)]
+    pub consul: Option<ConsulDiscoveryConfig>,
+    #[serde(default)]
+    pub etcd: Option<EtcdDiscoveryConfig>,
+}
+
+/// Monitor containers that carry `<label_prefix>.url` labels
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DockerDiscoveryConfig {
+    #[serde(default = "default_docker_socket")]
+    pub socket: PathBuf,
+    #[serde(default = "default_docker_label_prefix")]
+    pub label_prefix: String,
+}
+
+/// Monitor the services registered in a Consul catalog
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConsulDiscoveryConfig {
+    #[serde(default = "default_consul_address")]
+    pub address: String,
+    /// Only services with this tag (default: every service)
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub datacenter: Option<String>,
+    /// ACL token sent as X-Consul-Token
+    #[serde(default)]
+    pub token: Option<String>,
+    #[serde(default = "default_discovery_poll_interval")]
+    pub poll_interval: u64,
+}
+
+/// Monitor the sites stored as JSON under a key prefix in etcd (v3 JSON gateway)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EtcdDiscoveryConfig {
+    #[serde(default = "default_etcd_endpoint")]
+    pub endpoint: String,
+    pub prefix: String,
+    #[serde(default = "default_discovery_poll_interval")]
+    pub poll_interval: u64,
+}
+
+/// `sites_url`: where to fetch a sites.toml of `[[sites]]` from, given as a
+/// URL or as a table with the URL and how to fetch it
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "SitesUrl")]
+pub struct RemoteSitesConfig {
+    pub url: String,
+    pub refresh_interval: u64,
+    pub bearer_token: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SitesUrl {
+    Url(String),
+    Table {
+        url: String,
+        #[serde(default = "default_sites_url_refresh")]
+        refresh_interval: u64,
+        #[serde(default)]
+        bearer_token: Option<String>,
+        #[serde(default)]
+        username: Option<String>,
+        #[serde(default)]
+        password: Option<String>,
+    },
+}
+
+impl From<SitesUrl> for RemoteSitesConfig {
+    fn from(value: SitesUrl) -> Self {
+        match value {
+            SitesUrl::Url(url) => Self {
+                url,
+                refresh_interval: default_sites_url_refresh(),
+                bearer_token: None,
+                username: None,
+                password: None,
+            },
+            SitesUrl::Table { url, refresh_interval, bearer_token, username, password } => Self {
+                url,
+                refresh_interval,
+                bearer_token,
+                username,
+                password,
+            },
+        }
+    }
+}
+
+/// DNS resolution for HTTP checks; without this section the OS resolver is used
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DnsConfig {
+    #[serde(default)]
+    pub resolver: DnsResolverKind,
+    /// Name servers as "ip" or "ip:port" (custom and doh resolvers)
+    #[serde(default)]
+    pub servers: Vec<String>,
+    /// Name on the DoH servers' certificate, required with custom DoH servers
+    #[serde(default)]
+    pub tls_name: Option<String>,
+    /// Cache every answer for exactly this many seconds; 0 disables the cache
+    #[serde(default)]
+    pub cache_ttl: Option<u64>,
+}
+
+/// Egress profile name that always means a direct connection, ignoring proxy
+/// environment variables
+pub const DIRECT_EGRESS: &str = "direct";
+
+/// A route for checks to take, e.g. through a proxy in another region
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EgressProfile {
+    /// Proxy URL for every request ("http://", "https://")
+    pub proxy: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DnsResolverKind {
+    /// Name servers from /etc/resolv.conf (or the Windows equivalent)
+    #[default]
+    System,
+    /// Plain DNS to the listed servers
+    Custom,
+    /// DNS-over-HTTPS to the listed servers, or Cloudflare when none are given
+    Doh,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct SiteAlertSettings {
     pub enabled: Option<bool>,
     pub consecutive_failures: Option<usize>,
     pub cooldown_seconds: Option<u64>,
     pub terminal_bell: Option<bool>,
     pub desktop_notifications: Option<bool>,
+    /// Set to false to keep this site's alerts off the webhooks
+    pub webhooks: Option<bool>,
+    /// Slack webhooks for this site's alerts in place of the global ones; [] for none
+    pub slack_webhooks: Option<Vec<String>>,
+    /// Discord webhooks for this site's alerts in place of the global ones; [] for none
+    pub discord_webhooks: Option<Vec<String>>,
+    /// Override the global severity per transition
+    #[serde(default)]
+    pub severity: Option<SeveritySettings>,
 }
 
 impl Default for AlertSettings {
@@ -100,6 +958,21 @@ impl Default for AlertSettings {
             consecutive_failures: 2,
             cooldown_seconds: 300,
             transitions: TransitionSettings::default(),
+            severity: SeveritySettings::default(),
+            notification_timeout_seconds: default_notification_timeout_seconds(),
+            webhooks: Vec::new(),
+            slack_webhooks: Vec::new(),
+            discord_webhooks: Vec::new(),
+        }
+    }
+}
+
+impl Default for ChartSettings {
+    fn default() -> Self {
+        Self {
+            sparkline_length: default_sparkline_length(),
+            marker: ChartMarker::default(),
+            down_checks: DownCheckStyle::default(),
         }
     }
 }
@@ -113,6 +986,7 @@ impl Default for TransitionSettings {
             warn_to_down: true,
             warn_to_up: true,
             down_to_warn: false,
+            stale: false,
         }
     }
 }
@@ -122,6 +996,14 @@ fn default_refresh_interval() -> u64 {
     5
 }
 
+fn default_sparkline_length() -> usize {
+    30
+}
+
+fn default_baseline_window() -> u64 {
+    3600
+}
+
 fn default_history_size() -> usize {
     100
 }
@@ -130,6 +1012,10 @@ fn default_request_timeout() -> u64 {
     3
 }
 
+fn default_carousel_interval() -> u64 {
+    10
+}
+
 fn default_true() -> bool {
     true
 }
@@ -150,29 +1036,112 @@ fn default_cooldown_seconds() -> u64 {
     300
 }
 
+fn default_notification_timeout_seconds() -> u64 {
+    10
+}
+
+fn default_backoff_after_failures() -> u32 {
+    3
+}
+
 fn default_metrics_poll_interval() -> u64 {
     30
 }
 
-impl Config {
-    /// Load configuration from file, checking multiple locations in priority order
-    pub fn load() -> Result<Self> {
-        let config_paths = Self::get_config_paths();
+fn default_expected_status() -> u16 {
+    200
+}
+
+fn default_http_server_bind() -> String {
+    "127.0.0.1:8787".to_string()
+}
+
+fn default_docker_socket() -> PathBuf {
+    PathBuf::from("/var/run/docker.sock")
+}
+
+fn default_docker_label_prefix() -> String {
+    "monitor".to_string()
+}
+
+fn default_consul_address() -> String {
+    "http://127.0.0.1:8500".to_string()
+}
+
+fn default_etcd_endpoint() -> String {
+    "http://127.0.0.1:2379".to_string()
+}
+
+fn default_discovery_poll_interval() -> u64 {
+    30
+}
+
+fn default_sites_url_refresh() -> u64 {
+    300
+}
+
+fn default_report_send_at() -> String {
+    "08:00".to_string()
+}
+
+fn default_mqtt_port() -> u16 {
+    1883
+}
 
-        for path in &config_paths {
-            if path.exists() {
-                let contents = std::fs::read_to_string(path)
-                    .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+fn default_mqtt_client_id() -> String {
+    "monitor-tui".to_string()
+}
 
-                let config: Config = toml::from_str(&contents)
-                    .with_context(|| format!("Failed to parse TOML config: {}", path.display()))?;
+fn default_mqtt_topic_prefix() -> String {
+    "monitor".to_string()
+}
 
-                config.validate()?;
+fn default_mqtt_discovery_prefix() -> String {
+    "homeassistant".to_string()
+}
 
-                println!("Loaded config from: {}", path.display());
-                return Ok(config);
+/// Recursively merge `overlay` into `base`; nested tables are merged key by key,
+/// any other value in `overlay` replaces the one in `base`
+fn deep_merge(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                deep_merge(base_table, overlay_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
             }
         }
+    }
+}
+
+impl Config {
+    /// Load configuration from a file, usually the one `find_path` picks
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let (config, included) = Self::parse_with_includes(&Self::read_contents(path)?, path)?;
+        for (include, sites) in included {
+            println!("Included {} site(s) from: {}", sites, include.display());
+        }
+        println!("Loaded config from: {}", path.display());
+        Ok(config)
+    }
+
+    /// Read and validate the config at `path` without printing, for reloading
+    /// it under the TUI
+    pub fn read(path: &Path) -> Result<Self> {
+        Self::parse(&Self::read_contents(path)?, path)
+    }
+
+    fn read_contents(path: &Path) -> Result<String> {
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read config file: {}", path.display()))
+    }
+
+    /// The first config file that exists, in priority order
+    pub fn find_path() -> Result<PathBuf> {
+        let config_paths = Self::get_config_paths();
+        if let Some(path) = config_paths.iter().find(|path| path.exists()) {
+            return Ok(path.clone());
+        }
 
         anyhow::bail!(
             "No configuration file found. Checked:\n  - {}",
@@ -184,48 +1153,777 @@ impl Config {
         );
     }
 
+    /// Parse and validate config file contents; `path` locates includes and errors
+    pub fn parse(contents: &str, path: &Path) -> Result<Self> {
+        Self::parse_with_includes(contents, path).map(|(config, _)| config)
+    }
+
+    /// Like `parse`, also returning each included file and how many sites it added
+    fn parse_with_includes(contents: &str, path: &Path) -> Result<(Self, Vec<(PathBuf, usize)>)> {
+        let mut raw: toml::Table = toml::from_str(contents)
+            .with_context(|| format!("Failed to parse TOML config: {}", path.display()))?;
+        let included = Self::merge_includes(&mut raw, path)?;
+        Self::apply_site_defaults(&mut raw)?;
+
+        let mut config: Config = toml::Value::Table(raw)
+            .try_into()
+            .with_context(|| format!("Invalid configuration in: {}", path.display()))?;
+        config.index_sites();
+        config.validate()?;
+        config.source_path = path.to_path_buf();
+        Ok((config, included))
+    }
+
+    /// Look up a site's configuration by name
+    pub fn site(&self, name: &str) -> Option<&SiteConfig> {
+        self.site_index.get(name).map(|&i| &self.sites[i])
+    }
+
+    /// Whether `name` is checked the same way under `other`, so a checker
+    /// already running for it can carry on. Settings, DNS and egress profiles
+    /// shape every site's checks, so any change to them counts for all.
+    pub fn checks_site_like(&self, other: &Config, name: &str) -> bool {
+        self.site(name).is_some()
+            && self.site(name) == other.site(name)
+            && self.settings == other.settings
+            && self.dns == other.dns
+            && self.egress == other.egress
+    }
+
+    /// Rebuild the name lookup after `sites` changes
+    fn index_sites(&mut self) {
+        self.site_index.clear();
+        for (i, site) in self.sites.iter().enumerate() {
+            self.site_index.entry(site.name.clone()).or_insert(i);
+        }
+    }
+
+    /// Severity of a site's alerts for a transition: the site's mapping, then
+    /// the global one, then the default
+    pub fn alert_severity(&self, site_name: &str, transition: &StatusTransition) -> AlertSeverity {
+        self.site(site_name)
+            .and_then(|s| s.alerts.as_ref())
+            .and_then(|a| a.severity.as_ref())
+            .and_then(|s| s.get(transition))
+            .or_else(|| self.settings.alerts.severity.get(transition))
+            .unwrap_or_else(|| AlertSeverity::from_transition(transition))
+    }
+
+    /// Whether sites are added while running, so the config may list none
+    pub fn discovers_sites(&self) -> bool {
+        let discovery = &self.discovery;
+        discovery.docker.is_some() || discovery.consul.is_some() || discovery.etcd.is_some() || self.sites_url.is_some()
+    }
+
+    /// A copy with a site added at runtime
+    pub fn with_site(&self, site: SiteConfig) -> Self {
+        let mut config = self.clone();
+        config.sites.push(site);
+        config.index_sites();
+        config
+    }
+
+    /// A copy without a site added at runtime
+    pub fn without_site(&self, name: &str) -> Self {
+        let mut config = self.clone();
+        config.sites.retain(|site| site.name != name);
+        config.index_sites();
+        config
+    }
+
+    /// Append synthetic sites so demo mode can exercise large fleets
+    pub fn add_demo_sites(&mut self, count: usize) {
+        let start = self.sites.len();
+        self.sites.extend((start..start + count).map(|i| SiteConfig {
+            name: format!("Demo Site {:04}", i + 1),
+            kind: SiteKind::Http,
+            url: format!("https://demo-{:04}.example.com", i + 1),
+            expected_status: default_expected_status(),
+            check_interval: None,
+            schedule: None,
+            request_timeout: None,
+            timeout_status: None,
+            backoff_max_interval: None,
+            adaptive_intervals: None,
+            alerts: None,
+            warning_threshold_ms: None,
+            baseline_factor: None,
+            min_tls_version: None,
+            check_revocation: None,
+            diagnostics: None,
+            respect_retry_after: None,
+            user_agent: None,
+            method: None,
+            headers: BTreeMap::new(),
+            body: None,
+            tags: vec!["demo".to_string()],
+            description: None,
+            owner: None,
+            runbook_url: None,
+            slo_target: None,
+            grace_period: None,
+            heartbeat_token: None,
+            dns_bypass_cache: false,
+            disable_pooling: false,
+            egress: Vec::new(),
+            via: None,
+            blackouts: Vec::new(),
+            depends_on: Vec::new(),
+            members: Vec::new(),
+            aggregate: Aggregate::All,
+            quorum: None,
+            assets: Vec::new(),
+            xml_assertions: Vec::new(),
+            xml_namespaces: HashMap::new(),
+            status_codes: BTreeMap::new(),
+            max_content_age: None,
+            expected_content_type: None,
+            expected_body_contains: None,
+            expected_body_regex: None,
+            allowed_redirect_domains: Vec::new(),
+            graphql_query: None,
+            graphql_variables: None,
+            graphql_expect: BTreeMap::new(),
+            max_clock_drift_ms: None,
+            open_ports: Vec::new(),
+            closed_ports: Vec::new(),
+        }));
+        self.index_sites();
+    }
+
+    /// Read and deserialize a TOML file
+    fn read_toml<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse TOML config: {}", path.display()))
+    }
+
+    /// Append `[[sites]]` from every file matched by the top-level `include`
+    /// patterns, returning each file with the number of sites it added
+    fn merge_includes(raw: &mut toml::Table, config_path: &Path) -> Result<Vec<(PathBuf, usize)>> {
+        let Some(patterns) = raw.remove("include") else {
+            return Ok(Vec::new());
+        };
+        let patterns: Vec<String> = patterns
+            .try_into()
+            .context("'include' must be a list of glob patterns")?;
+
+        let base_dir = config_path.parent().unwrap_or(Path::new("."));
+        let mut included_sites = Vec::new();
+        let mut counts = Vec::new();
+
+        for pattern in &patterns {
+            let full_pattern = base_dir.join(pattern);
+            let full_pattern = full_pattern.to_string_lossy();
+
+            let mut matches: Vec<PathBuf> = glob::glob(&full_pattern)
+                .with_context(|| format!("Invalid include pattern '{}'", pattern))?
+                .filter_map(|entry| entry.ok())
+                .filter(|path| path.is_file())
+                .collect();
+            // Keep merge order stable regardless of filesystem ordering
+            matches.sort();
+
+            for path in matches {
+                let mut included: toml::Table = Self::read_toml(&path)?;
+                let sites = match included.remove("sites") {
+                    Some(toml::Value::Array(sites)) => sites,
+                    Some(_) => anyhow::bail!("'sites' in {} must be an array of tables", path.display()),
+                    None => Vec::new(),
+                };
+                counts.push((path, sites.len()));
+                included_sites.extend(sites);
+            }
+        }
+
+        match raw
+            .entry("sites")
+            .or_insert_with(|| toml::Value::Array(Vec::new()))
+        {
+            toml::Value::Array(sites) => sites.extend(included_sites),
+            _ => anyhow::bail!("'sites' must be an array of tables"),
+        }
+
+        Ok(counts)
+    }
+
+    /// Layer `[site_defaults]` and any referenced `[templates.<name>]` under each site.
+    /// Precedence (lowest to highest): site_defaults, template, the site's own keys.
+    pub fn apply_site_defaults(raw: &mut toml::Table) -> Result<()> {
+        let defaults = match raw.remove("site_defaults") {
+            Some(toml::Value::Table(table)) => table,
+            Some(_) => anyhow::bail!("'site_defaults' must be a table"),
+            None => toml::Table::new(),
+        };
+        let templates = match raw.remove("templates") {
+            Some(toml::Value::Table(table)) => table,
+            Some(_) => anyhow::bail!("'templates' must be a table of named templates"),
+            None => toml::Table::new(),
+        };
+
+        let Some(toml::Value::Array(sites)) = raw.get_mut("sites") else {
+            return Ok(());
+        };
+
+        for site in sites.iter_mut() {
+            let toml::Value::Table(site_table) = site else {
+                anyhow::bail!("Each entry in 'sites' must be a table");
+            };
+
+            let site_name = site_table
+                .get("name")
+                .and_then(|v| v.as_str())
+                .unwrap_or("<unnamed>")
+                .to_string();
+            let mut merged = defaults.clone();
+
+            if let Some(template) = site_table.remove("template") {
+                let template_name = template.as_str().with_context(|| {
+                    format!("Site '{}' has invalid 'template' - must be a template name", site_name)
+                })?;
+                match templates.get(template_name) {
+                    Some(toml::Value::Table(template_table)) => {
+                        deep_merge(&mut merged, template_table.clone())
+                    }
+                    _ => anyhow::bail!(
+                        "Site '{}' references unknown template '{}'",
+                        site_name,
+                        template_name
+                    ),
+                }
+            }
+
+            deep_merge(&mut merged, std::mem::take(site_table));
+            *site_table = merged;
+        }
+
+        Ok(())
+    }
+
     /// Get list of config file paths in priority order
-    fn get_config_paths() -> Vec<PathBuf> {
+    pub fn get_config_paths() -> Vec<PathBuf> {
         let mut paths = Vec::new();
 
         // 1. Current directory
         paths.push(PathBuf::from("./sites.toml"));
 
-        // 2. User config directory
-        if let Some(home) = std::env::var_os("HOME") {
-            let mut path = PathBuf::from(home);
-            path.push(".config/monitor/sites.toml");
-            paths.push(path);
+        // 2. User config directory: $XDG_CONFIG_HOME wherever it is set, then the
+        //    platform default (~/.config, ~/Library/Application Support, %APPDATA%)
+        if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+            paths.push(PathBuf::from(xdg).join("monitor/sites.toml"));
+        }
+        if let Some(dir) = dirs::config_dir() {
+            paths.push(dir.join("monitor").join("sites.toml"));
+        }
+        // Many CLI users keep dotfiles in ~/.config on macOS too
+        if let Some(home) = dirs::home_dir() {
+            paths.push(home.join(".config/monitor/sites.toml"));
         }
 
         // 3. System config directory
+        #[cfg(unix)]
         paths.push(PathBuf::from("/etc/monitor/sites.toml"));
+        #[cfg(windows)]
+        if let Some(program_data) = std::env::var_os("PROGRAMDATA") {
+            paths.push(PathBuf::from(program_data).join("monitor").join("sites.toml"));
+        }
+
+        // Platform defaults often coincide (e.g. XDG on Linux); keep the first of each
+        let mut seen = HashSet::new();
+        paths.retain(|path| seen.insert(path.clone()));
 
         paths
     }
 
+    /// Every `depends_on` and composite `members` entry must name another
+    /// site, without cycles
+    fn validate_site_references(&self) -> Result<()> {
+        for site in &self.sites {
+            for dependency in &site.depends_on {
+                if self.site(dependency).is_none() {
+                    anyhow::bail!("Site '{}' depends on unknown site '{}'", site.name, dependency);
+                }
+            }
+            for member in &site.members {
+                if self.site(member).is_none() {
+                    anyhow::bail!("Composite site '{}' has unknown member '{}'", site.name, member);
+                }
+            }
+        }
+
+        self.check_acyclic("Site dependencies", |site| &site.depends_on)?;
+        self.check_acyclic("Composite members", |site| &site.members)
+    }
+
+    /// Fail if following `edges` from any site leads back to it
+    fn check_acyclic(&self, what: &str, edges: impl Fn(&SiteConfig) -> &[String]) -> Result<()> {
+        // Depth-first search; a site reached again while still on the path closes a cycle
+        fn visit<'a>(
+            config: &'a Config,
+            edges: &dyn Fn(&'a SiteConfig) -> &'a [String],
+            name: &'a str,
+            path: &mut Vec<&'a str>,
+            done: &mut HashSet<&'a str>,
+        ) -> Result<(), String> {
+            if let Some(start) = path.iter().position(|&n| n == name) {
+                let mut cycle = path[start..].to_vec();
+                cycle.push(name);
+                return Err(cycle.join(" -> "));
+            }
+            if !done.insert(name) {
+                return Ok(());
+            }
+            path.push(name);
+            for next in config.site(name).map(edges).unwrap_or_default() {
+                visit(config, edges, next, path, done)?;
+            }
+            path.pop();
+            Ok(())
+        }
+
+        let mut done = HashSet::new();
+        for site in &self.sites {
+            if let Err(cycle) = visit(self, &edges, &site.name, &mut Vec::new(), &mut done) {
+                anyhow::bail!("{} form a cycle: {}", what, cycle);
+            }
+        }
+        Ok(())
+    }
+
     /// Validate configuration
     fn validate(&self) -> Result<()> {
-        // Validate that we have at least one site
-        if self.sites.is_empty() {
+        // Validate that we have at least one site, unless they come from elsewhere
+        if self.sites.is_empty() && !self.discovers_sites() {
             anyhow::bail!("Configuration must define at least one site");
         }
 
+        if self.settings.refresh_interval == 0 {
+            anyhow::bail!("refresh_interval must be at least 1 second");
+        }
+        if self.settings.request_timeout == 0 {
+            anyhow::bail!("request_timeout must be at least 1 second");
+        }
+        if self.settings.alerts.notification_timeout_seconds == 0 {
+            anyhow::bail!("[settings.alerts] notification_timeout_seconds must be at least 1");
+        }
+        let alerts = &self.settings.alerts;
+        for webhook in alerts.webhooks.iter().chain(&alerts.slack_webhooks).chain(&alerts.discord_webhooks) {
+            let url = Url::parse(webhook).with_context(|| format!("[settings.alerts] invalid webhook URL '{}'", webhook))?;
+            if !matches!(url.scheme(), "http" | "https") {
+                anyhow::bail!("[settings.alerts] webhook '{}' must be an http:// or https:// URL", webhook);
+            }
+        }
+        if self.settings.alerts.consecutive_failures == 0 {
+            anyhow::bail!("[settings.alerts] consecutive_failures must be at least 1");
+        }
+
+        if self.settings.interval_jitter_percent > 50 {
+            anyhow::bail!(
+                "interval_jitter_percent {} is too large - must be 0-50",
+                self.settings.interval_jitter_percent
+            );
+        }
+
+        if let (Some(min), Some(max)) = (self.settings.adaptive_min_interval, self.settings.adaptive_max_interval)
+            && min > max
+        {
+            anyhow::bail!("adaptive_min_interval ({}) is larger than adaptive_max_interval ({})", min, max);
+        }
+        if self.settings.baseline_factor.is_some_and(|f| f <= 1.0) {
+            anyhow::bail!("baseline_factor must be greater than 1");
+        }
+        if self.settings.chart.sparkline_length == 0 {
+            anyhow::bail!("chart.sparkline_length must be at least 1");
+        }
+        if self.settings.baseline_window == 0 {
+            anyhow::bail!("baseline_window must be at least 1 second");
+        }
+        if let Some(agent) = &self.settings.user_agent
+            && reqwest::header::HeaderValue::from_str(agent).is_err()
+        {
+            anyhow::bail!("user_agent '{}' is not a valid header value", agent);
+        }
+        if self.settings.carousel_interval == 0 {
+            anyhow::bail!("carousel_interval must be at least 1 second");
+        }
+        if self.settings.result_channel_capacity == Some(0) {
+            anyhow::bail!("result_channel_capacity must be at least 1");
+        }
+
+        if self.settings.adaptive_min_interval == Some(0) {
+            anyhow::bail!("adaptive_min_interval must be at least 1 second");
+        }
+
+        if let Some(smtp) = &self.smtp {
+            if smtp.to.is_empty() {
+                anyhow::bail!("[smtp] to needs at least one address");
+            }
+            for address in std::iter::once(&smtp.from).chain(&smtp.to) {
+                address
+                    .parse::<lettre::message::Mailbox>()
+                    .with_context(|| format!("[smtp] invalid address '{}'", address))?;
+            }
+            if smtp.password.is_some() && smtp.username.is_none() {
+                anyhow::bail!("[smtp] password needs a username");
+            }
+        }
+        if let Some(report) = &self.report {
+            if self.smtp.is_none() {
+                anyhow::bail!("[report] needs an [smtp] section to send through");
+            }
+            chrono::NaiveTime::parse_from_str(&report.send_at, "%H:%M")
+                .with_context(|| format!("[report] send_at '{}' must be HH:MM", report.send_at))?;
+        }
+
+        if let Some(dns) = &self.dns {
+            if dns.resolver == DnsResolverKind::Custom && dns.servers.is_empty() {
+                anyhow::bail!("[dns] resolver = \"custom\" needs at least one entry in servers");
+            }
+            if dns.resolver == DnsResolverKind::Doh && !dns.servers.is_empty() && dns.tls_name.is_none() {
+                anyhow::bail!("[dns] resolver = \"doh\" with custom servers needs tls_name");
+            }
+            for server in &dns.servers {
+                crate::checker::dns::parse_server(server, 53)?;
+            }
+        }
+
+        for (index, silence) in self.silences.iter().enumerate() {
+            silence.validate().with_context(|| format!("[[silences]] entry {} is invalid", index + 1))?;
+        }
+
+        for (name, profile) in &self.egress {
+            if name == DIRECT_EGRESS {
+                anyhow::bail!("[egress.{}] is built in and can't be redefined", DIRECT_EGRESS);
+            }
+            let proxy = Url::parse(&profile.proxy)
+                .with_context(|| format!("[egress.{}] has invalid proxy URL '{}'", name, profile.proxy))?;
+            if proxy.scheme() != "http" && proxy.scheme() != "https" {
+                anyhow::bail!("[egress.{}] proxy '{}' must start with http:// or https://", name, profile.proxy);
+            }
+        }
+
+        if let Some(docker) = &self.discovery.docker
+            && docker.label_prefix.is_empty()
+        {
+            anyhow::bail!("[discovery.docker] label_prefix must not be empty");
+        }
+        if cfg!(not(unix)) && self.discovery.docker.is_some() {
+            anyhow::bail!("[discovery.docker] needs the Docker unix socket, which this platform doesn't have");
+        }
+        if self.discovery.consul.as_ref().is_some_and(|c| c.poll_interval == 0) {
+            anyhow::bail!("[discovery.consul] poll_interval must be at least 1 second");
+        }
+        if self.discovery.etcd.as_ref().is_some_and(|e| e.poll_interval == 0) {
+            anyhow::bail!("[discovery.etcd] poll_interval must be at least 1 second");
+        }
+        if let Some(remote) = &self.sites_url {
+            let url = Url::parse(&remote.url)
+                .with_context(|| format!("sites_url '{}' is not a valid URL", remote.url))?;
+            if url.scheme() != "http" && url.scheme() != "https" {
+                anyhow::bail!("sites_url '{}' must start with http:// or https://", remote.url);
+            }
+            if remote.refresh_interval == 0 {
+                anyhow::bail!("sites_url refresh_interval must be at least 1 second");
+            }
+            if remote.password.is_some() && remote.username.is_none() {
+                anyhow::bail!("sites_url password needs a username");
+            }
+        }
+
+        self.validate_sites()
+    }
+
+    /// Validate the sites themselves, as loaded or with one added at runtime
+    pub fn validate_sites(&self) -> Result<()> {
+        self.validate_site_references()?;
+
         // Validate each site
+        let mut names = HashSet::new();
+        let mut slugs = HashMap::new();
+        let mut heartbeat_tokens = HashSet::new();
         for site in &self.sites {
-            // Check URL is valid
-            if site.url.is_empty() {
-                anyhow::bail!("Site '{}' has empty URL", site.name);
+            if site.name.trim().is_empty() {
+                anyhow::bail!("A site has an empty name (url: '{}')", site.url);
             }
-
-            if !site.url.starts_with("http://") && !site.url.starts_with("https://") {
+            // Sites are keyed by name, so a duplicate would silently replace the first
+            if !names.insert(site.name.as_str()) {
+                anyhow::bail!("Duplicate site name '{}' - every site needs a unique name", site.name);
+            }
+            // MQTT topics and entity ids are built from the slug, so two sites
+            // sharing one would overwrite each other's state
+            if self.mqtt.is_some()
+                && let Some(other) = slugs.insert(slugify(&site.name), site.name.as_str())
+            {
                 anyhow::bail!(
-                    "Site '{}' has invalid URL '{}' - must start with http:// or https://",
+                    "Sites '{}' and '{}' both publish to MQTT as '{}' - rename one",
+                    other,
                     site.name,
-                    site.url
+                    slugify(&site.name)
                 );
             }
 
+            if site.check_interval == Some(0) {
+                anyhow::bail!("Site '{}' has check_interval 0 - must be at least 1 second", site.name);
+            }
+            if let Some(schedule) = &site.schedule {
+                if site.check_interval.is_some() {
+                    anyhow::bail!("Site '{}' sets both schedule and check_interval - pick one", site.name);
+                }
+                if matches!(site.kind, SiteKind::Heartbeat | SiteKind::Composite) {
+                    anyhow::bail!("Site '{}' sets schedule but is not a checked site", site.name);
+                }
+                crate::checker::parse_cron(schedule).with_context(|| format!("Site '{}' has an invalid schedule", site.name))?;
+            }
+            if site.diagnostics == Some(true) && matches!(site.kind, SiteKind::Heartbeat | SiteKind::Composite) {
+                anyhow::bail!("Site '{}' sets diagnostics but is not a checked site", site.name);
+            }
+            for egress in &site.egress {
+                if egress != DIRECT_EGRESS && !self.egress.contains_key(egress) {
+                    anyhow::bail!("Site '{}' uses unknown egress profile '{}'", site.name, egress);
+                }
+            }
+            if !site.egress.is_empty() && !matches!(site.kind, SiteKind::Http | SiteKind::Graphql) {
+                anyhow::bail!("Site '{}' sets egress but is not an http or graphql site", site.name);
+            }
+            if let Some(via) = &site.via {
+                if via.trim().is_empty() || via.starts_with('-') || via.contains(char::is_whitespace) {
+                    anyhow::bail!("Site '{}' has via '{}' - must be an SSH host, like bastion.example.com", site.name, via);
+                }
+                if !matches!(site.kind, SiteKind::Http | SiteKind::Graphql) {
+                    anyhow::bail!("Site '{}' sets via but is not an http or graphql site", site.name);
+                }
+                if !site.egress.is_empty() {
+                    anyhow::bail!("Site '{}' sets both via and egress - pick one", site.name);
+                }
+            }
+            for blackout in &site.blackouts {
+                crate::checker::blackout::BlackoutWindow::parse(blackout)
+                    .with_context(|| format!("Site '{}' has an invalid blackout", site.name))?;
+            }
+            if site.baseline_factor.is_some_and(|f| f <= 1.0) {
+                anyhow::bail!("Site '{}' has baseline_factor {} - must be greater than 1", site.name, site.baseline_factor.unwrap_or_default());
+            }
+            if let Some(min) = &site.min_tls_version {
+                if crate::checker::tls::version_rank(min).is_none() {
+                    anyhow::bail!("Site '{}' has invalid min_tls_version '{}' - use 1.0, 1.1, 1.2 or 1.3", site.name, min);
+                }
+                if site.kind != SiteKind::Http {
+                    anyhow::bail!("Site '{}' sets min_tls_version but is not an http site", site.name);
+                }
+            }
+            if site.check_revocation == Some(true) && site.kind != SiteKind::Http {
+                anyhow::bail!("Site '{}' sets check_revocation but is not an http site", site.name);
+            }
+            if let Some(agent) = &site.user_agent {
+                if reqwest::header::HeaderValue::from_str(agent).is_err() {
+                    anyhow::bail!("Site '{}' has user_agent '{}' - not a valid header value", site.name, agent);
+                }
+                if !matches!(site.kind, SiteKind::Http | SiteKind::Graphql) {
+                    anyhow::bail!("Site '{}' sets user_agent but is not an http or graphql site", site.name);
+                }
+            }
+            if let Some(method) = &site.method {
+                if !HTTP_METHODS.contains(&method.to_uppercase().as_str()) {
+                    anyhow::bail!("Site '{}' has method '{}' - use one of {}", site.name, method, HTTP_METHODS.join(", "));
+                }
+                if site.kind != SiteKind::Http {
+                    anyhow::bail!("Site '{}' sets method but is not an http site", site.name);
+                }
+            }
+            for (name, value) in &site.headers {
+                if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() {
+                    anyhow::bail!("Site '{}' has header '{}' - not a valid header name", site.name, name);
+                }
+                if reqwest::header::HeaderValue::from_str(value).is_err() {
+                    anyhow::bail!("Site '{}' has header '{}' with a value that isn't valid in a header", site.name, name);
+                }
+                if !matches!(site.kind, SiteKind::Http | SiteKind::Graphql) {
+                    anyhow::bail!("Site '{}' sets headers but is not an http or graphql site", site.name);
+                }
+            }
+            if site.body.is_some() && site.kind != SiteKind::Http {
+                anyhow::bail!("Site '{}' sets body but is not an http site", site.name);
+            }
+            if let Some(target) = site.slo_target
+                && !(target > 0.0 && target < 100.0)
+            {
+                anyhow::bail!("Site '{}' has slo_target {} - must be a percentage between 0 and 100", site.name, target);
+            }
+            if site.request_timeout == Some(0) {
+                anyhow::bail!("Site '{}' has request_timeout 0 - must be at least 1 second", site.name);
+            }
+            if let Some(alerts) = &site.alerts
+                && alerts.consecutive_failures == Some(0)
+            {
+                anyhow::bail!("Site '{}' has alerts.consecutive_failures 0 - must be at least 1", site.name);
+            }
+            if let Some(alerts) = &site.alerts {
+                let chat = alerts.slack_webhooks.iter().chain(&alerts.discord_webhooks).flatten();
+                for webhook in chat {
+                    if !Url::parse(webhook).is_ok_and(|url| matches!(url.scheme(), "http" | "https")) {
+                        anyhow::bail!("Site '{}' has webhook '{}' - must be an http:// or https:// URL", site.name, webhook);
+                    }
+                }
+            }
+
+            if site.kind == SiteKind::Composite {
+                if site.members.is_empty() {
+                    anyhow::bail!("Composite site '{}' must list its members", site.name);
+                }
+                if site.members.contains(&site.name) {
+                    anyhow::bail!("Composite site '{}' lists itself as a member", site.name);
+                }
+                if site.aggregate == Aggregate::Quorum
+                    && site.quorum.is_some_and(|q| q == 0 || q > site.members.len())
+                {
+                    anyhow::bail!(
+                        "Composite site '{}' has quorum {} - must be 1-{}",
+                        site.name,
+                        site.quorum.unwrap_or_default(),
+                        site.members.len()
+                    );
+                }
+                continue;
+            }
+            if !site.members.is_empty() {
+                anyhow::bail!("Site '{}' lists members but is not kind = \"composite\"", site.name);
+            }
+
+            if site.kind == SiteKind::Heartbeat {
+                if self.http_server.is_none() {
+                    anyhow::bail!(
+                        "Site '{}' is a heartbeat site but [http_server] is not configured",
+                        site.name
+                    );
+                }
+                if site.grace_period.is_none_or(|g| g == 0) {
+                    anyhow::bail!("Heartbeat site '{}' must set a grace_period in seconds", site.name);
+                }
+                if !heartbeat_tokens.insert(site.heartbeat_token()) {
+                    anyhow::bail!(
+                        "Heartbeat site '{}' reuses token '{}' - set a unique heartbeat_token",
+                        site.name,
+                        site.heartbeat_token()
+                    );
+                }
+                continue;
+            }
+
+            if site.kind == SiteKind::Graphql && site.graphql_query.as_deref().is_none_or(|q| q.trim().is_empty()) {
+                anyhow::bail!("GraphQL site '{}' must set graphql_query", site.name);
+            }
+            if site.kind != SiteKind::Graphql && (site.graphql_query.is_some() || !site.graphql_expect.is_empty()) {
+                anyhow::bail!("Site '{}' sets graphql options but is not kind = \"graphql\"", site.name);
+            }
+
+            if !site.xml_assertions.is_empty() && site.kind != SiteKind::Http {
+                anyhow::bail!("Site '{}' sets xml_assertions but is not an http site", site.name);
+            }
+            for assertion in &site.xml_assertions {
+                crate::checker::xml::compile(&assertion.xpath)
+                    .with_context(|| format!("Site '{}' has an invalid xml_assertions entry", site.name))?;
+            }
+
+            for (code, rule) in &site.status_codes {
+                if !code.parse::<u16>().is_ok_and(|code| (100..=599).contains(&code)) {
+                    anyhow::bail!("Site '{}' maps status code {} - must be 100-599", site.name, code);
+                }
+                if let Some(color) = &rule.color
+                    && color.parse::<Color>().is_err()
+                {
+                    anyhow::bail!("Site '{}' has invalid color '{}' for status code {}", site.name, color, code);
+                }
+            }
+
+            if let Some(drift) = site.max_clock_drift_ms {
+                if drift == 0 {
+                    anyhow::bail!("Site '{}' has max_clock_drift_ms = 0 - must be at least 1", site.name);
+                }
+                if site.kind != SiteKind::Ntp {
+                    anyhow::bail!("Site '{}' sets max_clock_drift_ms but is not kind = \"ntp\"", site.name);
+                }
+            }
+
+            if site.kind == SiteKind::Ports {
+                let expand = |specs| {
+                    crate::checker::ports::expand(specs)
+                        .with_context(|| format!("Port sweep site '{}' has invalid ports", site.name))
+                };
+                let (open, closed) = (expand(&site.open_ports)?, expand(&site.closed_ports)?);
+                if open.is_empty() && closed.is_empty() {
+                    anyhow::bail!("Port sweep site '{}' must list open_ports, closed_ports or both", site.name);
+                }
+                if open.len() + closed.len() > crate::checker::ports::MAX_PORTS {
+                    anyhow::bail!(
+                        "Port sweep site '{}' lists {} ports - at most {}",
+                        site.name,
+                        open.len() + closed.len(),
+                        crate::checker::ports::MAX_PORTS
+                    );
+                }
+                if let Some(port) = open.iter().find(|port| closed.contains(port)) {
+                    anyhow::bail!("Port sweep site '{}' lists port {} as both open and closed", site.name, port);
+                }
+            } else if !site.open_ports.is_empty() || !site.closed_ports.is_empty() {
+                anyhow::bail!("Site '{}' sets open_ports or closed_ports but is not kind = \"ports\"", site.name);
+            }
+
+            if let Some(hours) = site.max_content_age {
+                if hours == 0 {
+                    anyhow::bail!("Site '{}' has max_content_age = 0 - must be at least 1 hour", site.name);
+                }
+                if site.kind != SiteKind::Http {
+                    anyhow::bail!("Site '{}' sets max_content_age but is not an http site", site.name);
+                }
+            }
+
+            if let Some(expected) = &site.expected_content_type {
+                if expected.split_once('/').is_none_or(|(kind, subtype)| kind.is_empty() || subtype.is_empty()) {
+                    anyhow::bail!("Site '{}' has expected_content_type '{}' - use type/subtype, like application/json", site.name, expected);
+                }
+                if site.kind != SiteKind::Http {
+                    anyhow::bail!("Site '{}' sets expected_content_type but is not an http site", site.name);
+                }
+            }
+
+            if let Some(text) = &site.expected_body_contains {
+                if text.is_empty() {
+                    anyhow::bail!("Site '{}' has an empty expected_body_contains", site.name);
+                }
+                if site.kind != SiteKind::Http {
+                    anyhow::bail!("Site '{}' sets expected_body_contains but is not an http site", site.name);
+                }
+            }
+
+            if let Some(pattern) = &site.expected_body_regex {
+                regex::Regex::new(pattern)
+                    .with_context(|| format!("Site '{}' has invalid expected_body_regex '{}'", site.name, pattern))?;
+                if site.kind != SiteKind::Http {
+                    anyhow::bail!("Site '{}' sets expected_body_regex but is not an http site", site.name);
+                }
+            }
+
+            if !site.allowed_redirect_domains.is_empty() {
+                if site.kind != SiteKind::Http {
+                    anyhow::bail!("Site '{}' sets allowed_redirect_domains but is not an http site", site.name);
+                }
+                for domain in &site.allowed_redirect_domains {
+                    let host = domain.strip_prefix("*.").unwrap_or(domain);
+                    if host.is_empty() || host.contains(['/', ':', '*']) || host.contains(char::is_whitespace) {
+                        anyhow::bail!(
+                            "Site '{}' has allowed_redirect_domains entry '{}' - use a bare domain like example.com or *.example.com",
+                            site.name,
+                            domain
+                        );
+                    }
+                }
+            }
+
+            if site.dns_bypass_cache && self.dns.is_none() {
+                anyhow::bail!("Site '{}' sets dns_bypass_cache but [dns] is not configured", site.name);
+            }
+
             // Validate status code is in valid range
             if site.expected_status < 100 || site.expected_status >= 600 {
                 anyhow::bail!(
@@ -234,6 +1932,69 @@ impl Config {
                     site.expected_status
                 );
             }
+
+            // Check URL is valid
+            if site.url.is_empty() {
+                anyhow::bail!("Site '{}' has empty URL", site.name);
+            }
+
+            if crate::checker::unix::UnixTarget::parse(&site.url)
+                .with_context(|| format!("Site '{}' has invalid URL '{}'", site.name, site.url))?
+                .is_some()
+            {
+                if cfg!(not(unix)) {
+                    anyhow::bail!("Site '{}' checks a unix socket, which this platform doesn't have", site.name);
+                }
+                if site.kind != SiteKind::Http {
+                    anyhow::bail!("Site '{}' checks a unix socket but is not an http site", site.name);
+                }
+                let remote_only = [
+                    ("via", site.via.is_some()),
+                    ("egress", !site.egress.is_empty()),
+                    ("assets", !site.assets.is_empty()),
+                    ("allowed_redirect_domains", !site.allowed_redirect_domains.is_empty()),
+                ];
+                if let Some((option, _)) = remote_only.iter().find(|(_, set)| *set) {
+                    anyhow::bail!("Site '{}' checks a unix socket, which {} doesn't work with", site.name, option);
+                }
+                // Nothing below applies to a socket
+                continue;
+            }
+
+            let url = Url::parse(&site.url)
+                .with_context(|| format!("Site '{}' has invalid URL '{}'", site.name, site.url))?;
+
+            if site.kind == SiteKind::Ntp {
+                if url.scheme() != "ntp" {
+                    anyhow::bail!("NTP site '{}' has URL '{}' - must look like ntp://pool.ntp.org", site.name, site.url);
+                }
+            } else if site.kind == SiteKind::Ports {
+                if url.scheme() != "tcp" || url.port().is_some() {
+                    anyhow::bail!(
+                        "Port sweep site '{}' has URL '{}' - must look like tcp://host.example.com, with the ports in open_ports and closed_ports",
+                        site.name,
+                        site.url
+                    );
+                }
+            } else if url.scheme() != "http" && url.scheme() != "https" {
+                anyhow::bail!(
+                    "Site '{}' has invalid URL '{}' - must start with http://, https:// or http+unix://",
+                    site.name,
+                    site.url
+                );
+            }
+
+            if url.host_str().is_none_or(str::is_empty) {
+                anyhow::bail!("Site '{}' has URL '{}' with no host", site.name, site.url);
+            }
+
+            if !site.assets.is_empty() && site.kind != SiteKind::Http {
+                anyhow::bail!("Site '{}' sets assets but is not an http site", site.name);
+            }
+            for asset in &site.assets {
+                url.join(asset)
+                    .with_context(|| format!("Site '{}' has invalid asset URL '{}'", site.name, asset))?;
+            }
         }
 
         Ok(())
@@ -0,0 +1,57 @@
+use anyhow::{Context, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Quiet time after the last change before it's reported, so an editor's
+/// write, rename and chmod count as one edit
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watches the config file for edits; watching stops when it's dropped
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+    task: JoinHandle<()>,
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Start watching `path`, with a message on the returned channel after each edit.
+/// The directory is watched rather than the file, since editors that save by
+/// replacing the file would otherwise leave the watch on the old one.
+pub fn watch(path: &Path) -> Result<(ConfigWatcher, mpsc::Receiver<()>)> {
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve config path: {}", path.display()))?;
+    let dir = path.parent().context("Config file has no directory")?.to_path_buf();
+
+    let (raw_tx, mut raw_rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event
+            && !matches!(event.kind, EventKind::Access(_))
+            && event.paths.contains(&path)
+        {
+            let _ = raw_tx.send(());
+        }
+    })
+    .context("Failed to start watching the config file")?;
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {}", dir.display()))?;
+
+    let (tx, rx) = mpsc::channel(1);
+    let task = tokio::spawn(async move {
+        while raw_rx.recv().await.is_some() {
+            while let Ok(Some(())) = tokio::time::timeout(DEBOUNCE, raw_rx.recv()).await {}
+            // A reload already pending covers this edit too
+            let _ = tx.try_send(());
+        }
+    });
+
+    Ok((ConfigWatcher { _watcher: watcher, task }, rx))
+}
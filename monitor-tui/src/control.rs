@@ -0,0 +1,306 @@
+use crate::config::Config;
+#[cfg(unix)]
+use crate::instance_lock::runtime_path;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read, Write};
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+#[cfg(unix)]
+use std::path::PathBuf;
+#[cfg(unix)]
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{mpsc, oneshot, watch};
+
+/// A command for a running instance, sent with `monitor-tui ctl <command>`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ControlCommand {
+    /// Stop checking every site until resumed
+    Pause,
+    /// Resume checking and check every site right away
+    Resume,
+    /// Email the availability report for the period so far
+    Report,
+    /// Empty the alert history
+    ClearAlerts,
+    /// Drop recovery alerts from the alert history
+    ClearRecoveries,
+    /// Reply with a `StatusSummary` as JSON
+    Status,
+}
+
+impl ControlCommand {
+    pub const NAMES: &'static str = "pause, resume, report, clear-alerts, clear-recoveries, status";
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "pause" => Some(Self::Pause),
+            "resume" => Some(Self::Resume),
+            "report" => Some(Self::Report),
+            "clear-alerts" => Some(Self::ClearAlerts),
+            "clear-recoveries" => Some(Self::ClearRecoveries),
+            "status" => Some(Self::Status),
+            _ => None,
+        }
+    }
+}
+
+/// How sites are doing, the reply to the `status` command
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct StatusSummary {
+    pub up: usize,
+    pub down: usize,
+    pub warning: usize,
+    /// Not checked yet, or only skipped
+    pub unknown: usize,
+    pub paused: bool,
+    pub down_sites: Vec<String>,
+    pub warning_sites: Vec<String>,
+}
+
+impl StatusSummary {
+    fn total(&self) -> usize {
+        self.up + self.down + self.warning + self.unknown
+    }
+
+    /// e.g. "12/14 UP, 2 DOWN"
+    fn counts(&self) -> String {
+        let mut text = format!("{}/{} UP", self.up, self.total());
+        if self.down > 0 {
+            text.push_str(&format!(", {} DOWN", self.down));
+        }
+        if self.warning > 0 {
+            text.push_str(&format!(", {} WARN", self.warning));
+        }
+        if self.paused {
+            text.push_str(" (paused)");
+        }
+        text
+    }
+}
+
+/// How `monitor-tui status` prints the summary
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusFormat {
+    /// One line, e.g. "12/14 UP, 2 DOWN: api, web"
+    Text,
+    /// The summary as JSON
+    Json,
+    /// A waybar custom module's JSON: text, tooltip, class and percentage
+    Waybar,
+    /// One line with tmux color codes, for status-right
+    Tmux,
+}
+
+impl StatusFormat {
+    pub const NAMES: &'static str = "text, json, waybar, tmux";
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            "waybar" => Some(Self::Waybar),
+            "tmux" => Some(Self::Tmux),
+            _ => None,
+        }
+    }
+}
+
+/// A command from the socket and where to send the reply line
+pub struct ControlRequest {
+    pub command: ControlCommand,
+    pub reply: oneshot::Sender<String>,
+}
+
+/// Control socket of the instance monitoring `config_path`
+#[cfg(unix)]
+pub fn socket_path(config_path: &Path) -> PathBuf {
+    runtime_path(config_path, "sock")
+}
+
+/// Listen on the control socket, passing each command to the main loop and
+/// writing back its reply. Each connection sends one command line.
+#[cfg(unix)]
+pub fn spawn_control_socket(
+    config_path: &Path,
+    requests: mpsc::Sender<ControlRequest>,
+    mut shutdown: watch::Receiver<bool>,
+) -> Result<tokio::task::JoinHandle<()>> {
+    let path = socket_path(config_path);
+    if path.exists() {
+        // A socket nobody answers on was left by an instance that crashed
+        if std::os::unix::net::UnixStream::connect(&path).is_ok() {
+            anyhow::bail!("another instance is listening on {}", path.display());
+        }
+        std::fs::remove_file(&path).with_context(|| format!("Failed to remove stale {}", path.display()))?;
+    }
+    let listener = UnixListener::bind(&path).with_context(|| format!("Failed to bind {}", path.display()))?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+
+    Ok(tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => {
+                    if let Ok((stream, _)) = accepted {
+                        tokio::spawn(serve(stream, requests.clone()));
+                    }
+                }
+                _ = shutdown.changed() => break,
+            }
+        }
+        let _ = std::fs::remove_file(&path);
+    }))
+}
+
+/// Without unix domain sockets there is nothing to listen on
+#[cfg(not(unix))]
+pub fn spawn_control_socket(
+    _config_path: &Path,
+    _requests: mpsc::Sender<ControlRequest>,
+    _shutdown: watch::Receiver<bool>,
+) -> Result<tokio::task::JoinHandle<()>> {
+    anyhow::bail!("the control socket needs unix domain sockets, which this platform lacks")
+}
+
+#[cfg(unix)]
+async fn serve(stream: UnixStream, requests: mpsc::Sender<ControlRequest>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut line = String::new();
+    if tokio::io::BufReader::new(reader).read_line(&mut line).await.is_err() {
+        return;
+    }
+    let name = line.trim();
+
+    let reply = match ControlCommand::parse(name) {
+        Some(command) => {
+            let (reply, response) = oneshot::channel();
+            match requests.send(ControlRequest { command, reply }).await {
+                Ok(()) => response.await.unwrap_or_else(|_| "error: shutting down".to_string()),
+                Err(_) => "error: shutting down".to_string(),
+            }
+        }
+        None => format!("error: unknown command '{}' - expected one of: {}", name, ControlCommand::NAMES),
+    };
+    let _ = writer.write_all(format!("{}\n", reply).as_bytes()).await;
+}
+
+/// `monitor-tui ctl <command>`: send a command to the instance monitoring this
+/// config and print its reply
+pub fn run(command: &str) -> Result<()> {
+    if ControlCommand::parse(command).is_none() {
+        anyhow::bail!("Unknown command '{}' - expected one of: {}", command, ControlCommand::NAMES);
+    }
+    let stream = connect()?;
+    println!("{}", request(stream, command)?);
+    Ok(())
+}
+
+/// `monitor-tui status`: print how the running instance's sites are doing, for
+/// status bars. Bars get an "offline" line rather than an error when nothing
+/// is running, so they don't keep showing the last state.
+pub fn status(format: StatusFormat) -> Result<()> {
+    let stream = match connect() {
+        Ok(stream) => stream,
+        Err(e) => {
+            match format {
+                StatusFormat::Text | StatusFormat::Json => return Err(e),
+                StatusFormat::Waybar => {
+                    let output = serde_json::json!({ "text": "offline", "tooltip": format!("{:#}", e), "class": "offline" });
+                    println!("{}", output);
+                }
+                StatusFormat::Tmux => println!("#[fg=colour244]monitor offline#[default]"),
+            }
+            return Ok(());
+        }
+    };
+    let reply = request(stream, "status")?;
+    let summary: StatusSummary = serde_json::from_str(&reply).context("Unexpected reply to status")?;
+
+    match format {
+        StatusFormat::Json => println!("{}", reply),
+        StatusFormat::Text => {
+            let failing: Vec<&str> = summary.down_sites.iter().chain(&summary.warning_sites).map(String::as_str).collect();
+            if failing.is_empty() {
+                println!("{}", summary.counts());
+            } else {
+                println!("{}: {}", summary.counts(), failing.join(", "));
+            }
+        }
+        StatusFormat::Waybar => {
+            let mut tooltip = Vec::new();
+            if !summary.down_sites.is_empty() {
+                tooltip.push(format!("DOWN: {}", summary.down_sites.join(", ")));
+            }
+            if !summary.warning_sites.is_empty() {
+                tooltip.push(format!("WARN: {}", summary.warning_sites.join(", ")));
+            }
+            if tooltip.is_empty() {
+                tooltip.push("All sites up".to_string());
+            }
+            let class = if summary.paused {
+                "paused"
+            } else if summary.down > 0 {
+                "down"
+            } else if summary.warning > 0 {
+                "warning"
+            } else {
+                "up"
+            };
+            let percentage = (summary.up * 100).checked_div(summary.total()).unwrap_or(100);
+            let output = serde_json::json!({
+                "text": summary.counts(),
+                "tooltip": tooltip.join("\n"),
+                "class": class,
+                "percentage": percentage,
+            });
+            println!("{}", output);
+        }
+        StatusFormat::Tmux => {
+            let color = if summary.down > 0 {
+                "red"
+            } else if summary.warning > 0 {
+                "yellow"
+            } else {
+                "green"
+            };
+            println!("#[fg={}]● {}#[default]", color, summary.counts());
+        }
+    }
+    Ok(())
+}
+
+/// Connect to the control socket of the instance monitoring this config
+#[cfg(unix)]
+fn connect() -> Result<std::os::unix::net::UnixStream> {
+    let config_path = Config::find_path()?;
+    let path = socket_path(&config_path);
+    std::os::unix::net::UnixStream::connect(&path).with_context(|| {
+        format!("No running instance is monitoring {} (no control socket at {})", config_path.display(), path.display())
+    })
+}
+
+/// Without unix domain sockets no instance can be reached
+#[cfg(not(unix))]
+fn connect() -> Result<std::io::Empty> {
+    let config_path = Config::find_path()?;
+    anyhow::bail!(
+        "Can't reach the instance monitoring {} - the control socket needs unix domain sockets, which this platform lacks",
+        config_path.display()
+    )
+}
+
+/// Send one command line and return the reply line, or its error
+fn request(mut stream: impl Read + Write, command: &str) -> Result<String> {
+    writeln!(stream, "{}", command)?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply).context("Failed to read the reply")?;
+    let reply = reply.trim();
+    match reply.strip_prefix("error: ") {
+        Some(error) => anyhow::bail!("{}", error),
+        None => Ok(reply.to_string()),
+    }
+}
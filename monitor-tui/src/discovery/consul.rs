@@ -0,0 +1,110 @@
+use super::Found;
+use crate::config::ConsulDiscoveryConfig;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// One instance of a service in the Consul catalog
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CatalogService {
+    #[serde(default)]
+    node: String,
+    #[serde(default)]
+    address: String,
+    #[serde(default)]
+    service_address: String,
+    #[serde(default)]
+    service_port: u16,
+    #[serde(default)]
+    service_tags: Vec<String>,
+    #[serde(default)]
+    service_meta: HashMap<String, String>,
+}
+
+/// Read the catalog: a site per instance of each service (with the configured tag)
+pub async fn fetch(client: &reqwest::Client, config: &ConsulDiscoveryConfig) -> Result<Found> {
+    let services: HashMap<String, Vec<String>> = get(client, config, "/v1/catalog/services").await?;
+
+    let mut found = Found::default();
+    let mut names: Vec<&String> = services
+        .iter()
+        .filter(|(name, tags)| *name != "consul" && config.tag.as_ref().is_none_or(|tag| tags.contains(tag)))
+        .map(|(name, _)| name)
+        .collect();
+    names.sort();
+
+    for service in names {
+        let path = format!("/v1/catalog/service/{}", url::form_urlencoded::byte_serialize(service.as_bytes()).collect::<String>());
+        let instances: Vec<CatalogService> = get(client, config, &path).await?;
+        let several = instances.len() > 1;
+        for instance in &instances {
+            // Instances of one service are told apart by node
+            let name = if several { format!("{} ({})", service, instance.node) } else { service.clone() };
+            match site_table(name.clone(), instance) {
+                Ok(table) => found.sites.push(table),
+                Err(e) => found.problems.push(format!("service '{}': {:#}", name, e)),
+            }
+        }
+    }
+    Ok(found)
+}
+
+async fn get<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    config: &ConsulDiscoveryConfig,
+    path: &str,
+) -> Result<T> {
+    let mut request = client.get(format!("{}{}", config.address.trim_end_matches('/'), path));
+    if let Some(datacenter) = &config.datacenter {
+        request = request.query(&[("dc", datacenter)]);
+    }
+    if let Some(token) = &config.token {
+        request = request.header("X-Consul-Token", token);
+    }
+    let response = request
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach Consul at {}", config.address))?
+        .error_for_status()?;
+    response.json().await.with_context(|| format!("Unexpected response from Consul {}", path))
+}
+
+/// Site table for a service instance. Service meta can set `monitor_url`
+/// outright, or `monitor_scheme` and `monitor_path`, plus
+/// `monitor_expected_status`; otherwise the instance is checked over HTTP at /.
+fn site_table(name: String, instance: &CatalogService) -> Result<toml::Table> {
+    let meta = |key: &str| instance.service_meta.get(key).map(String::as_str).filter(|v| !v.is_empty());
+
+    let url = match meta("monitor_url") {
+        Some(url) => url.to_string(),
+        None => {
+            let host = if instance.service_address.is_empty() { &instance.address } else { &instance.service_address };
+            if host.is_empty() || instance.service_port == 0 {
+                anyhow::bail!("no address and port to check");
+            }
+            let host = if host.contains(':') { format!("[{}]", host) } else { host.clone() };
+            format!(
+                "{}://{}:{}{}",
+                meta("monitor_scheme").unwrap_or("http"),
+                host,
+                instance.service_port,
+                meta("monitor_path").unwrap_or("/")
+            )
+        }
+    };
+
+    let mut table = toml::Table::new();
+    table.insert("name".into(), name.into());
+    table.insert("url".into(), url.into());
+    if let Some(status) = meta("monitor_expected_status") {
+        let status: i64 = status
+            .parse()
+            .with_context(|| format!("invalid monitor_expected_status '{}'", status))?;
+        table.insert("expected_status".into(), status.into());
+    }
+    let mut tags: Vec<toml::Value> = instance.service_tags.iter().map(|t| t.as_str().into()).collect();
+    tags.push("consul".into());
+    table.insert("tags".into(), toml::Value::Array(tags));
+    Ok(table)
+}
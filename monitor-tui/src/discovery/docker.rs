@@ -0,0 +1,160 @@
+use super::{DiscoveredSites, Found, SiteChange};
+use crate::config::DockerDiscoveryConfig;
+use anyhow::{Context, Result};
+use hyper::body::HttpBody;
+use hyper::client::conn;
+use hyper::{Body, Request};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use tokio::net::UnixStream;
+use tokio::sync::{mpsc, watch};
+
+/// Wait before reconnecting after the Docker socket fails or closes
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A container as listed by the Docker API
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Container {
+    #[serde(default)]
+    names: Vec<String>,
+    #[serde(default)]
+    labels: HashMap<String, String>,
+}
+
+/// Watch the Docker socket and add a site for each running container with a
+/// `<label_prefix>.url` label, removing it when the container stops
+pub fn spawn_docker_discovery(
+    config: DockerDiscoveryConfig,
+    changes: mpsc::Sender<SiteChange>,
+    mut shutdown: watch::Receiver<bool>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut discovery = DockerDiscovery {
+            config,
+            sites: DiscoveredSites::new("Docker discovery", changes),
+        };
+        loop {
+            tokio::select! {
+                result = discovery.watch() => {
+                    if let Err(e) = result {
+                        discovery.sites.report(format!("{:#}", e)).await;
+                    }
+                }
+                _ = shutdown.changed() => return,
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(RETRY_INTERVAL) => {}
+                _ = shutdown.changed() => return,
+            }
+        }
+    })
+}
+
+struct DockerDiscovery {
+    config: DockerDiscoveryConfig,
+    sites: DiscoveredSites,
+}
+
+impl DockerDiscovery {
+    /// Follow container events, re-reading the container list after each one.
+    /// Returns when Docker closes the stream.
+    async fn watch(&mut self) -> Result<()> {
+        // Subscribe before listing so nothing that changes in between is missed
+        let filters = format!(
+            r#"{{"type":["container"],"label":["{}.url"]}}"#,
+            self.config.label_prefix
+        );
+        let mut events = self.get(&format!("/events?filters={}", encode(&filters))).await?;
+        self.sync().await?;
+
+        while let Some(chunk) = events.data().await {
+            chunk.context("Docker event stream failed")?;
+            self.sync().await?;
+        }
+        Ok(())
+    }
+
+    /// Bring the sites in line with the containers running now
+    async fn sync(&mut self) -> Result<()> {
+        let filters = format!(r#"{{"label":["{}.url"]}}"#, self.config.label_prefix);
+        let body = self.get(&format!("/containers/json?filters={}", encode(&filters))).await?;
+        let bytes = hyper::body::to_bytes(body).await.context("Failed to read container list")?;
+        let containers: Vec<Container> =
+            serde_json::from_slice(&bytes).context("Unexpected container list from Docker")?;
+
+        let mut found = Found::default();
+        let prefix = format!("{}.", self.config.label_prefix);
+        for container in containers {
+            let container_name = container.names.first().map_or("", |n| n.trim_start_matches('/'));
+            let labels: BTreeMap<&str, &str> = container
+                .labels
+                .iter()
+                .filter_map(|(key, value)| key.strip_prefix(&prefix).map(|key| (key, value.as_str())))
+                .collect();
+            match site_from_labels(container_name, &labels) {
+                Ok(table) => found.sites.push(table),
+                Err(e) => found.problems.push(format!("container '{}': {:#}", container_name, e)),
+            }
+        }
+
+        self.sites.update(found).await;
+        Ok(())
+    }
+
+    /// Start a GET request to the Docker API and return its body
+    async fn get(&self, path: &str) -> Result<Body> {
+        let socket = &self.config.socket;
+        let stream = UnixStream::connect(socket)
+            .await
+            .with_context(|| format!("Failed to connect to {}", socket.display()))?;
+        let (mut sender, connection) = conn::handshake(stream).await?;
+        tokio::spawn(async move {
+            let _ = connection.await;
+        });
+
+        let request = Request::get(path).header("Host", "docker").body(Body::empty())?;
+        let response = sender.send_request(request).await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Docker API {} returned {}", path, response.status());
+        }
+        Ok(response.into_body())
+    }
+}
+
+/// Site table from a container's labels (prefix removed): `url` plus optional
+/// `name`, `expected_status`, `interval`, `timeout` and comma-separated `tags`
+fn site_from_labels(container_name: &str, labels: &BTreeMap<&str, &str>) -> Result<toml::Table> {
+    let number = |key: &str| -> Result<Option<i64>> {
+        labels
+            .get(key)
+            .map(|value| value.trim().parse::<i64>().with_context(|| format!("invalid {} '{}'", key, value)))
+            .transpose()
+    };
+
+    let mut table = toml::Table::new();
+    table.insert("name".into(), labels.get("name").copied().unwrap_or(container_name).into());
+    table.insert("url".into(), labels.get("url").copied().unwrap_or_default().into());
+    if let Some(status) = number("expected_status")? {
+        table.insert("expected_status".into(), status.into());
+    }
+    if let Some(interval) = number("interval")? {
+        table.insert("check_interval".into(), interval.into());
+    }
+    if let Some(timeout) = number("timeout")? {
+        table.insert("request_timeout".into(), timeout.into());
+    }
+    let mut tags: Vec<toml::Value> = labels
+        .get("tags")
+        .map(|tags| tags.split(',').map(str::trim).filter(|t| !t.is_empty()).map(Into::into).collect())
+        .unwrap_or_default();
+    tags.push("docker".into());
+    table.insert("tags".into(), toml::Value::Array(tags));
+    Ok(table)
+}
+
+/// Percent-encode a query parameter value
+fn encode(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
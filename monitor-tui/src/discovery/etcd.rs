@@ -0,0 +1,84 @@
+use super::Found;
+use crate::config::EtcdDiscoveryConfig;
+use anyhow::{Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::Deserialize;
+
+/// Response of the v3 JSON gateway's range request
+#[derive(Debug, Deserialize)]
+struct RangeResponse {
+    #[serde(default)]
+    kvs: Vec<KeyValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KeyValue {
+    key: String,
+    #[serde(default)]
+    value: String,
+}
+
+/// Read every key under the prefix. Each value is a JSON object with the keys
+/// of a `[[sites]]` entry; `name` defaults to the key with the prefix removed.
+pub async fn fetch(client: &reqwest::Client, config: &EtcdDiscoveryConfig) -> Result<Found> {
+    let request = serde_json::json!({
+        "key": BASE64.encode(&config.prefix),
+        "range_end": BASE64.encode(range_end(config.prefix.as_bytes())),
+    });
+    let response: RangeResponse = client
+        .post(format!("{}/v3/kv/range", config.endpoint.trim_end_matches('/')))
+        .json(&request)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach etcd at {}", config.endpoint))?
+        .error_for_status()?
+        .json()
+        .await
+        .context("Unexpected response from etcd")?;
+
+    let mut found = Found::default();
+    for kv in response.kvs {
+        let key = decode(&kv.key);
+        match site_table(&key, &config.prefix, &decode(&kv.value)) {
+            Ok(table) => found.sites.push(table),
+            Err(e) => found.problems.push(format!("key '{}': {:#}", key, e)),
+        }
+    }
+    Ok(found)
+}
+
+fn site_table(key: &str, prefix: &str, value: &str) -> Result<toml::Table> {
+    let json: serde_json::Value = serde_json::from_str(value).context("value is not JSON")?;
+    let Ok(toml::Value::Table(mut table)) = toml::Value::try_from(json) else {
+        anyhow::bail!("value must be a JSON object of site settings");
+    };
+    table
+        .entry("name")
+        .or_insert_with(|| key.strip_prefix(prefix).unwrap_or(key).trim_matches('/').into());
+    let tags = table.entry("tags").or_insert_with(|| toml::Value::Array(Vec::new()));
+    if let toml::Value::Array(tags) = tags {
+        tags.push("etcd".into());
+    }
+    Ok(table)
+}
+
+/// End of the key range holding every key that starts with `prefix`
+fn range_end(prefix: &[u8]) -> Vec<u8> {
+    let mut end = prefix.to_vec();
+    while let Some(last) = end.pop() {
+        if last < 0xff {
+            end.push(last + 1);
+            return end;
+        }
+    }
+    // An empty (or all-0xff) prefix means every key
+    vec![0]
+}
+
+fn decode(value: &str) -> String {
+    BASE64
+        .decode(value)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default()
+}
@@ -0,0 +1,193 @@
+mod consul;
+#[cfg(unix)]
+mod docker;
+mod etcd;
+mod remote;
+
+use crate::config::{Config, SiteConfig, SiteKind};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use url::Url;
+
+/// A site appearing or going away while running
+#[derive(Debug)]
+pub enum SiteChange {
+    Added(Box<SiteConfig>),
+    Removed(String),
+    /// The source couldn't be read; the sites it found before are kept
+    Failed(String),
+}
+
+/// Start a task for each configured source of sites: discovery and `sites_url`
+pub fn spawn_discovery(
+    full_config: &Config,
+    changes: &mpsc::Sender<SiteChange>,
+    shutdown: &watch::Receiver<bool>,
+) -> Vec<tokio::task::JoinHandle<()>> {
+    let config = &full_config.discovery;
+    let mut tasks = Vec::new();
+    if let Some(remote) = full_config.sites_url.clone() {
+        let interval = Duration::from_secs(remote.refresh_interval);
+        let sites = DiscoveredSites::new("sites_url", changes.clone());
+        tasks.push(spawn_poll(sites, interval, shutdown.clone(), move |client| {
+            let remote = remote.clone();
+            async move { remote::fetch(&client, &remote).await }
+        }));
+    }
+    // Validation turns [discovery.docker] away where there is no unix socket to watch
+    #[cfg(unix)]
+    if let Some(docker) = config.docker.clone() {
+        tasks.push(docker::spawn_docker_discovery(docker, changes.clone(), shutdown.clone()));
+    }
+    if let Some(consul) = config.consul.clone() {
+        let interval = Duration::from_secs(consul.poll_interval);
+        let sites = DiscoveredSites::new("Consul discovery", changes.clone());
+        tasks.push(spawn_poll(sites, interval, shutdown.clone(), move |client| {
+            let consul = consul.clone();
+            async move { consul::fetch(&client, &consul).await }
+        }));
+    }
+    if let Some(etcd) = config.etcd.clone() {
+        let interval = Duration::from_secs(etcd.poll_interval);
+        let sites = DiscoveredSites::new("etcd discovery", changes.clone());
+        tasks.push(spawn_poll(sites, interval, shutdown.clone(), move |client| {
+            let etcd = etcd.clone();
+            async move { etcd::fetch(&client, &etcd).await }
+        }));
+    }
+    tasks
+}
+
+/// Read a source now and every `interval` until shutdown
+fn spawn_poll<F, Fut>(
+    mut sites: DiscoveredSites,
+    interval: Duration,
+    mut shutdown: watch::Receiver<bool>,
+    fetch: F,
+) -> tokio::task::JoinHandle<()>
+where
+    F: Fn(reqwest::Client) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = Result<Found>> + Send,
+{
+    tokio::spawn(async move {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        loop {
+            tokio::select! {
+                found = fetch(client.clone()) => match found {
+                    Ok(found) => sites.update(found).await,
+                    Err(e) => sites.report(format!("{:#}", e)).await,
+                },
+                _ = shutdown.changed() => return,
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => {}
+                _ = shutdown.changed() => return,
+            }
+        }
+    })
+}
+
+/// What one read of a source turned up: a `[[sites]]` table per site, and
+/// problems with entries that couldn't become one
+#[derive(Debug, Default)]
+pub struct Found {
+    pub sites: Vec<toml::Table>,
+    pub problems: Vec<String>,
+}
+
+/// The sites one discovery source has added, kept in step with what it finds
+struct DiscoveredSites {
+    /// Prefix for problems shown to the user, e.g. "Docker discovery"
+    label: &'static str,
+    changes: mpsc::Sender<SiteChange>,
+    /// Sites added so far, with the table each was built from
+    sites: HashMap<String, toml::Table>,
+    /// Last problem reported, so a persistent one is reported once
+    last_error: Option<String>,
+}
+
+impl DiscoveredSites {
+    fn new(label: &'static str, changes: mpsc::Sender<SiteChange>) -> Self {
+        Self {
+            label,
+            changes,
+            sites: HashMap::new(),
+            last_error: None,
+        }
+    }
+
+    /// Make the added sites match what the source has now
+    async fn update(&mut self, found: Found) {
+        let mut problems = found.problems;
+        let mut wanted: HashMap<String, toml::Table> = HashMap::new();
+        for table in found.sites {
+            let name = table.get("name").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+            if wanted.contains_key(&name) {
+                problems.push(format!("site name '{}' found more than once", name));
+                continue;
+            }
+            wanted.insert(name, table);
+        }
+
+        // Drop sites that went away or changed...
+        let stale: Vec<String> = self
+            .sites
+            .iter()
+            .filter(|(name, table)| wanted.get(*name) != Some(table))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in stale {
+            self.sites.remove(&name);
+            let _ = self.changes.send(SiteChange::Removed(name)).await;
+        }
+
+        // ...then add the new and changed ones
+        for (name, table) in wanted {
+            if self.sites.contains_key(&name) {
+                continue;
+            }
+            match site_from_table(table.clone()) {
+                Ok(site) => {
+                    self.sites.insert(name, table);
+                    let _ = self.changes.send(SiteChange::Added(Box::new(site))).await;
+                }
+                Err(e) => problems.push(format!("site '{}': {:#}", name, e)),
+            }
+        }
+
+        if problems.is_empty() {
+            self.last_error = None;
+        } else {
+            self.report(problems.join("; ")).await;
+        }
+    }
+
+    async fn report(&mut self, problem: String) {
+        let message = format!("{}: {}", self.label, problem);
+        if self.last_error.as_ref() != Some(&message) {
+            self.last_error = Some(message.clone());
+            let _ = self.changes.send(SiteChange::Failed(message)).await;
+        }
+    }
+}
+
+/// Build a discovered site from config keys, as a `[[sites]]` table would give them
+fn site_from_table(table: toml::Table) -> Result<SiteConfig> {
+    let site: SiteConfig = toml::Value::Table(table).try_into().context("invalid site settings")?;
+    if site.name.trim().is_empty() {
+        anyhow::bail!("empty site name");
+    }
+    if !matches!(site.kind, SiteKind::Http | SiteKind::Graphql) {
+        anyhow::bail!("only http and graphql sites can be added at runtime");
+    }
+    let url = Url::parse(&site.url).with_context(|| format!("invalid url '{}'", site.url))?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        anyhow::bail!("url '{}' must start with http:// or https://", site.url);
+    }
+    Ok(site)
+}
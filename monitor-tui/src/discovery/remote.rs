@@ -0,0 +1,41 @@
+use super::Found;
+use crate::config::{Config, RemoteSitesConfig};
+use anyhow::{Context, Result};
+
+/// Fetch the shared site list: a TOML file of `[[sites]]`, which may use
+/// `[site_defaults]` and `[templates]` like a local config
+pub async fn fetch(client: &reqwest::Client, config: &RemoteSitesConfig) -> Result<Found> {
+    let mut request = client.get(&config.url);
+    if let Some(token) = &config.bearer_token {
+        request = request.bearer_auth(token);
+    }
+    if let Some(username) = &config.username {
+        request = request.basic_auth(username, config.password.as_ref());
+    }
+    let contents = request
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch {}", config.url))?
+        .error_for_status()?
+        .text()
+        .await
+        .with_context(|| format!("Failed to read {}", config.url))?;
+
+    let mut raw: toml::Table = toml::from_str(&contents).with_context(|| format!("{} is not valid TOML", config.url))?;
+    Config::apply_site_defaults(&mut raw)?;
+
+    let mut found = Found::default();
+    match raw.remove("sites") {
+        Some(toml::Value::Array(sites)) => {
+            for (index, site) in sites.into_iter().enumerate() {
+                match site {
+                    toml::Value::Table(table) => found.sites.push(table),
+                    _ => found.problems.push(format!("entry {} in 'sites' is not a table", index + 1)),
+                }
+            }
+        }
+        Some(_) => anyhow::bail!("'sites' in {} must be an array of tables", config.url),
+        None => {}
+    }
+    Ok(found)
+}
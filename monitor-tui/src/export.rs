@@ -0,0 +1,163 @@
+use crate::checker::Status;
+use crate::config::slugify;
+use crate::history::SiteHistory;
+use anyhow::Result;
+use chrono::{DateTime, Local, TimeDelta, Utc};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// Size of exported charts in pixels
+const SIZE: (u32, u32) = (1200, 600);
+
+/// Share of the image height given to the status strip under the response-time chart
+const STRIP_SHARE: f64 = 0.18;
+
+/// Smallest status strip: its time labels plus a visible band
+const STRIP_MIN_HEIGHT: u32 = 50;
+
+const UP: RGBColor = RGBColor(46, 160, 67);
+const WARNING: RGBColor = RGBColor(210, 153, 34);
+const DOWN: RGBColor = RGBColor(218, 54, 51);
+const SKIPPED: RGBColor = RGBColor(150, 150, 150);
+const LINE: RGBColor = RGBColor(56, 110, 200);
+
+/// Image formats a chart can be exported as
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartFormat {
+    Svg,
+    Png,
+}
+
+impl ChartFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ChartFormat::Svg => "svg",
+            ChartFormat::Png => "png",
+        }
+    }
+}
+
+/// Render a site's response-time and status history as an image in `dir`,
+/// for pasting into incident reports. Returns the file written.
+pub fn export_chart(site_name: &str, history: &SiteHistory, dir: &Path, format: ChartFormat) -> Result<PathBuf> {
+    if history.is_empty() {
+        anyhow::bail!("No checks recorded for '{}' yet", site_name);
+    }
+
+    let file_name = format!(
+        "{}-{}.{}",
+        slugify(site_name),
+        Local::now().format("%Y%m%d-%H%M%S"),
+        format.extension()
+    );
+    let path = dir.join(file_name);
+
+    let title = Some(site_name);
+    match format {
+        ChartFormat::Svg => draw(SVGBackend::new(&path, SIZE).into_drawing_area(), title, history)?,
+        ChartFormat::Png => draw(BitMapBackend::new(&path, SIZE).into_drawing_area(), title, history)?,
+    }
+
+    Ok(path)
+}
+
+/// Render the same chart, untitled, into an RGB pixel buffer of the given size
+pub fn render_rgb(history: &SiteHistory, size: (u32, u32)) -> Result<Vec<u8>> {
+    let mut buffer = vec![0; size.0 as usize * size.1 as usize * 3];
+    draw(BitMapBackend::with_buffer(&mut buffer, size).into_drawing_area(), None, history)?;
+    Ok(buffer)
+}
+
+fn draw<DB>(root: DrawingArea<DB, Shift>, title: Option<&str>, history: &SiteHistory) -> Result<()>
+where
+    DB: DrawingBackend,
+    DB::ErrorType: 'static,
+{
+    let results = history.all_results();
+    let (Some(first), Some(last)) = (results.front(), results.back()) else {
+        return Ok(());
+    };
+
+    // A single check still needs a time range to draw on
+    let start = first.timestamp;
+    let end = if last.timestamp > start { last.timestamp } else { start + TimeDelta::minutes(1) };
+    let max_ms = history.max_response_time().unwrap_or(0).max(10) as f64 * 1.1;
+    let time_label = |t: &DateTime<Utc>| t.with_timezone(&Local).format("%H:%M:%S").to_string();
+
+    root.fill(&WHITE)?;
+    let height = root.dim_in_pixel().1;
+    let strip_height = ((height as f64 * STRIP_SHARE) as u32).max(STRIP_MIN_HEIGHT);
+    let (upper, lower) = root.split_vertically(height.saturating_sub(strip_height));
+    // Small images (inline terminal charts) can't spare wide margins
+    let margin = if height >= 400 { 15 } else { 5 };
+
+    // Response times, with each check's point colored by its status; the
+    // strip below carries the time labels for both
+    let mut builder = ChartBuilder::on(&upper);
+    if let Some(site_name) = title {
+        builder.caption(format!("{} - response time", site_name), ("sans-serif", 24));
+    }
+    let mut chart = builder
+        .margin(margin)
+        .margin_bottom(0)
+        .x_label_area_size(0)
+        .y_label_area_size(60)
+        .build_cartesian_2d(start..end, 0.0..max_ms)?;
+    chart
+        .configure_mesh()
+        .x_labels(8)
+        .y_label_formatter(&|ms| format!("{:.0}", ms))
+        .y_desc("ms")
+        .label_style(("sans-serif", 14))
+        .draw()?;
+
+    let points: Vec<(DateTime<Utc>, f64, &Status)> = results
+        .iter()
+        .filter_map(|r| r.response_time_ms.map(|ms| (r.timestamp, ms as f64, &r.status)))
+        .collect();
+    chart.draw_series(LineSeries::new(points.iter().map(|&(t, ms, _)| (t, ms)), &LINE))?;
+    chart.draw_series(
+        points
+            .iter()
+            .map(|&(t, ms, status)| Circle::new((t, ms), 3, status_color(status).filled())),
+    )?;
+
+    // Status strip: each check's status holds until the next check
+    let mut strip = ChartBuilder::on(&lower)
+        .margin_left(margin)
+        .margin_right(margin)
+        .margin_top(5)
+        .margin_bottom(margin)
+        .x_label_area_size(25)
+        .y_label_area_size(60)
+        .build_cartesian_2d(start..end, 0.0..1.0)?;
+    strip
+        .configure_mesh()
+        .disable_mesh()
+        .x_labels(8)
+        .x_label_formatter(&time_label)
+        .y_labels(0)
+        .y_desc("status")
+        .label_style(("sans-serif", 14))
+        .draw()?;
+    strip.draw_series(results.iter().zip(results.iter().skip(1).map(Some).chain([None])).map(
+        |(result, next)| {
+            let until = next.map_or(end, |n| n.timestamp);
+            Rectangle::new([(result.timestamp, 0.0), (until, 1.0)], status_color(&result.status).filled())
+        },
+    ))?;
+
+    root.present()?;
+    Ok(())
+}
+
+fn status_color(status: &Status) -> RGBColor {
+    match status {
+        Status::Up => UP,
+        Status::Warning => WARNING,
+        Status::Down => DOWN,
+        Status::Skipped => SKIPPED,
+        Status::RateLimited => WARNING,
+    }
+}
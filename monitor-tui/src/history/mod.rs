@@ -0,0 +1,402 @@
+use crate::checker::{CheckResult, Status};
+use crate::config::UptimeWindow;
+use chrono::{DateTime, Datelike, TimeDelta, TimeZone, Utc};
+use std::collections::VecDeque;
+
+mod store;
+
+pub use store::HistoryStore;
+
+/// Historical data for a single monitored site
+pub struct SiteHistory {
+    results: VecDeque<CheckResult>,
+    max_size: usize,
+    /// Status of the checks since the last change and when it began; kept
+    /// apart from `results` so streaks outlast the ring buffer
+    streak: Option<(Status, DateTime<Utc>)>,
+    /// Downtime this month, likewise outlasting the ring buffer
+    month: MonthDowntime,
+    /// Check counts over the last week, for uptime and status codes over a
+    /// span of time rather than over however many checks the ring buffer holds
+    uptime: VecDeque<UptimeBucket>,
+}
+
+/// Stretch of time the uptime counts are grouped into
+const UPTIME_BUCKET_SECS: i64 = 300;
+
+/// How far back uptime counts are kept
+const UPTIME_RETENTION: TimeDelta = TimeDelta::days(7);
+
+/// Up and conclusive check counts for one bucket of time
+#[derive(Debug, Clone)]
+struct UptimeBucket {
+    start: DateTime<Utc>,
+    up: u32,
+    checked: u32,
+    /// Checks by HTTP status code, None for no response
+    codes: Vec<(Option<u16>, u32)>,
+}
+
+/// Time a site spent Down in the current calendar month (UTC), counting each
+/// gap between checks as the status of the check that began it
+#[derive(Debug, Clone, Copy)]
+pub struct MonthDowntime {
+    pub start: DateTime<Utc>,
+    pub down: TimeDelta,
+    pub observed: TimeDelta,
+}
+
+impl MonthDowntime {
+    fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            start: month_start(now),
+            down: TimeDelta::zero(),
+            observed: TimeDelta::zero(),
+        }
+    }
+
+    /// Count the time from `previous` (checked with `status`) until `now`
+    fn record(&mut self, status: &Status, previous: DateTime<Utc>, now: DateTime<Utc>) {
+        if now >= month_end(self.start) {
+            *self = Self::new(now);
+        }
+        let elapsed = now - previous.max(self.start);
+        if elapsed <= TimeDelta::zero() {
+            return;
+        }
+        self.observed += elapsed;
+        if *status == Status::Down {
+            self.down += elapsed;
+        }
+    }
+}
+
+/// Midnight UTC on the first of the month `time` falls in
+pub fn month_start(time: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(time.year(), time.month(), 1, 0, 0, 0).single().unwrap_or(time)
+}
+
+/// Midnight UTC on the first of the month after the one starting at `start`
+pub fn month_end(start: DateTime<Utc>) -> DateTime<Utc> {
+    let (year, month) = if start.month() == 12 { (start.year() + 1, 1) } else { (start.year(), start.month() + 1) };
+    Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single().unwrap_or(start)
+}
+
+impl SiteHistory {
+    /// Create a new SiteHistory with specified maximum size
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            results: VecDeque::with_capacity(max_size),
+            max_size,
+            streak: None,
+            month: MonthDowntime::new(Utc::now()),
+            uptime: VecDeque::new(),
+        }
+    }
+
+    /// Add a new check result, maintaining size limit
+    pub fn add_result(&mut self, result: CheckResult) {
+        if result.status.is_conclusive()
+            && let Some(previous) = self.latest_checked()
+        {
+            let (status, since) = (previous.status.clone(), previous.timestamp);
+            self.month.record(&status, since, result.timestamp);
+        }
+        if self.results.len() >= self.max_size {
+            self.results.pop_front();
+        }
+        self.count_in_bucket(&result);
+        if result.status.is_conclusive() && self.streak.as_ref().is_none_or(|(status, _)| *status != result.status) {
+            self.streak = Some((result.status.clone(), result.timestamp));
+        }
+        self.results.push_back(result);
+    }
+
+    /// Add a check to its time bucket; skipped and rate-limited checks only
+    /// count toward status codes
+    fn count_in_bucket(&mut self, result: &CheckResult) {
+        if result.status == Status::Skipped {
+            return;
+        }
+        let secs = result.timestamp.timestamp();
+        let start = DateTime::from_timestamp(secs - secs.rem_euclid(UPTIME_BUCKET_SECS), 0).unwrap_or(result.timestamp);
+        if self.uptime.back().is_none_or(|bucket| bucket.start != start) {
+            self.uptime.push_back(UptimeBucket { start, up: 0, checked: 0, codes: Vec::new() });
+        }
+        if let Some(bucket) = self.uptime.back_mut() {
+            if result.status.is_conclusive() {
+                bucket.checked += 1;
+                bucket.up += (result.status == Status::Up) as u32;
+            }
+            match bucket.codes.iter_mut().find(|(code, _)| *code == result.http_status) {
+                Some((_, count)) => *count += 1,
+                None => bucket.codes.push((result.http_status, 1)),
+            }
+        }
+        while self.uptime.front().is_some_and(|b| b.start < result.timestamp - UPTIME_RETENTION) {
+            self.uptime.pop_front();
+        }
+    }
+
+    /// Uptime percentage over `window`, None with no checks in it
+    pub fn uptime(&self, window: UptimeWindow) -> Option<f64> {
+        let Some(span) = window.duration() else {
+            return self.latest_checked().is_some().then(|| self.uptime_percentage());
+        };
+        // Buckets count whole when any of them falls in the window
+        let since = Utc::now() - span - TimeDelta::seconds(UPTIME_BUCKET_SECS);
+        let (up, checked) = self
+            .uptime
+            .iter()
+            .filter(|b| b.start > since)
+            .fold((0u64, 0u64), |(up, checked), b| (up + b.up as u64, checked + b.checked as u64));
+        (checked > 0).then(|| up as f64 / checked as f64 * 100.0)
+    }
+
+    /// How often each HTTP status code was seen over `window`, most frequent
+    /// first; None counts checks that got no response. Empty until a code is seen.
+    pub fn status_codes(&self, window: UptimeWindow) -> Vec<(Option<u16>, u32)> {
+        let mut counts: Vec<(Option<u16>, u32)> = Vec::new();
+        let mut add = |code: Option<u16>, n: u32| match counts.iter_mut().find(|(c, _)| *c == code) {
+            Some((_, count)) => *count += n,
+            None => counts.push((code, n)),
+        };
+        match window.duration() {
+            None => {
+                for result in self.results.iter().filter(|r| r.status != Status::Skipped) {
+                    add(result.http_status, 1);
+                }
+            }
+            Some(span) => {
+                let since = Utc::now() - span - TimeDelta::seconds(UPTIME_BUCKET_SECS);
+                for bucket in self.uptime.iter().filter(|b| b.start > since) {
+                    for &(code, n) in &bucket.codes {
+                        add(code, n);
+                    }
+                }
+            }
+        }
+        if counts.iter().all(|(code, _)| code.is_none()) {
+            return Vec::new();
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        counts
+    }
+
+    /// Current status and how long the site has had it, inconclusive checks aside
+    pub fn current_streak(&self) -> Option<(&Status, TimeDelta)> {
+        self.streak
+            .as_ref()
+            .map(|(status, since)| (status, Utc::now().signed_duration_since(*since)))
+    }
+
+    /// Downtime recorded so far this month
+    pub fn month_downtime(&self) -> MonthDowntime {
+        self.month
+    }
+
+    /// Share of the time since `since` the site was Down, as far as the
+    /// stored results cover it
+    pub fn down_fraction_since(&self, since: DateTime<Utc>) -> Option<f64> {
+        let checked: Vec<&CheckResult> = self.results.iter().filter(|r| r.status.is_conclusive()).collect();
+        let mut down = TimeDelta::zero();
+        let mut observed = TimeDelta::zero();
+        for pair in checked.windows(2) {
+            let elapsed = pair[1].timestamp - pair[0].timestamp.max(since);
+            if elapsed <= TimeDelta::zero() {
+                continue;
+            }
+            observed += elapsed;
+            if pair[0].status == Status::Down {
+                down += elapsed;
+            }
+        }
+        (observed > TimeDelta::zero())
+            .then(|| down.num_milliseconds() as f64 / observed.num_milliseconds() as f64)
+    }
+
+    /// Get the most recent check result
+    pub fn latest(&self) -> Option<&CheckResult> {
+        self.results.back()
+    }
+
+    /// Get the most recent result that actually checked the site
+    pub fn latest_checked(&self) -> Option<&CheckResult> {
+        self.results.iter().rev().find(|r| r.status.is_conclusive())
+    }
+
+    /// Attach diagnostics findings to the result checked at `checked_at`
+    pub fn attach_diagnostics(&mut self, checked_at: DateTime<Utc>, findings: Vec<String>) {
+        if let Some(result) = self.results.iter_mut().rev().find(|r| r.timestamp == checked_at) {
+            result.diagnostics = findings;
+        }
+    }
+
+    /// Diagnostics gathered when the current Down streak began, if any
+    pub fn current_diagnostics(&self) -> Option<&[String]> {
+        self.results
+            .iter()
+            .rev()
+            .filter(|r| r.status.is_conclusive())
+            .take_while(|r| r.status == Status::Down)
+            .find(|r| !r.diagnostics.is_empty())
+            .map(|r| r.diagnostics.as_slice())
+    }
+
+    /// Calculate average response time from recent results
+    pub fn avg_response_time(&self) -> Option<u64> {
+        let times: Vec<u64> = self
+            .results
+            .iter()
+            .filter_map(|r| r.response_time_ms)
+            .collect();
+
+        if times.is_empty() {
+            None
+        } else {
+            Some(times.iter().sum::<u64>() / times.len() as u64)
+        }
+    }
+
+    /// Calculate uptime percentage (% of Up status results), leaving out
+    /// skipped and rate-limited checks
+    pub fn uptime_percentage(&self) -> f64 {
+        let checked = self.results.iter().filter(|r| r.status.is_conclusive()).count();
+        if checked == 0 {
+            return 0.0;
+        }
+
+        let up_count = self
+            .results
+            .iter()
+            .filter(|r| r.status == Status::Up)
+            .count();
+
+        (up_count as f64 / checked as f64) * 100.0
+    }
+
+    /// Get the number of stored results
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Check if history is empty
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Get recent checks for sparkline visualization
+    /// Returns up to the last `limit` response times in chronological order,
+    /// None for checks that got no response (Down); skipped and rate-limited checks are left out
+    pub fn recent_checks(&self, limit: usize) -> Vec<Option<u64>> {
+        self.results
+            .iter()
+            .rev()
+            .filter(|r| r.status.is_conclusive())
+            .take(limit)
+            .map(|r| r.response_time_ms)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect()
+    }
+
+    /// Get minimum response time from all results
+    pub fn min_response_time(&self) -> Option<u64> {
+        self.results
+            .iter()
+            .filter_map(|r| r.response_time_ms)
+            .min()
+    }
+
+    /// Get maximum response time from all results
+    pub fn max_response_time(&self) -> Option<u64> {
+        self.results
+            .iter()
+            .filter_map(|r| r.response_time_ms)
+            .max()
+    }
+
+    /// Get chart data points for visualization, downsampled to at most about
+    /// `max_points` so large histories stay cheap to draw.
+    /// Returns (timestamp_seconds, response_time_ms) pairs
+    /// With `down_as_zero`, checks that got no response count as 0ms instead
+    /// of being left out.
+    pub fn chart_data(&self, max_points: usize, down_as_zero: bool) -> Vec<(f64, f64)> {
+        let points: Vec<(f64, f64)> = self
+            .results
+            .iter()
+            .filter(|r| r.status.is_conclusive())
+            .filter_map(|r| {
+                let time = r.response_time_ms.or(down_as_zero.then_some(0))?;
+                Some((r.timestamp.timestamp() as f64, time as f64))
+            })
+            .collect();
+
+        downsample_min_max(points, max_points)
+    }
+
+    /// Chart data as separate line segments, broken wherever a check got no
+    /// response, each downsampled in proportion to its share of the points
+    pub fn chart_segments(&self, max_points: usize) -> Vec<Vec<(f64, f64)>> {
+        let mut segments = Vec::new();
+        let mut current = Vec::new();
+        for r in self.results.iter().filter(|r| r.status.is_conclusive()) {
+            match r.response_time_ms {
+                Some(time) => current.push((r.timestamp.timestamp() as f64, time as f64)),
+                None if !current.is_empty() => segments.push(std::mem::take(&mut current)),
+                None => {}
+            }
+        }
+        if !current.is_empty() {
+            segments.push(current);
+        }
+
+        let total: usize = segments.iter().map(Vec::len).sum();
+        segments
+            .into_iter()
+            .map(|s| {
+                let budget = (max_points * s.len()).checked_div(total).unwrap_or(0).max(2);
+                downsample_min_max(s, budget)
+            })
+            .collect()
+    }
+
+    /// Timestamps (seconds) of checks that got no response
+    pub fn down_times(&self) -> Vec<f64> {
+        self.results
+            .iter()
+            .filter(|r| r.status.is_conclusive() && r.response_time_ms.is_none())
+            .map(|r| r.timestamp.timestamp() as f64)
+            .collect()
+    }
+
+    /// Get all results (for detailed view)
+    pub fn all_results(&self) -> &VecDeque<CheckResult> {
+        &self.results
+    }
+}
+
+/// Reduce points to about `max_points` by keeping the lowest and highest value
+/// of each bucket in time order, so spikes survive where averaging would hide them
+fn downsample_min_max(points: Vec<(f64, f64)>, max_points: usize) -> Vec<(f64, f64)> {
+    let buckets = max_points / 2;
+    if buckets == 0 || points.len() <= max_points {
+        return points;
+    }
+
+    let bucket_size = points.len().div_ceil(buckets);
+    let mut sampled = Vec::with_capacity(buckets * 2);
+    for bucket in points.chunks(bucket_size) {
+        let min = bucket.iter().copied().min_by(|a, b| a.1.total_cmp(&b.1));
+        let max = bucket.iter().copied().max_by(|a, b| a.1.total_cmp(&b.1));
+        match (min, max) {
+            (Some(min), Some(max)) if min.0 <= max.0 => sampled.extend([min, max]),
+            (Some(min), Some(max)) => sampled.extend([max, min]),
+            _ => {}
+        }
+    }
+    sampled
+}
@@ -0,0 +1,200 @@
+use super::{month_start, SiteHistory, UPTIME_RETENTION};
+use crate::checker::CheckResult;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+/// How often a running store drops what the file no longer needs
+const COMPACT_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// One line of the history file
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    site: String,
+    #[serde(flatten)]
+    result: CheckResult,
+}
+
+/// Check results kept on disk, so history, uptime and error budgets survive a
+/// restart. Results are appended as JSON Lines by a background thread, so a
+/// slow disk never holds up the UI; the file is compacted on every start and
+/// daily while running.
+pub struct HistoryStore {
+    tx: mpsc::Sender<String>,
+    /// Stored results of sites not configured when the file was opened
+    unclaimed: HashMap<String, Vec<CheckResult>>,
+}
+
+impl HistoryStore {
+    /// Default file location: the platform data directory
+    /// (~/.local/share/monitor/ on Linux), one file per config
+    pub fn default_path(config_path: &Path) -> Option<PathBuf> {
+        let name = crate::instance_lock::runtime_path(config_path, "history.jsonl");
+        let name = name.file_name()?;
+        dirs::data_local_dir().map(|dir| dir.join("monitor").join(name))
+    }
+
+    /// Replay the results stored at `path` into the histories of the sites
+    /// configured, rewrite the file with only what's still needed, and start
+    /// appending to it. Results of other sites stay in the file, and are kept
+    /// for `replay` in case they're discovered. Returns the store and how many
+    /// results came back.
+    pub fn open(path: &Path, sites: &mut IndexMap<String, SiteHistory>, history_size: usize) -> Result<(Self, usize)> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        }
+        let stored = read(path)?;
+
+        let since = keep_since();
+        let mut restored = 0;
+        let mut kept = Vec::new();
+        let mut unclaimed = HashMap::new();
+        for (site, results) in stored {
+            let results = retain(results, history_size, since);
+            kept.extend(results.iter().map(|result| Entry { site: site.clone(), result: result.clone() }));
+            match sites.get_mut(&site) {
+                Some(history) => {
+                    restored += results.len();
+                    results.into_iter().for_each(|result| history.add_result(result));
+                }
+                None => {
+                    unclaimed.insert(site, results);
+                }
+            }
+        }
+        kept.sort_by_key(|entry| entry.result.timestamp);
+        compact(path, &kept)?;
+
+        let file = open_append(path)?;
+        let path = path.to_path_buf();
+        let (tx, rx) = mpsc::channel::<String>();
+        std::thread::spawn(move || {
+            let mut out = BufWriter::new(file);
+            let mut compact_at = Instant::now() + COMPACT_INTERVAL;
+            loop {
+                // Write whatever has queued up, then flush once before waiting again
+                match rx.recv_timeout(compact_at.saturating_duration_since(Instant::now())) {
+                    Ok(line) => {
+                        let _ = writeln!(out, "{}", line);
+                        for line in rx.try_iter() {
+                            let _ = writeln!(out, "{}", line);
+                        }
+                        let _ = out.flush();
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+                // A long-running instance would otherwise grow the file forever;
+                // on failure keep appending to the old one and try again later
+                if Instant::now() >= compact_at {
+                    compact_at = Instant::now() + COMPACT_INTERVAL;
+                    if let Ok(file) = recompact(&path, history_size) {
+                        out = BufWriter::new(file);
+                    }
+                }
+            }
+        });
+        Ok((Self { tx, unclaimed }, restored))
+    }
+
+    /// Replay the stored results of a site added since the file was opened,
+    /// like a discovered one, into its history. Returns how many came back.
+    pub fn replay(&mut self, site: &str, history: &mut SiteHistory) -> usize {
+        let results = self.unclaimed.remove(site).unwrap_or_default();
+        let restored = results.len();
+        results.into_iter().for_each(|result| history.add_result(result));
+        restored
+    }
+
+    /// Queue a result to be appended to the file
+    pub fn record(&self, site: &str, result: &CheckResult) {
+        let entry = Entry { site: site.to_string(), result: result.clone() };
+        if let Ok(line) = serde_json::to_string(&entry) {
+            let _ = self.tx.send(line);
+        }
+    }
+}
+
+/// Start of what the file keeps: enough for the longest uptime window and
+/// this month's error budget
+fn keep_since() -> DateTime<Utc> {
+    let now = Utc::now();
+    month_start(now).min(now - UPTIME_RETENTION)
+}
+
+/// The results of one site worth keeping, oldest first: everything since
+/// `since`, and at least a full ring buffer
+fn retain(results: Vec<CheckResult>, history_size: usize, since: DateTime<Utc>) -> Vec<CheckResult> {
+    let last_ring = results.len().saturating_sub(history_size);
+    results
+        .into_iter()
+        .enumerate()
+        .filter(|(index, result)| result.timestamp >= since || *index >= last_ring)
+        .map(|(_, result)| result)
+        .collect()
+}
+
+/// Compact the file by the same rule as on startup, keeping every site in
+/// it, and return a new handle to append to
+fn recompact(path: &Path, history_size: usize) -> Result<File> {
+    let since = keep_since();
+    let mut kept: Vec<Entry> = read(path)?
+        .into_iter()
+        .flat_map(|(site, results)| {
+            retain(results, history_size, since).into_iter().map(move |result| Entry { site: site.clone(), result })
+        })
+        .collect();
+    kept.sort_by_key(|entry| entry.result.timestamp);
+    compact(path, &kept)?;
+    open_append(path)
+}
+
+fn open_append(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open history file: {}", path.display()))
+}
+
+/// Results stored for each site, oldest first. Lines that don't parse, like
+/// one cut short by a crash, are skipped.
+fn read(path: &Path) -> Result<HashMap<String, Vec<CheckResult>>> {
+    let mut stored: HashMap<String, Vec<CheckResult>> = HashMap::new();
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(stored),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read history file: {}", path.display())),
+    };
+    for line in BufReader::new(file).lines() {
+        let line = line.with_context(|| format!("Failed to read history file: {}", path.display()))?;
+        if let Ok(entry) = serde_json::from_str::<Entry>(&line) {
+            stored.entry(entry.site).or_default().push(entry.result);
+        }
+    }
+    Ok(stored)
+}
+
+/// Replace the file with `entries`, in one step so a crash mid-write can't
+/// lose the history
+fn compact(path: &Path, entries: &[Entry]) -> Result<()> {
+    let temp = path.with_extension("jsonl.tmp");
+    let mut out = BufWriter::new(
+        File::create(&temp).with_context(|| format!("Failed to write history file: {}", temp.display()))?,
+    );
+    for entry in entries {
+        writeln!(out, "{}", serde_json::to_string(entry)?)?;
+    }
+    out.flush()?;
+    drop(out);
+    std::fs::rename(&temp, path).with_context(|| format!("Failed to write history file: {}", path.display()))?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use crate::checker::heartbeat::HeartbeatRegistry;
+use crate::config::HttpServerConfig;
+use crate::monitor_health::MonitorHealth;
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{any, get},
+    Json, Router,
+};
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// What the request handlers share
+#[derive(Clone)]
+struct ServerState {
+    heartbeats: HeartbeatRegistry,
+    health: Arc<MonitorHealth>,
+}
+
+/// Bind the embedded HTTP server and serve it until shutdown
+pub async fn spawn_http_server(
+    config: HttpServerConfig,
+    heartbeats: HeartbeatRegistry,
+    health: Arc<MonitorHealth>,
+    mut shutdown: watch::Receiver<bool>,
+) -> Result<tokio::task::JoinHandle<()>> {
+    let app = Router::new()
+        .route("/heartbeat/:token", any(heartbeat))
+        .route("/healthz", get(healthz))
+        .with_state(ServerState { heartbeats, health });
+
+    // Bind up front so a bad address fails before the TUI takes over the terminal
+    let listener = tokio::net::TcpListener::bind(&config.bind)
+        .await
+        .with_context(|| format!("Failed to bind HTTP server to {}", config.bind))?;
+
+    Ok(tokio::spawn(async move {
+        let _ = axum::serve(listener, app)
+            .with_graceful_shutdown(async move {
+                let _ = shutdown.changed().await;
+            })
+            .await;
+    }))
+}
+
+/// Cron jobs and backups ping this (any method) to prove they ran
+async fn heartbeat(State(state): State<ServerState>, Path(token): Path<String>) -> (StatusCode, &'static str) {
+    if state.heartbeats.record_ping(&token) {
+        (StatusCode::OK, "ok\n")
+    } else {
+        (StatusCode::NOT_FOUND, "unknown heartbeat\n")
+    }
+}
+
+/// The monitor's own state for meta-monitoring: 200 when every checker task is
+/// running and the UI is drawing, 503 otherwise
+async fn healthz(State(state): State<ServerState>) -> (StatusCode, Json<serde_json::Value>) {
+    let (healthy, report) = state.health.report();
+    let status = if healthy { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(report))
+}
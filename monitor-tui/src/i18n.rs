@@ -0,0 +1,195 @@
+use crate::config::Config;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Every translatable string by key, in English. Text in braces, like `{site}`,
+/// is filled in when the string is used and must appear in translations too.
+const ENGLISH: &[(&str, &str)] = &[
+    ("help.title", "Monitor TUI - Keyboard Shortcuts"),
+    ("help.block_title", "Help"),
+    ("help.section.global", "Global Commands"),
+    ("help.section.dashboard", "Dashboard View"),
+    ("help.section.detail", "Detail View"),
+    ("help.section.alerts", "Alert History View"),
+    ("help.section.alert_detail", "Alert Detail View"),
+    ("help.section.server", "Server View"),
+    ("help.section.status", "Status Indicators"),
+    ("help.section.config", "Configuration"),
+    ("help.quit", "Quit the application (asks first while sites are down)"),
+    ("help.exit", "Exit the application"),
+    ("help.help", "Show this help screen"),
+    ("help.dashboard", "Return to dashboard"),
+    ("help.palette", "Search and run any command by name"),
+    ("help.alerts", "View alert history"),
+    ("help.server", "View server metrics"),
+    ("help.overview", "View fleet-wide statistics for this session"),
+    ("help.budgets", "View error budgets of sites with an SLO target"),
+    ("help.theme", "Cycle theme (Dark/Light/High-Contrast)"),
+    ("help.pause", "Pause / resume all checks (only the marked sites' while any are marked)"),
+    ("help.carousel", "Start / stop cycling through each site's details"),
+    ("help.copy", "Copy the dashboard, details or alerts as text to the clipboard"),
+    ("help.previous_site", "Navigate up (select previous site)"),
+    ("help.next_site", "Navigate down (select next site)"),
+    ("help.jump", "Jump to first / last site (also Home / End)"),
+    ("help.expand", "Expand / collapse a composite site's members"),
+    ("help.mark", "Mark / unmark the selected site for bulk actions (ESC clears the marks)"),
+    ("help.pause_site", "Pause / resume checks of the selected site (or the marked ones), e.g. for maintenance"),
+    ("help.bulk", "With sites marked, p / P / R / m / T / + / - pause, re-check, silence, tag or reschedule all of them"),
+    ("help.interval", "Check the site less / more often, until stepped back to its configured interval"),
+    ("help.tag", "Add tags to the selected site, or remove them with a leading '-'"),
+    ("help.move", "Move the selected site up / down"),
+    ("help.health_sort", "Sort by health score, worst first / back to your order"),
+    ("help.pin", "Pin / unpin the selected site at the top"),
+    ("help.open_url", "Open the selected site's URL in a browser (also in detail view)"),
+    ("help.open_detail", "Open detail view for selected site"),
+    ("help.refresh", "Force refresh all sites immediately"),
+    ("help.recheck_selected", "Re-check only the selected site"),
+    ("help.recheck_site", "Re-check only this site"),
+    ("help.uptime_window", "Show uptime over stored checks / last 1h / 24h / 7d"),
+    ("help.export", "Export history chart as SVG / PNG"),
+    ("help.carousel_step", "Previous / next site while cycling (also Tab)"),
+    ("help.carousel_hold", "Hold on / move on from this site while cycling"),
+    ("help.previous_alert", "Navigate up (previous alert)"),
+    ("help.next_alert", "Navigate down (next alert)"),
+    ("help.open_alert", "View details for selected alert"),
+    ("help.clear_alerts", "Clear all alerts / only recovery alerts (asks first)"),
+    ("help.acknowledge", "Acknowledge the selected alert / take the acknowledgement back"),
+    ("help.hide_acknowledged", "Hide / show acknowledged alerts"),
+    ("help.alert_stats", "Alert statistics: alerts per site and day, MTBF, top errors"),
+    ("help.silence", "Silence alerts by site glob, severity and duration / lift those silences"),
+    ("help.back_to_alerts", "Return to alert history"),
+    ("help.comment", "Add or edit a comment on the alert"),
+    ("help.refresh_metrics", "Force refresh metrics immediately"),
+    ("help.scroll_up", "Scroll apps up"),
+    ("help.scroll_down", "Scroll apps down"),
+    ("help.status_up", "Site is responding with expected HTTP status"),
+    ("help.status_down", "Site is not responding or connection failed"),
+    ("help.status_warning", "Site is responding but with wrong HTTP status"),
+    ("help.health", "Health score 0-100: uptime, latency vs. threshold, alerts in the last day"),
+    ("help.checking", "A check of the site is in progress"),
+    ("help.config_paths", "Config file locations (in priority order):"),
+    ("help.loaded", "(loaded)"),
+    ("help.close", "Press ESC to return to dashboard"),
+    (
+        "footer.dashboard",
+        "↑↓: Navigate | Enter: Details | a: Alerts | o: Overview | s: Server | r/R: Refresh all/selected | p: Pause site | J/K: Move | f: Pin | H: Sort by health | y: Copy | c: Carousel | P: Pause | +/-: Interval | Space: Mark | T: Tag | Ctrl+P: Commands | ?/h: Help | q: Quit",
+    ),
+    (
+        "footer.detail",
+        "ESC: Back to Dashboard | e/E: Export SVG/PNG | a: Alerts | r: Refresh | R: Re-check site | p: Pause site | +/-: Interval | u: Uptime window | y: Copy | c: Carousel | ?/h: Help | q: Quit",
+    ),
+    ("footer.carousel", "←/→: Previous/Next site | Space: Hold/Continue | c: Stop carousel | ESC: Dashboard | q: Quit"),
+    ("footer.alerts", "↑↓: Navigate | Enter: Details | A: Acknowledge | F: Hide/show acknowledged | x/X: Clear all/recoveries | S: Statistics | m: Silence | y: Copy | ESC: Dashboard | r: Refresh | ?/h: Help | q: Quit"),
+    ("footer.alert_detail", "ESC: Back to Alerts | A: Acknowledge | n: Comment | y: Copy | a: Alerts List | r: Refresh | ?/h: Help | q: Quit"),
+    ("footer.alert_stats", "ESC: Back to Alerts | a: Alerts List | r: Refresh | ?/h: Help | q: Quit"),
+    ("footer.comment", "Type a comment | Enter: Save (empty clears it) | ESC: Cancel"),
+    ("footer.server", "s: Refresh | ↑↓/jk: Scroll | ESC: Dashboard | q: Quit"),
+    ("footer.overview", "ESC: Dashboard | b: Error budgets | a: Alerts | r: Refresh | ?/h: Help | q: Quit"),
+    ("footer.slo", "ESC: Dashboard | o: Overview | a: Alerts | r: Refresh | ?/h: Help | q: Quit"),
+    ("footer.silence", "Site glob [severity] [duration], e.g. staging-* warning 2h | Enter: Silence | ESC: Cancel"),
+    ("footer.tag", "Tags separated by spaces, '-tag' removes one | Enter: Apply | ESC: Cancel"),
+    ("footer.palette", "↑↓: Select | Enter: Run | ESC: Close"),
+    ("alert.down", "{site} is DOWN"),
+    ("alert.warning", "{site} has WARNING status"),
+    ("alert.recovered", "{site} has RECOVERED"),
+    ("alert.warning_to_down", "{site} went from WARNING to DOWN"),
+    ("alert.recovered_from_warning", "{site} recovered from WARNING"),
+    ("alert.down_to_warning", "{site} went from DOWN to WARNING"),
+    ("alert.stale", "{site} has stopped reporting results"),
+    ("alert.diagnosed", "Diagnostics for {site}, which is DOWN"),
+    ("alert.notification_title", "Monitor TUI Alert"),
+];
+
+/// Translations of the configured locale; empty until `init`, so English is used
+static TRANSLATIONS: OnceLock<HashMap<&'static str, String>> = OnceLock::new();
+
+/// Load the translations for `settings.locale` from `locales/<locale>.toml`
+/// next to the config file. Keys the file leaves out stay in English.
+pub fn init(config: &Config) -> Result<()> {
+    let Some(locale) = config.settings.locale.as_deref().filter(|locale| *locale != "en") else {
+        return Ok(());
+    };
+    let base_dir = config.source_path.parent().unwrap_or(std::path::Path::new("."));
+    let path = base_dir.join("locales").join(format!("{}.toml", locale));
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read translations for locale '{}': {}", locale, path.display()))?;
+    let table: toml::Table = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse translations: {}", path.display()))?;
+
+    let mut flat = Vec::new();
+    flatten(String::new(), toml::Value::Table(table), &mut flat)
+        .with_context(|| format!("Invalid translations in {}", path.display()))?;
+
+    let mut translations = HashMap::new();
+    for (key, text) in flat {
+        let Some(&(key, english)) = ENGLISH.iter().find(|(known, _)| *known == key) else {
+            anyhow::bail!("Unknown string '{}' in {}", key, path.display());
+        };
+        for placeholder in placeholders(english) {
+            if !text.contains(placeholder) {
+                anyhow::bail!("'{}' in {} must include {}", key, path.display(), placeholder);
+            }
+        }
+        translations.insert(key, text);
+    }
+    // Only ever set here, once at startup
+    let _ = TRANSLATIONS.set(translations);
+    Ok(())
+}
+
+/// Turn nested tables into dotted keys, the way they're written in TOML
+fn flatten(prefix: String, value: toml::Value, out: &mut Vec<(String, String)>) -> Result<()> {
+    match value {
+        toml::Value::String(text) => out.push((prefix, text)),
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let key = if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) };
+                flatten(key, value, out)?;
+            }
+        }
+        _ => anyhow::bail!("'{}' must be a string", prefix),
+    }
+    Ok(())
+}
+
+/// The `{name}` parts of a string
+fn placeholders(text: &str) -> impl Iterator<Item = &str> {
+    text.match_indices('{').filter_map(|(start, _)| {
+        let end = start + text[start..].find('}')?;
+        Some(&text[start..=end])
+    })
+}
+
+/// The string for `key` in the configured locale
+pub fn t(key: &'static str) -> &'static str {
+    if let Some(text) = TRANSLATIONS.get().and_then(|translations| translations.get(key)) {
+        return text;
+    }
+    ENGLISH
+        .iter()
+        .find(|(known, _)| *known == key)
+        .map(|(_, text)| *text)
+        .unwrap_or(key)
+}
+
+/// The string for `key` with each `{name}` replaced by its value in `args`
+pub fn tf(key: &'static str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(t(key).to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+}
+
+/// Every string in English as a locale file, for `monitor-tui strings`
+pub fn template() -> String {
+    let mut out = String::from("# Copy to locales/<locale>.toml next to sites.toml, translate, and set\n# `locale = \"<locale>\"` under [settings]. Leave out anything to keep it in English.\n");
+    let mut section = "";
+    for (key, text) in ENGLISH {
+        let (group, name) = key.split_once('.').unwrap_or(("", key));
+        if group != section {
+            out.push_str(&format!("\n[{}]\n", group));
+            section = group;
+        }
+        out.push_str(&format!("{} = {}\n", name, toml::Value::String(text.to_string())));
+    }
+    out
+}
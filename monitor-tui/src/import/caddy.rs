@@ -0,0 +1,137 @@
+use super::{ImportedSite, VirtualHost};
+use anyhow::Result;
+
+/// Convert each site block of a Caddyfile to a site, returning them and why
+/// any addresses were skipped
+pub fn convert(contents: &str) -> Result<(Vec<ImportedSite>, Vec<String>)> {
+    let mut sites = Vec::new();
+    let mut skipped = Vec::new();
+
+    for block in site_blocks(contents) {
+        let tls_internal = block.body.windows(2).any(|w| w[0] == "tls" && w[1] == "internal");
+        let mut hosts = Vec::new();
+        for address in &block.addresses {
+            match parse_address(address) {
+                Ok(host) => hosts.push(host),
+                Err(reason) => skipped.push(format!("'{}': {}", address, reason)),
+            }
+        }
+        let Some((first, aliases)) = hosts.split_first() else {
+            continue;
+        };
+
+        let mut site = first.to_site(aliases);
+        if first.scheme == "https" && (tls_internal || first.host == "localhost") {
+            site.notes.push("served with Caddy's internal CA, which checks won't trust".to_string());
+        }
+        sites.push(site);
+    }
+
+    Ok((sites, skipped))
+}
+
+/// A top-level block of the Caddyfile that serves sites
+struct SiteBlock {
+    addresses: Vec<String>,
+    /// Directive tokens inside the block, nested blocks included
+    body: Vec<String>,
+}
+
+/// Find the site blocks, skipping the global options block, snippets and named routes
+fn site_blocks(contents: &str) -> Vec<SiteBlock> {
+    let lines: Vec<Vec<&str>> = contents
+        .lines()
+        .map(|line| line.split_whitespace().take_while(|t| !t.starts_with('#')).collect())
+        .collect();
+
+    let mut blocks = Vec::new();
+    let mut depth = 0usize;
+    let mut addresses: Vec<String> = Vec::new();
+    let mut current: Option<SiteBlock> = None;
+    // First line of addresses not followed by a block, and where it ends
+    let mut braceless: Option<(Vec<String>, usize)> = None;
+
+    for (index, tokens) in lines.iter().enumerate() {
+        for &token in tokens {
+            match token {
+                "{" => {
+                    if depth == 0 {
+                        let names = std::mem::take(&mut addresses);
+                        let is_site = names
+                            .first()
+                            .is_some_and(|name| !name.starts_with('(') && !name.starts_with("&("));
+                        current = is_site.then(|| SiteBlock { addresses: names, body: Vec::new() });
+                    }
+                    depth += 1;
+                }
+                "}" => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0
+                        && let Some(block) = current.take()
+                    {
+                        blocks.push(block);
+                    }
+                }
+                _ if depth == 0 => {
+                    addresses.extend(token.split(',').filter(|a| !a.is_empty()).map(str::to_string))
+                }
+                _ => {
+                    if let Some(block) = current.as_mut() {
+                        block.body.push(token.to_string());
+                    }
+                }
+            }
+        }
+
+        // Addresses continue onto the next line only after a trailing comma
+        if depth == 0 && !addresses.is_empty() && tokens.last().is_some_and(|t| !t.ends_with(',')) {
+            let line = std::mem::take(&mut addresses);
+            braceless.get_or_insert((line, index));
+        }
+    }
+
+    // A Caddyfile serving one site may leave out the braces: its first line
+    // is the address and everything after it belongs to that site
+    if blocks.is_empty()
+        && let Some((addresses, index)) = braceless
+    {
+        let body = lines[index + 1..].iter().flatten().map(|t| t.to_string()).collect();
+        blocks.push(SiteBlock { addresses, body });
+    }
+
+    blocks
+}
+
+/// Work out the URL Caddy serves for a site address such as `example.com`,
+/// `http://example.com:8080/api` or `:8080`
+fn parse_address(address: &str) -> Result<VirtualHost, String> {
+    if address.contains('{') {
+        return Err("addresses with placeholders aren't supported".to_string());
+    }
+    if address.contains('*') {
+        return Err("wildcard addresses can't be checked".to_string());
+    }
+
+    let (scheme, rest) = match address.split_once("://") {
+        Some((scheme @ ("http" | "https"), rest)) => (Some(scheme), rest),
+        Some((scheme, _)) => return Err(format!("{} addresses can't be checked", scheme)),
+        None => (None, address),
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) if !port.contains(']') => {
+            let port = port.parse::<u16>().map_err(|_| format!("invalid port '{}'", port))?;
+            (host, Some(port))
+        }
+        _ => (authority, None),
+    };
+
+    // Caddy serves plain HTTP on port 80 and for addresses without a host,
+    // and HTTPS everywhere else
+    let scheme = scheme.unwrap_or(if port == Some(80) || host.is_empty() { "http" } else { "https" });
+    let host = if host.is_empty() { "localhost" } else { host };
+    Ok(VirtualHost::new(scheme, host, port, path.trim_end_matches('*')))
+}
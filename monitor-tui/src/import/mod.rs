@@ -0,0 +1,234 @@
+mod caddy;
+mod nginx;
+mod uptime_kuma;
+mod urls;
+
+use crate::config::Config;
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::io::{Read, Write as _};
+use std::path::{Path, PathBuf};
+use toml::Value;
+
+/// Configurations `monitor-tui import` can convert
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportFormat {
+    Urls,
+    UptimeKuma,
+    Caddy,
+    Nginx,
+}
+
+impl ImportFormat {
+    pub const NAMES: &'static str = "urls, uptime-kuma, caddy, nginx";
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "urls" | "csv" => Some(ImportFormat::Urls),
+            "uptime-kuma" => Some(ImportFormat::UptimeKuma),
+            "caddy" => Some(ImportFormat::Caddy),
+            "nginx" => Some(ImportFormat::Nginx),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ImportFormat::Urls => "URL list",
+            ImportFormat::UptimeKuma => "Uptime Kuma",
+            ImportFormat::Caddy => "Caddyfile",
+            ImportFormat::Nginx => "nginx config",
+        }
+    }
+}
+
+/// Options of the `import` subcommand
+#[derive(Debug)]
+pub struct ImportArgs {
+    pub format: ImportFormat,
+    /// File to convert; `-` reads standard input
+    pub file: PathBuf,
+    /// Add the sites to the config file instead of printing them
+    pub append: bool,
+}
+
+/// A site converted from another tool, ready to print as a `[[sites]]` entry
+#[derive(Debug, Default)]
+pub struct ImportedSite {
+    pub name: String,
+    /// Site keys after `name`, in output order
+    fields: Vec<(&'static str, Value)>,
+    /// `[sites.alerts]` consecutive_failures
+    pub consecutive_failures: Option<usize>,
+    /// Settings that didn't carry over, printed as comments
+    pub notes: Vec<String>,
+}
+
+impl ImportedSite {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn set(&mut self, key: &'static str, value: impl Into<Value>) {
+        self.fields.push((key, value.into()));
+    }
+
+    fn url(&self) -> Option<&str> {
+        self.fields.iter().find(|(key, _)| *key == "url").and_then(|(_, value)| value.as_str())
+    }
+}
+
+/// A virtual host of a web server config, as the URL to check
+#[derive(Debug)]
+pub struct VirtualHost {
+    pub scheme: String,
+    pub host: String,
+    pub port: Option<u16>,
+    pub path: String,
+}
+
+impl VirtualHost {
+    pub fn new(scheme: &str, host: &str, port: Option<u16>, path: &str) -> Self {
+        // Leave out the scheme's own port
+        let port = port.filter(|&p| !matches!((scheme, p), ("http", 80) | ("https", 443)));
+        Self {
+            scheme: scheme.to_string(),
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        }
+    }
+
+    fn authority(&self) -> String {
+        match self.port {
+            Some(port) => format!("{}:{}", self.host, port),
+            None => self.host.clone(),
+        }
+    }
+
+    /// A site named after the host, listing the names it also answers to
+    pub fn to_site(&self, aliases: &[VirtualHost]) -> ImportedSite {
+        let mut site = ImportedSite::new(self.authority());
+        site.set("url", format!("{}://{}{}", self.scheme, self.authority(), self.path));
+        if !aliases.is_empty() {
+            let names: Vec<String> = aliases.iter().map(VirtualHost::authority).collect();
+            site.notes.push(format!("also serves: {}", names.join(", ")));
+        }
+        site
+    }
+}
+
+/// Convert the file and print the sites as TOML to add to sites.toml, or
+/// append them to the config file
+pub fn run(args: &ImportArgs) -> Result<()> {
+    let path = &args.file;
+    let stdin = path == Path::new("-");
+    let source = if stdin { "standard input".to_string() } else { path.display().to_string() };
+    let contents = if stdin {
+        let mut contents = String::new();
+        std::io::stdin()
+            .read_to_string(&mut contents)
+            .context("Failed to read standard input")?;
+        contents
+    } else {
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?
+    };
+    let (mut sites, mut skipped) = match args.format {
+        ImportFormat::Urls => urls::convert(&contents)?,
+        ImportFormat::UptimeKuma => uptime_kuma::convert(&contents)?,
+        ImportFormat::Caddy => caddy::convert(&contents)?,
+        ImportFormat::Nginx => nginx::convert(&contents)?,
+    };
+
+    let summary = |sites: usize, skipped: usize| {
+        if skipped == 0 { format!("{} site(s)", sites) } else { format!("{} site(s), skipped {}", sites, skipped) }
+    };
+
+    if !args.append {
+        dedupe_names(&mut sites, HashSet::new());
+        print!("{}", render(args.format, &source, &sites, &skipped));
+        eprintln!("Imported {} from {}", summary(sites.len(), skipped.len()), source);
+        return Ok(());
+    }
+
+    let config_path = Config::find_path()?;
+    let existing = std::fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
+    let config = Config::parse(&existing, &config_path)?;
+
+    // Leave out what the config already checks
+    let normalize = |url: &str| url::Url::parse(url).map_or_else(|_| url.to_string(), String::from);
+    let monitored: HashMap<String, &str> = config.sites.iter().map(|s| (normalize(&s.url), s.name.as_str())).collect();
+    sites.retain(|site| match site.url().and_then(|url| monitored.get(&normalize(url))) {
+        Some(name) => {
+            skipped.push(format!("'{}': already monitored as '{}'", site.name, name));
+            false
+        }
+        None => true,
+    });
+    if sites.is_empty() {
+        eprintln!("Nothing to append from {}: {}", source, summary(0, skipped.len()));
+        for reason in &skipped {
+            eprintln!("  skipped {}", reason);
+        }
+        return Ok(());
+    }
+    dedupe_names(&mut sites, config.sites.iter().map(|s| s.name.clone()).collect());
+
+    // Only write a config that still loads
+    let separator = if existing.is_empty() || existing.ends_with('\n') { "\n" } else { "\n\n" };
+    let addition = format!("{}{}", separator, render(args.format, &source, &sites, &skipped));
+    Config::parse(&format!("{}{}", existing, addition), &config_path)
+        .context("The imported sites would make the config invalid; nothing was written")?;
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(&config_path)
+        .and_then(|mut file| file.write_all(addition.as_bytes()))
+        .with_context(|| format!("Failed to write config file: {}", config_path.display()))?;
+
+    eprintln!(
+        "Appended {} from {} to {}",
+        summary(sites.len(), skipped.len()),
+        source,
+        config_path.display()
+    );
+    Ok(())
+}
+
+/// Site names must be unique, also against `seen`; number any repeats
+fn dedupe_names(sites: &mut [ImportedSite], mut seen: HashSet<String>) {
+    for site in sites {
+        let base = site.name.clone();
+        let mut n = 2;
+        while !seen.insert(site.name.clone()) {
+            site.name = format!("{} ({})", base, n);
+            n += 1;
+        }
+    }
+}
+
+fn render(format: ImportFormat, source: &str, sites: &[ImportedSite], skipped: &[String]) -> String {
+    let mut out = format!("# Imported from {} ({})\n", format.label(), source);
+    for reason in skipped {
+        let _ = writeln!(out, "# Skipped {}", reason);
+    }
+
+    for site in sites {
+        out.push_str("\n[[sites]]\n");
+        let _ = writeln!(out, "name = {}", Value::from(site.name.as_str()));
+        for (key, value) in &site.fields {
+            let _ = writeln!(out, "{} = {}", key, value);
+        }
+        for note in &site.notes {
+            let _ = writeln!(out, "# {}", note);
+        }
+        if let Some(failures) = site.consecutive_failures {
+            let _ = writeln!(out, "\n[sites.alerts]\nconsecutive_failures = {}", failures);
+        }
+    }
+    out
+}
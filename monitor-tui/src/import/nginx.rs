@@ -0,0 +1,217 @@
+use super::{ImportedSite, VirtualHost};
+use anyhow::{Result, bail};
+
+/// Convert each `server` block of an nginx config to a site, returning them
+/// and why any blocks were skipped
+pub fn convert(contents: &str) -> Result<(Vec<ImportedSite>, Vec<String>)> {
+    let mut sites = Vec::new();
+    let mut skipped = Vec::new();
+
+    for server in server_blocks(&tokenize(contents))? {
+        let label = server.names.first().map_or("server without a server_name", String::as_str);
+        if let Some(code) = &server.returns {
+            skipped.push(format!("'{}': only returns {}", label, code));
+            continue;
+        }
+        let Some(listen) = server.listen() else {
+            skipped.push(format!("'{}': only listens on a unix socket", label));
+            continue;
+        };
+
+        let mut hosts = Vec::new();
+        for name in server.names.iter().filter(|name| !name.is_empty() && *name != "_") {
+            match check_name(name) {
+                Ok(host) => hosts.push(VirtualHost::new(listen.scheme(), host, Some(listen.port), "/")),
+                Err(reason) => skipped.push(format!("'{}': {}", name, reason)),
+            }
+        }
+        // Catch-all and unnamed servers answer on their listen address
+        if server.names.iter().all(|name| name.is_empty() || name == "_") {
+            let host = listen.address.as_deref().unwrap_or("localhost");
+            hosts.push(VirtualHost::new(listen.scheme(), host, Some(listen.port), "/"));
+        }
+
+        if let Some((first, aliases)) = hosts.split_first() {
+            sites.push(first.to_site(aliases));
+        }
+    }
+
+    Ok((sites, skipped))
+}
+
+/// Split a config into words and the `;`, `{` and `}` that end statements
+fn tokenize(contents: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = contents.chars();
+    let mut word = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '#' => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' | '\'' => {
+                for next in chars.by_ref() {
+                    if next == c {
+                        break;
+                    }
+                    word.push(next);
+                }
+                continue;
+            }
+            ';' | '{' | '}' => {}
+            c if c.is_whitespace() => {}
+            c => {
+                word.push(c);
+                continue;
+            }
+        }
+        if !word.is_empty() {
+            tokens.push(std::mem::take(&mut word));
+        }
+        if matches!(c, ';' | '{' | '}') {
+            tokens.push(c.to_string());
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(word);
+    }
+    tokens
+}
+
+/// What one `server` block serves
+#[derive(Default)]
+struct Server {
+    names: Vec<String>,
+    listens: Vec<Listen>,
+    /// `ssl on;`, from before listen took an ssl flag
+    ssl: bool,
+    /// Has a `listen unix:...`, which checks can't reach
+    unix: bool,
+    /// Status code of a server-level `return`, which answers every request
+    returns: Option<String>,
+}
+
+struct Listen {
+    address: Option<String>,
+    port: u16,
+    ssl: bool,
+}
+
+impl Listen {
+    fn scheme(&self) -> &'static str {
+        if self.ssl { "https" } else { "http" }
+    }
+}
+
+impl Server {
+    /// The listener to check: HTTPS when there is one; nginx listens on 80 by default
+    fn listen(&self) -> Option<Listen> {
+        if self.listens.is_empty() {
+            if self.unix {
+                return None;
+            }
+            return Some(Listen { address: None, port: 80, ssl: self.ssl });
+        }
+        self.listens
+            .iter()
+            .max_by_key(|listen| listen.ssl || self.ssl)
+            .map(|listen| Listen {
+                address: listen.address.clone(),
+                port: listen.port,
+                ssl: listen.ssl || self.ssl,
+            })
+    }
+}
+
+/// Collect the HTTP `server` blocks, at the top level (a sites-enabled file)
+/// or inside `http`
+fn server_blocks(tokens: &[String]) -> Result<Vec<Server>> {
+    let mut servers = Vec::new();
+    let mut blocks: Vec<String> = Vec::new();
+    let mut statement: Vec<&str> = Vec::new();
+    let mut current: Option<Server> = None;
+
+    for token in tokens {
+        match token.as_str() {
+            "{" => {
+                let name = statement.first().copied().unwrap_or_default();
+                if name == "server" && blocks.iter().all(|block| block == "http") {
+                    current = Some(Server::default());
+                }
+                blocks.push(name.to_string());
+                statement.clear();
+            }
+            "}" => {
+                if blocks.pop().is_none() {
+                    bail!("Unbalanced '}}' in nginx config");
+                }
+                if blocks.iter().all(|block| block == "http")
+                    && let Some(server) = current.take()
+                {
+                    servers.push(server);
+                }
+                statement.clear();
+            }
+            ";" => {
+                if blocks.last().is_some_and(|block| block == "server")
+                    && let Some(server) = current.as_mut()
+                {
+                    record(server, &statement);
+                }
+                statement.clear();
+            }
+            word => statement.push(word),
+        }
+    }
+
+    if !blocks.is_empty() {
+        bail!("Unclosed '{}' block in nginx config", blocks.join(" > "));
+    }
+    Ok(servers)
+}
+
+/// Note a server-level directive that decides what the server answers to
+fn record(server: &mut Server, statement: &[&str]) {
+    match statement {
+        ["server_name", names @ ..] => server.names.extend(names.iter().map(|n| n.to_string())),
+        ["listen", address, flags @ ..] => {
+            if address.starts_with("unix:") {
+                server.unix = true;
+                return;
+            }
+            let (host, port) = match address.rsplit_once(':') {
+                Some((host, port)) if !port.contains(']') => (Some(host), port),
+                _ if address.parse::<u16>().is_ok() => (None, *address),
+                _ => (Some(*address), "80"),
+            };
+            let Ok(port) = port.parse() else {
+                return;
+            };
+            // Wildcard addresses mean "any interface": check via localhost
+            let address = host
+                .filter(|h| !matches!(*h, "*" | "0.0.0.0" | "[::]"))
+                .map(str::to_string);
+            let ssl = flags.iter().any(|f| matches!(*f, "ssl" | "quic"));
+            server.listens.push(Listen { address, port, ssl });
+        }
+        ["ssl", "on"] => server.ssl = true,
+        ["return", code, ..] if code.parse::<u16>().is_ok() => server.returns = Some(code.to_string()),
+        _ => {}
+    }
+}
+
+/// A server_name that can be checked, as a host name
+fn check_name(name: &str) -> Result<&str, String> {
+    match name {
+        _ if name.starts_with('~') => Err("regex names can't be checked".to_string()),
+        _ if name.contains('*') => Err("wildcard names can't be checked".to_string()),
+        _ if name.contains('$') => Err("names with variables aren't supported".to_string()),
+        // ".example.com" matches example.com and its subdomains
+        _ => Ok(name.trim_start_matches('.')),
+    }
+}
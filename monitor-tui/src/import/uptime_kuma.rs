@@ -0,0 +1,197 @@
+use super::ImportedSite;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// The parts of an Uptime Kuma backup ("Settings > Backup > Export") we use
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Backup {
+    #[serde(default)]
+    monitor_list: Vec<Monitor>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Monitor {
+    id: Option<i64>,
+    name: String,
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    method: Option<String>,
+    /// JSON object of header names to values, as text
+    #[serde(default)]
+    headers: Option<String>,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    interval: Option<i64>,
+    #[serde(default)]
+    timeout: Option<f64>,
+    #[serde(default)]
+    maxretries: Option<i64>,
+    #[serde(default)]
+    accepted_statuscodes: Vec<String>,
+    #[serde(default)]
+    keyword: Option<String>,
+    #[serde(default)]
+    invert_keyword: serde_json::Value,
+    #[serde(default)]
+    json_path: Option<String>,
+    #[serde(default)]
+    expected_value: Option<String>,
+    #[serde(default)]
+    push_token: Option<String>,
+    #[serde(default)]
+    parent: Option<i64>,
+    #[serde(default)]
+    tags: Vec<Tag>,
+    #[serde(default = "default_active")]
+    active: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct Tag {
+    name: String,
+}
+
+fn default_active() -> serde_json::Value {
+    serde_json::Value::Bool(true)
+}
+
+/// Kuma exports flags as booleans or, straight from SQLite, as 0/1
+fn is_true(value: &serde_json::Value) -> bool {
+    value.as_bool().or_else(|| value.as_i64().map(|n| n != 0)).unwrap_or(false)
+}
+
+/// Convert the backup's monitors to sites, returning them and why any were skipped
+pub fn convert(contents: &str) -> Result<(Vec<ImportedSite>, Vec<String>)> {
+    let backup: Backup = serde_json::from_str(contents).context("Not an Uptime Kuma backup file")?;
+
+    let mut sites = Vec::new();
+    let mut skipped = Vec::new();
+    let mut imported_ids = HashMap::new();
+
+    for monitor in backup.monitor_list.iter().filter(|m| m.kind != "group") {
+        match convert_monitor(monitor) {
+            Ok(site) => {
+                if let Some(id) = monitor.id {
+                    imported_ids.insert(id, monitor.name.clone());
+                }
+                sites.push(site);
+            }
+            Err(reason) => skipped.push(format!("'{}': {}", monitor.name, reason)),
+        }
+    }
+
+    // Groups become composites of whichever children came across
+    for group in backup.monitor_list.iter().filter(|m| m.kind == "group") {
+        let members: Vec<&str> = backup
+            .monitor_list
+            .iter()
+            .filter(|m| m.parent.is_some() && m.parent == group.id)
+            .filter_map(|m| m.id.and_then(|id| imported_ids.get(&id)).map(String::as_str))
+            .collect();
+        if members.is_empty() {
+            skipped.push(format!("group '{}': none of its monitors could be imported", group.name));
+            continue;
+        }
+        let mut site = ImportedSite::new(&group.name);
+        site.set("kind", "composite");
+        site.set("members", members);
+        sites.push(site);
+    }
+
+    Ok((sites, skipped))
+}
+
+fn convert_monitor(monitor: &Monitor) -> Result<ImportedSite, String> {
+    let mut site = ImportedSite::new(&monitor.name);
+
+    match monitor.kind.as_str() {
+        "http" | "keyword" | "json-query" => {
+            let url = monitor.url.as_deref().filter(|u| !u.is_empty()).ok_or("no URL")?;
+            site.set("url", url);
+        }
+        "push" => {
+            site.set("kind", "heartbeat");
+            // Kuma marks a push monitor down after one silent interval
+            site.set("grace_period", monitor.interval.unwrap_or(60));
+            if let Some(token) = monitor.push_token.as_deref().filter(|t| !t.is_empty()) {
+                site.set("heartbeat_token", token);
+            }
+        }
+        other => return Err(format!("{} monitors aren't supported", other)),
+    }
+
+    if monitor.kind != "push"
+        && let Some(interval) = monitor.interval.filter(|&i| i > 0)
+    {
+        site.set("check_interval", interval);
+    }
+    if let Some(timeout) = monitor.timeout.filter(|&t| t > 0.0) {
+        site.set("request_timeout", timeout.ceil() as i64);
+    }
+    let codes = &monitor.accepted_statuscodes;
+    match codes.as_slice() {
+        [code] if code.parse::<i64>().is_ok() => site.set("expected_status", code.parse::<i64>().unwrap_or_default()),
+        [] => {}
+        [range] if range == "200-299" => {}
+        _ => site.notes.push(format!("accepted status codes: {} (expects 200 here)", codes.join(", "))),
+    }
+    if !monitor.tags.is_empty() {
+        site.set("tags", monitor.tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>());
+    }
+    if let Some(retries) = monitor.maxretries.filter(|&r| r > 0) {
+        site.consecutive_failures = Some(retries as usize + 1);
+    }
+
+    if monitor.kind != "push" {
+        if let Some(method) = monitor.method.as_deref().filter(|m| !m.eq_ignore_ascii_case("get")) {
+            site.set("method", method.to_uppercase());
+        }
+        if let Some(headers) = monitor.headers.as_deref().filter(|h| !h.trim().is_empty()) {
+            match serde_json::from_str::<HashMap<String, String>>(headers) {
+                Ok(headers) if !headers.is_empty() => {
+                    let mut table = toml::Table::new();
+                    for (name, value) in headers {
+                        table.insert(name, value.into());
+                    }
+                    site.set("headers", table);
+                }
+                Ok(_) => {}
+                Err(_) => site.notes.push(format!("headers: {} (not a JSON object of strings)", headers)),
+            }
+        }
+        if let Some(body) = monitor.body.as_deref().filter(|b| !b.is_empty()) {
+            site.set("body", body);
+        }
+    }
+
+    if monitor.kind == "keyword"
+        && let Some(keyword) = monitor.keyword.as_deref().filter(|k| !k.is_empty())
+    {
+        if is_true(&monitor.invert_keyword) {
+            site.notes.push(format!("keyword check: body must not contain {:?}", keyword));
+        } else {
+            site.set("expected_body_contains", keyword);
+        }
+    }
+
+    // Not expressible in a site yet: keep them visible for the user
+    if monitor.kind == "json-query" {
+        site.notes.push(format!(
+            "JSON query: {} == {}",
+            monitor.json_path.as_deref().unwrap_or("?"),
+            monitor.expected_value.as_deref().unwrap_or("?")
+        ));
+    }
+    if !is_true(&monitor.active) {
+        site.notes.push("paused in Uptime Kuma".to_string());
+    }
+
+    Ok(site)
+}
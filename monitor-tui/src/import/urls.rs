@@ -0,0 +1,106 @@
+use super::ImportedSite;
+use anyhow::{Context, Result};
+use url::Url;
+
+/// Columns of a CSV row, by position
+struct Columns {
+    name: Option<usize>,
+    url: usize,
+    status: Option<usize>,
+}
+
+/// Without a header row: a bare URL, or name,url[,status]
+const DEFAULT_COLUMNS: Columns = Columns { name: Some(0), url: 1, status: Some(2) };
+
+/// Convert a list of URLs, one per line, or CSV rows of name,url,status
+/// (with or without a header row naming those columns)
+pub fn convert(contents: &str) -> Result<(Vec<ImportedSite>, Vec<String>)> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .comment(Some(b'#'))
+        .trim(csv::Trim::All)
+        .from_reader(contents.as_bytes());
+
+    let mut sites = Vec::new();
+    let mut skipped = Vec::new();
+    let mut columns = None;
+
+    for record in reader.records() {
+        let record = record.context("Failed to read URL list")?;
+        let line = record.position().map_or(0, |p| p.line());
+        let fields: Vec<&str> = record.iter().collect();
+        if fields.iter().all(|f| f.is_empty()) {
+            continue;
+        }
+
+        if columns.is_none()
+            && let Some(header) = header_columns(&fields)
+        {
+            columns = Some(header);
+            continue;
+        }
+        let columns = columns.get_or_insert(DEFAULT_COLUMNS);
+
+        match convert_row(&fields, columns) {
+            Ok(site) => sites.push(site),
+            Err(reason) => skipped.push(format!("line {}: {}", line, reason)),
+        }
+    }
+
+    Ok((sites, skipped))
+}
+
+/// Column positions from a header row, if this row is one
+fn header_columns(fields: &[&str]) -> Option<Columns> {
+    let find = |names: &[&str]| fields.iter().position(|f| names.iter().any(|n| f.eq_ignore_ascii_case(n)));
+    Some(Columns {
+        url: find(&["url"])?,
+        name: find(&["name"]),
+        status: find(&["status", "expected_status"]),
+    })
+}
+
+fn convert_row(fields: &[&str], columns: &Columns) -> Result<ImportedSite, String> {
+    let field = |index: Option<usize>| index.and_then(|i| fields.get(i)).copied().filter(|f| !f.is_empty());
+    let (name, url, status) = match fields {
+        // A plain list: just the URL
+        [url] => (None, *url, None),
+        _ => (field(columns.name), field(Some(columns.url)).ok_or("no URL")?, field(columns.status)),
+    };
+
+    let url = parse_url(url)?;
+    let mut site = ImportedSite::new(name.map_or_else(|| default_name(&url), str::to_string));
+    site.set("url", url.as_str());
+    if let Some(status) = status {
+        match status.parse::<u16>() {
+            Ok(code @ 100..=599) => {
+                if code != 200 {
+                    site.set("expected_status", code as i64);
+                }
+            }
+            _ => return Err(format!("invalid status '{}'", status)),
+        }
+    }
+    Ok(site)
+}
+
+/// An http(s) URL; a bare host name is taken as https
+fn parse_url(text: &str) -> Result<Url, String> {
+    let text = if text.contains("://") { text.to_string() } else { format!("https://{}", text) };
+    let url = Url::parse(&text).map_err(|e| format!("invalid URL '{}': {}", text, e))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err(format!("'{}' is not an http(s) URL", text));
+    }
+    Ok(url)
+}
+
+/// Name a site after its host, port and path
+fn default_name(url: &Url) -> String {
+    let mut name = url.host_str().unwrap_or_default().to_string();
+    if let Some(port) = url.port() {
+        name = format!("{}:{}", name, port);
+    }
+    let path = url.path().trim_end_matches('/');
+    format!("{}{}", name, path)
+}
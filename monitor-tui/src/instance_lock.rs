@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+
+/// Advisory lock preventing two instances from monitoring the same config.
+///
+/// The OS releases the lock when the holder exits (even on a crash), so a
+/// leftover lock file never blocks a fresh start.
+pub struct InstanceLock {
+    _file: File,
+}
+
+impl InstanceLock {
+    /// Acquire the lock for `config_path`, or explain who holds it
+    pub fn acquire(config_path: &Path) -> Result<Self> {
+        let lock_path = Self::lock_path(config_path);
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+            .with_context(|| format!("Failed to open lock file: {}", lock_path.display()))?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut holder = String::new();
+                let _ = file.read_to_string(&mut holder);
+                anyhow::bail!(Self::already_running_message(holder.trim(), config_path));
+            }
+            Err(TryLockError::Error(e)) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to lock: {}", lock_path.display()));
+            }
+        }
+
+        // Record our PID so a second instance can point the user at us
+        file.set_len(0)?;
+        file.rewind()?;
+        write!(file, "{}", std::process::id())?;
+        file.flush()?;
+
+        Ok(Self { _file: file })
+    }
+
+    /// One lock file per config file, keyed by its canonical path
+    fn lock_path(config_path: &Path) -> PathBuf {
+        runtime_path(config_path, "lock")
+    }
+
+    fn already_running_message(pid: &str, config_path: &Path) -> String {
+        let pid = if pid.is_empty() { "unknown" } else { pid };
+
+        // Best effort: name the terminal the other instance is attached to
+        let tty = std::fs::read_link(format!("/proc/{}/fd/0", pid))
+            .ok()
+            .map(|p| format!(" on {}", p.display()))
+            .unwrap_or_default();
+
+        format!(
+            "Another monitor-tui instance (PID {}{}) is already monitoring {}.\n\
+             Switch to that terminal, or run with --force to start anyway.",
+            pid,
+            tty,
+            config_path.display()
+        )
+    }
+}
+
+/// Path in the runtime directory for a file belonging to the instance that
+/// monitors `config_path`, e.g. its lock or control socket. The name also
+/// keys files kept across upgrades, like the history file, so it's a hash
+/// that never changes between builds.
+pub fn runtime_path(config_path: &Path, extension: &str) -> PathBuf {
+    let canonical = config_path
+        .canonicalize()
+        .unwrap_or_else(|_| config_path.to_path_buf());
+    let hash = fnv1a(canonical.as_os_str().as_encoded_bytes());
+
+    let dir = std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir);
+    dir.join(format!("monitor-tui-{:016x}.{}", hash, extension))
+}
+
+/// 64-bit FNV-1a, unlike std's hashers specified to stay the same
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
@@ -1,25 +1,86 @@
 mod alerts;
 mod app;
 mod checker;
+mod cli;
+mod clipboard;
 mod config;
+mod config_watcher;
+mod control;
+mod discovery;
+mod export;
 mod history;
+mod i18n;
+mod http_server;
+mod import;
+mod instance_lock;
 mod metrics_poller;
+mod monitor_health;
+mod palette;
+mod mqtt;
+mod preferences;
+mod report;
+mod result_log;
+mod score;
+mod slo;
+mod stats;
+mod systemd;
 mod ui;
+mod wizard;
 
-use alerts::AlertNotifier;
+use alerts::{AlertNotifier, NotificationStats};
 use app::{App, AppAction, View};
-use checker::spawn_checker_task;
-use config::Config;
+use checker::{
+    demo::DemoChecker,
+    graphql::GraphqlChecker,
+    heartbeat::{HeartbeatChecker, HeartbeatRegistry},
+    ntp::NtpChecker,
+    ports::PortChecker,
+    http::{HttpChecker, HttpClients, DEFAULT_USER_AGENT},
+    xml,
+    spawn_checker_task, CheckEvent, CheckResult, CheckSchedule, Checker, QueueStats, ResultSender, TaskSignals,
+};
+use cli::Cli;
+use config::{Config, SiteConfig, SiteKind};
+use control::{spawn_control_socket, ControlRequest};
+use discovery::{spawn_discovery, SiteChange};
+use history::HistoryStore;
+use http_server::spawn_http_server;
+use instance_lock::InstanceLock;
 use metrics_poller::{spawn_metrics_task, MetricsPoll};
+use monitor_health::MonitorHealth;
+use mqtt::MqttPublisher;
+use systemd::SystemdNotifier;
+use result_log::ResultLog;
+use ui::graphics::{ChartPainter, Protocol};
+use ui::title::TerminalTitle;
+use anyhow::Context;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
+    event::{DisableMouseCapture, EnableMouseCapture, Event, EventStream},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use chrono::{DateTime, Local};
+use futures::StreamExt;
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io::stdout;
-use std::time::Duration;
+use std::collections::HashMap;
+use std::io::{stdout, IsTerminal, Stdout};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, mpsc, watch};
+use tokio::time::MissedTickBehavior;
+
+/// Longest to wait for background tasks after quitting
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Most check results handled between two frames
+const MAX_RESULTS_PER_FRAME: usize = 256;
+
+/// Repaint at least this often even when idle, to keep clocks and ages current
+const IDLE_REPAINT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Shortest time between restarts of a site's checker task, so one that keeps
+/// failing doesn't spin
+const CHECKER_RESTART_DELAY: Duration = Duration::from_secs(10);
 
 /// RAII guard to ensure terminal is properly restored on drop
 struct TerminalCleanup;
@@ -39,42 +100,159 @@ impl Drop for TerminalCleanup {
     }
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    // Load configuration
-    let config = Config::load()?;
+fn main() -> anyhow::Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    let result = runtime.block_on(run());
+    // Don't wait forever on blocking work that ignored cancellation (e.g. a hung
+    // desktop notification)
+    runtime.shutdown_timeout(SHUTDOWN_TIMEOUT);
+    result
+}
+
+async fn run() -> anyhow::Result<()> {
+    let cli = Cli::parse()?;
+    if let Some(args) = &cli.import {
+        return import::run(args);
+    }
+    if let Some(command) = &cli.ctl {
+        return control::run(command);
+    }
+    if let Some(format) = cli.status {
+        return control::status(format);
+    }
+    if cli.strings {
+        print!("{}", i18n::template());
+        return Ok(());
+    }
+
+    // Load configuration, shared read-only by the UI, detector and notifier.
+    // On a first run in a terminal, offer to write one instead of bailing.
+    let interactive = !cli.headless && std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+    let path = match Config::find_path() {
+        Ok(path) => path,
+        Err(e) if interactive => wizard::run().await?.ok_or(e)?,
+        Err(e) => return Err(e),
+    };
+    let mut config = Config::load_from(&path)?;
+    i18n::init(&config)?;
+    if cli.demo_sites > 0 {
+        config.add_demo_sites(cli.demo_sites);
+    }
+    let config = Arc::new(config);
 
-    // Create channels for communication
-    let (tx, mut rx) = mpsc::channel(100);
+    // Refuse to double-check and double-alert alongside another instance
+    let _instance_lock = if config.settings.single_instance && !cli.force && !cli.demo {
+        Some(InstanceLock::acquire(&config.source_path)?)
+    } else {
+        None
+    };
+
+    // Create channels for communication; by default size the result channel so a
+    // round of checks from a large fleet doesn't stall the checker tasks
+    let capacity = config
+        .settings
+        .result_channel_capacity
+        .unwrap_or_else(|| config.sites.len().clamp(100, 4096));
+    let (tx, mut rx) = mpsc::channel(capacity);
+    let queue_stats = Arc::new(QueueStats::default());
+    let tx = ResultSender::new(tx, config.settings.result_overflow, Arc::clone(&queue_stats));
     let (shutdown_tx, shutdown_rx) = watch::channel(false);
     let (force_refresh_tx, _) = broadcast::channel(16);
 
+    // Create alert notifier
+    let notification_stats = Arc::new(NotificationStats::default());
+    let mut alert_notifier = AlertNotifier::new(Arc::clone(&config), Arc::clone(&notification_stats));
+
     // Initialize app state with force refresh sender
-    let mut app = App::new(config.clone(), force_refresh_tx.clone());
+    let health = Arc::new(MonitorHealth::new(
+        config.source_path.clone(),
+        Arc::clone(&queue_stats),
+        Arc::clone(&notification_stats),
+    ));
+    let mut app = App::new(Arc::clone(&config), force_refresh_tx.clone(), queue_stats, notification_stats);
 
-    // Create alert notifier
-    let alert_notifier = AlertNotifier::new(config.clone());
-
-    // Spawn health checker tasks
-    let mut tasks = Vec::new();
-    for site in config.sites.clone() {
-        let handle = spawn_checker_task(
-            site,
-            tx.clone(),
-            shutdown_rx.clone(),
-            force_refresh_tx.subscribe(),
-            config.settings.request_timeout,
-            config.settings.refresh_interval,
-            config.settings.warning_threshold_ms,
-        );
-        tasks.push(handle);
+    // Headless, what the TUI would show is logged instead
+    if cli.headless {
+        let log = match &cli.log_file {
+            Some(path) => ResultLog::open(path)?,
+            None => ResultLog::stdout(),
+        };
+        app.set_result_log(log);
     }
 
-    // Conditionally spawn metrics poller if configured
+    // Check results from earlier sessions; without them the TUI starts empty as before
+    if config.settings.persist_history && !cli.demo {
+        let path = config
+            .settings
+            .history_file
+            .clone()
+            .or_else(|| HistoryStore::default_path(&config.source_path));
+        if let Some(path) = path {
+            match app.restore_history(&path) {
+                Ok(0) => {}
+                Ok(restored) => app.set_error(format!("Restored {} checks from {}", restored, path.display())),
+                Err(e) => app.set_error(format!("Check history unavailable: {:#}", e)),
+            }
+        }
+    }
+
+    // Embedded HTTP server for heartbeat pings (demo mode stays offline)
+    let heartbeats = HeartbeatRegistry::new(&config);
+    let mut http_server_task: Option<tokio::task::JoinHandle<()>> = None;
+
+    if let Some(server_config) = config.http_server.clone().filter(|_| !cli.demo) {
+        let handle = spawn_http_server(server_config, heartbeats.clone(), Arc::clone(&health), shutdown_rx.clone()).await?;
+        http_server_task = Some(handle);
+    }
+
+    // Commands from `monitor-tui ctl`; without the socket the TUI works as before
+    let mut control_rx: Option<mpsc::Receiver<ControlRequest>> = None;
+    let mut control_task: Option<tokio::task::JoinHandle<()>> = None;
+
+    if config.settings.control_socket && !cli.demo {
+        let (requests_tx, rx) = mpsc::channel(16);
+        match spawn_control_socket(&config.source_path, requests_tx, shutdown_rx.clone()) {
+            Ok(handle) => {
+                control_rx = Some(rx);
+                control_task = Some(handle);
+            }
+            Err(e) => app.set_error(format!("Control socket unavailable: {:#}", e)),
+        }
+    }
+
+    // Spawn health checker tasks, sharing HTTP clients across sites. The
+    // spawner keeps a sender, so sites added later report on the same channel.
+    let spawner = TaskSpawner {
+        tx,
+        shutdown: shutdown_rx.clone(),
+        force_refresh: force_refresh_tx.clone(),
+        health: Arc::clone(&health),
+    };
+    let mut http_clients = HttpClients::new(Arc::clone(&config));
+    let checkers = build_checkers(&config, cli.demo, &heartbeats, &mut http_clients)?;
+    let mut site_tasks = spawner.spawn_configured(&mut app, &config, checkers);
+    // When each site's checker task was last restarted after stopping
+    let mut restarted: HashMap<String, Instant> = HashMap::new();
+
+    // Edits to the config file are applied as they're saved
+    let mut config_changes_rx: Option<mpsc::Receiver<()>> = None;
+    let mut _config_watcher = None;
+
+    if config.settings.watch_config {
+        match config_watcher::watch(&config.source_path) {
+            Ok((watcher, rx)) => {
+                config_changes_rx = Some(rx);
+                _config_watcher = Some(watcher);
+            }
+            Err(e) => app.set_error(format!("Config changes won't be picked up: {:#}", e)),
+        }
+    }
+
+    // Conditionally spawn metrics poller if configured (demo mode stays offline)
     let mut metrics_rx: Option<mpsc::Receiver<MetricsPoll>> = None;
     let mut metrics_task: Option<tokio::task::JoinHandle<()>> = None;
 
-    if let Some(metrics_config) = config.server_metrics.clone() {
+    if let Some(metrics_config) = config.server_metrics.clone().filter(|_| !cli.demo) {
         let (metrics_tx, rx) = mpsc::channel(100);
         let handle = spawn_metrics_task(
             metrics_config,
@@ -86,79 +264,564 @@ async fn main() -> anyhow::Result<()> {
         metrics_task = Some(handle);
     }
 
-    // Drop original tx so channel closes when all tasks finish
-    drop(tx);
+    // Optionally publish site state to MQTT (demo mode stays offline)
+    let mut mqtt_publisher: Option<MqttPublisher> = None;
+    let mut mqtt_task: Option<tokio::task::JoinHandle<()>> = None;
 
-    // Set up terminal
-    let _cleanup = TerminalCleanup::new()?;
-    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
-    terminal.clear()?;
+    if let Some(mqtt_config) = config.mqtt.clone().filter(|_| !cli.demo) {
+        let (publisher, handle) = MqttPublisher::spawn(&config, mqtt_config, shutdown_rx.clone());
+        mqtt_publisher = Some(publisher);
+        mqtt_task = Some(handle);
+    }
 
-    // Track frame size for mouse click calculations
-    let mut last_frame_size = terminal.size()?;
+    // Discovered sites get checker tasks as they appear (demo mode stays offline)
+    let mut site_changes_rx: Option<mpsc::Receiver<SiteChange>> = None;
+    let mut discovery_tasks = Vec::new();
+    let mut discovered_tasks: HashMap<String, tokio::task::JoinHandle<()>> = HashMap::new();
 
-    // Main event loop
+    if config.discovers_sites() && !cli.demo {
+        let (changes_tx, rx) = mpsc::channel(100);
+        discovery_tasks = spawn_discovery(&config, &changes_tx, &shutdown_rx);
+        site_changes_rx = Some(rx);
+    }
+
+    // Set up terminal, unless running headless
+    let (_cleanup, mut terminal) = if cli.headless {
+        (None, None)
+    } else {
+        let cleanup = TerminalCleanup::new()?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+        terminal.clear()?;
+        (Some(cleanup), Some(terminal))
+    };
+
+    // Image charts on terminals that support them
+    let mut chart_painter = if cli.headless {
+        None
+    } else {
+        Protocol::resolve(config.settings.chart_graphics).map(ChartPainter::new)
+    };
+    app.chart_graphics.set(chart_painter.is_some());
+
+    // Fleet summary in the window title, for watching from a tab bar
+    let mut terminal_title = if config.settings.terminal_title && !cli.headless {
+        Some(TerminalTitle::new()?)
+    } else {
+        None
+    };
+
+    // Let systemd know we're up (no-op outside a Type=notify unit)
+    let mut systemd = SystemdNotifier::from_env();
+    systemd.ready();
+
+    // The loop only wakes when something changed; the tick keeps clocks and ages
+    // current and must also be frequent enough to feed a short watchdog
+    let mut events = (!cli.headless).then(EventStream::new);
+    let tick_interval = systemd
+        .watchdog_interval()
+        .map_or(IDLE_REPAINT_INTERVAL, |w| w.min(IDLE_REPAINT_INTERVAL));
+    let mut tick = tokio::time::interval(tick_interval);
+    tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    // Scheduled availability report; the wall clock is re-read each wait so
+    // suspend and clock changes don't delay it
+    let mut report_due = config.report.as_ref().map(|r| report::next_due(r, Local::now()));
+
+    if !cli.headless && (cli.carousel || config.settings.carousel) {
+        app.start_carousel();
+    }
+
+    // Ctrl+C or SIGTERM shuts down as cleanly as 'q'
+    let mut stop = std::pin::pin!(shutdown_signal());
+
+    // Main event loop: sleep until input, a result, or the tick arrives, and
+    // draw only when one of them changed something
+    let mut needs_redraw = true;
     loop {
-        // Check if error should be auto-dismissed
-        app.check_error_dismissal();
+        // Keep the systemd watchdog fed while the loop is alive
+        systemd.watchdog_tick();
 
-        // Render UI based on current view
-        terminal.draw(|frame| {
-            last_frame_size = frame.size();
-            match &app.current_view {
-                View::Dashboard => ui::dashboard::render_dashboard(frame, &app),
-                View::Detail(site_name) => ui::detail::render_detail(frame, &app, site_name),
-                View::Alerts => ui::alerts::render_alerts(frame, &app),
-                View::AlertDetail(index) => ui::alert_detail::render_alert_detail(frame, &app, *index),
-                View::Help => ui::help::render_help(frame, &app),
-                View::Server => ui::server::render_server(frame, &app),
+        if needs_redraw {
+            needs_redraw = false;
+            if let Some(terminal) = &mut terminal {
+                draw(terminal, &app, &mut chart_painter)?;
             }
-        })?;
+            if let Some(title) = &mut terminal_title {
+                title.update(&app)?;
+            }
+        }
+        health.record_frame(rx.len(), rx.max_capacity());
+
+        tokio::select! {
+            _ = &mut stop => break,
 
-        // Poll for events with timeout (~60 FPS)
-        if event::poll(Duration::from_millis(16))? {
-            match event::read()? {
-                Event::Key(key) => {
-                    match app.handle_key_event(key) {
-                        AppAction::Quit => break,
-                        AppAction::Continue => {}
+            maybe_event = next_optional(&mut events) => {
+                match maybe_event {
+                    Some(Ok(Event::Key(key))) => {
+                        needs_redraw = true;
+                        match app.handle_key_event(key) {
+                            AppAction::Quit => break,
+                            AppAction::Continue => {}
+                        }
                     }
+                    Some(Ok(Event::Mouse(mouse))) => needs_redraw |= app.handle_mouse_event(mouse),
+                    Some(Ok(Event::Resize(..))) => needs_redraw = true,
+                    // Focus changes and the like leave the screen as it is
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e.into()),
+                    None => break,
+                }
+            }
+
+            Some((site_name, event)) = rx.recv() => {
+                needs_redraw = true;
+                handle_event(&mut app, site_name, event, mqtt_publisher.as_ref(), &alert_notifier);
+                // Batch what else already arrived into the same frame, but leave
+                // the rest for later frames so a flood can't starve rendering
+                for _ in 1..MAX_RESULTS_PER_FRAME {
+                    let Ok((site_name, event)) = rx.try_recv() else { break };
+                    handle_event(&mut app, site_name, event, mqtt_publisher.as_ref(), &alert_notifier);
                 }
-                Event::Mouse(mouse) => {
-                    app.handle_mouse_event(mouse, last_frame_size);
+            }
+
+            Some(poll) = recv_optional(&mut metrics_rx) => {
+                needs_redraw = true;
+                app.update_metrics(poll);
+            }
+
+            Some(change) = recv_optional(&mut site_changes_rx) => {
+                needs_redraw = true;
+                match change {
+                    SiteChange::Added(site) => {
+                        let name = site.name.clone();
+                        if let Err(e) = app.add_site((*site).clone()) {
+                            app.set_error(format!("{:#}", e));
+                            continue;
+                        }
+                        heartbeats.sync(&app.config);
+                        match build_checker(&site, &app.config, false, &heartbeats, &mut http_clients) {
+                            Ok(checker) => {
+                                if let Some(publisher) = &mqtt_publisher {
+                                    publisher.add_site(&name);
+                                }
+                                let schedule = CheckSchedule::for_site(&site, &app.config.settings, 0, 1);
+                                let handle = spawner.spawn(&mut app, *site, checker, schedule);
+                                discovered_tasks.insert(name, handle);
+                            }
+                            Err(e) => {
+                                app.remove_site(&name);
+                                app.set_error(format!("Discovered site '{}' can't be checked: {:#}", name, e));
+                            }
+                        }
+                    }
+                    SiteChange::Removed(name) => {
+                        if let Some(task) = discovered_tasks.remove(&name) {
+                            task.abort();
+                            health.untrack_task(&name);
+                            app.remove_site(&name);
+                            heartbeats.sync(&app.config);
+                            if let Some(publisher) = &mqtt_publisher {
+                                publisher.remove_site(&name);
+                            }
+                        }
+                    }
+                    SiteChange::Failed(message) => app.set_error(message),
                 }
-                _ => {}
+                alert_notifier.set_config(Arc::clone(&app.config));
             }
-        }
 
-        // Check for new health check results (non-blocking)
-        while let Ok((site_name, result)) = rx.try_recv() {
-            if let Some(alert) = app.handle_check_result(site_name, result) {
-                // Spawn async notification task (non-blocking)
-                let notifier = alert_notifier.clone();
-                let alert_clone = alert.clone();
-                tokio::spawn(async move {
-                    notifier.notify(&alert_clone).await;
+            Some(()) = recv_optional(&mut config_changes_rx) => {
+                needs_redraw = true;
+                // Everything is built before anything is replaced, so a broken
+                // edit leaves the running checks alone
+                let reloaded = Config::read(&config.source_path).and_then(|mut loaded| {
+                    // Heartbeat sites would wait on pings nothing is listening for
+                    if loaded.http_server.is_some() && http_server_task.is_none() && !cli.demo {
+                        anyhow::bail!("[http_server] is only started at startup - restart to turn it on");
+                    }
+                    if cli.demo_sites > 0 {
+                        loaded.add_demo_sites(cli.demo_sites);
+                    }
+                    let mut clients = http_clients.reloaded(Arc::new(loaded.clone()));
+                    let checkers = build_checkers(&loaded, cli.demo, &heartbeats, &mut clients)?;
+                    Ok((loaded, clients, checkers))
                 });
+                health.record_config_load(&reloaded);
+                let (loaded, clients, checkers) = match reloaded {
+                    Ok(reloaded) => reloaded,
+                    Err(e) => {
+                        app.set_error(format!("Config not reloaded: {:#}", e));
+                        continue;
+                    }
+                };
+                // Sites checked just as before keep their task, and with it
+                // their backoff and place in the schedule
+                let previous = Arc::clone(&app.config);
+                let mut kept = HashMap::new();
+                for (name, task) in site_tasks.drain() {
+                    if previous.checks_site_like(&loaded, &name) && !task.is_finished() {
+                        kept.insert(name, task);
+                    } else {
+                        task.abort();
+                        health.untrack_task(&name);
+                    }
+                }
+                let checkers = checkers
+                    .into_iter()
+                    .filter(|(index, _)| !kept.contains_key(&loaded.sites[*index].name))
+                    .collect();
+                // Discovered sites aren't in the file, so they carry over
+                let mut merged = loaded.clone();
+                for name in discovered_tasks.keys() {
+                    if let Some(site) = app.config.site(name).filter(|_| loaded.site(name).is_none()) {
+                        merged = merged.with_site(site.clone());
+                    }
+                }
+                let merged = Arc::new(merged);
+                heartbeats.sync(&merged);
+                let (added, removed) = app.reload_config(Arc::clone(&merged));
+                if let Some(publisher) = &mqtt_publisher {
+                    removed.iter().for_each(|name| publisher.remove_site(name));
+                    added.iter().for_each(|name| publisher.add_site(name));
+                }
+                site_tasks = spawner.spawn_configured(&mut app, &loaded, checkers);
+                site_tasks.extend(kept);
+                http_clients = clients;
+                http_clients.close_unused_tunnels(&merged);
+                alert_notifier = AlertNotifier::new(merged, Arc::clone(&app.notification_stats));
+                report_due = loaded.report.as_ref().map(|r| report::next_due(r, Local::now()));
+
+                let mut message = "Config reloaded".to_string();
+                if !added.is_empty() {
+                    message.push_str(&format!(", added {}", added.join(", ")));
+                }
+                if !removed.is_empty() {
+                    message.push_str(&format!(", removed {}", removed.join(", ")));
+                }
+                app.set_error(message);
             }
-        }
 
-        // Drain metrics poll results (non-blocking)
-        if let Some(ref mut mrx) = metrics_rx {
-            while let Ok(poll) = mrx.try_recv() {
-                app.update_metrics(poll);
+            Some(request) = recv_optional(&mut control_rx) => {
+                needs_redraw = true;
+                let reply = app.handle_control(request.command);
+                let _ = request.reply.send(reply);
+            }
+
+            _ = sleep_until_optional(report_due) => {
+                needs_redraw = true;
+                report_due = app.config.report.as_ref().map(|r| report::next_due(r, Local::now()));
+                if let Err(e) = app.send_report(true) {
+                    app.set_error(format!("Availability report not sent: {:#}", e));
+                }
+            }
+
+            _ = tick.tick() => {
+                // Clocks, ages and spinners move on even when nothing happened
+                needs_redraw = true;
+                app.check_error_dismissal();
+                app.advance_carousel();
+                for alert in app.check_stale() {
+                    if !alert.silenced {
+                        alert_notifier.notify(&alert);
+                    }
+                }
+
+                // Checker tasks only end at shutdown, so one that has stopped
+                // (most likely a panic) leaves its site stalled until restarted
+                for (name, task) in site_tasks.iter_mut().chain(discovered_tasks.iter_mut()) {
+                    if !task.is_finished() {
+                        continue;
+                    }
+                    app.mark_stalled(name);
+                    if restarted.get(name).is_some_and(|at| at.elapsed() < CHECKER_RESTART_DELAY) {
+                        continue;
+                    }
+                    let Some(site) = app.config.site(name).cloned() else { continue };
+                    restarted.insert(name.clone(), Instant::now());
+                    match build_checker(&site, &app.config, cli.demo, &heartbeats, &mut http_clients) {
+                        Ok(checker) => {
+                            let schedule = CheckSchedule::for_site(&site, &app.config.settings, 0, 1);
+                            *task = spawner.spawn(&mut app, site, checker, schedule);
+                            app.set_error(format!("Checker for '{}' stopped unexpectedly; restarted it", name));
+                        }
+                        Err(e) => app.set_error(format!("Checker for '{}' stopped and can't restart: {:#}", name, e)),
+                    }
+                }
             }
         }
     }
 
     // Graceful shutdown
+    systemd.stopping();
     let _ = shutdown_tx.send(true);
-    for task in tasks {
-        let _ = task.await;
-    }
-    if let Some(task) = metrics_task {
-        let _ = task.await;
+    let mut tasks: Vec<_> = site_tasks.into_values().collect();
+    tasks.extend(discovered_tasks.into_values());
+    tasks.extend(discovery_tasks);
+    tasks.extend(metrics_task);
+    tasks.extend(mqtt_task);
+    tasks.extend(http_server_task);
+    tasks.extend(control_task);
+
+    // Tasks stop at their next await point; anything still running when the
+    // deadline passes is aborted
+    let aborts: Vec<_> = tasks.iter().map(|task| task.abort_handle()).collect();
+    if tokio::time::timeout(SHUTDOWN_TIMEOUT, futures::future::join_all(tasks)).await.is_err() {
+        for abort in aborts {
+            abort.abort();
+        }
     }
 
     Ok(())
 }
+
+/// Starts checker tasks, for the configured sites and those added later
+struct TaskSpawner {
+    tx: ResultSender,
+    shutdown: watch::Receiver<bool>,
+    force_refresh: broadcast::Sender<()>,
+    health: Arc<MonitorHealth>,
+}
+
+impl TaskSpawner {
+    /// Start checking `site` on `schedule`
+    fn spawn(&self, app: &mut App, site: SiteConfig, checker: Checker, schedule: CheckSchedule) -> tokio::task::JoinHandle<()> {
+        let name = site.name.clone();
+        let signals = TaskSignals {
+            shutdown: self.shutdown.clone(),
+            force_refresh: self.force_refresh.subscribe(),
+            refresh: app.refresh_signal(&name),
+            paused: app.pause_signal(&name),
+            interval: app.interval_signal(&name),
+        };
+        let handle = spawn_checker_task(site, checker, self.tx.clone(), signals, schedule, &app.config.settings);
+        self.health.track_task(&name, handle.abort_handle());
+        handle
+    }
+
+    /// Start checking each of the config's sites with the checker built for it
+    fn spawn_configured(
+        &self,
+        app: &mut App,
+        config: &Config,
+        checkers: Vec<(usize, Checker)>,
+    ) -> HashMap<String, tokio::task::JoinHandle<()>> {
+        checkers
+            .into_iter()
+            .map(|(index, checker)| {
+                let site = &config.sites[index];
+                let schedule = CheckSchedule::for_site(site, &config.settings, index, config.sites.len());
+                (site.name.clone(), self.spawn(app, site.clone(), checker, schedule))
+            })
+            .collect()
+    }
+}
+
+/// Checkers for the config's sites by their index; composite sites have none,
+/// being derived from their members' results
+fn build_checkers(
+    config: &Config,
+    demo: bool,
+    heartbeats: &HeartbeatRegistry,
+    http_clients: &mut HttpClients,
+) -> anyhow::Result<Vec<(usize, Checker)>> {
+    config
+        .sites
+        .iter()
+        .enumerate()
+        .filter(|(_, site)| site.kind != SiteKind::Composite)
+        .map(|(index, site)| Ok((index, build_checker(site, config, demo, heartbeats, http_clients)?)))
+        .collect()
+}
+
+/// Pick how a site is checked: simulated in demo mode, otherwise by its kind
+fn build_checker(
+    site: &SiteConfig,
+    config: &Config,
+    demo: bool,
+    heartbeats: &HeartbeatRegistry,
+    http_clients: &mut HttpClients,
+) -> anyhow::Result<Checker> {
+    if demo {
+        return Ok(Checker::Demo(DemoChecker::new(&site.name)));
+    }
+    if site.kind == SiteKind::Heartbeat {
+        return Ok(Checker::Heartbeat(HeartbeatChecker::new(site, heartbeats.clone())));
+    }
+
+    let timeout = site.request_timeout.unwrap_or(config.settings.request_timeout);
+    if site.kind == SiteKind::Ntp {
+        return Ok(Checker::Ntp(NtpChecker::new(timeout)));
+    }
+    if site.kind == SiteKind::Ports {
+        return Ok(Checker::Ports(PortChecker::new(timeout)));
+    }
+    let body_regex = site
+        .expected_body_regex
+        .as_deref()
+        .map(regex::Regex::new)
+        .transpose()
+        .with_context(|| format!("Site '{}' has an invalid expected_body_regex", site.name))?;
+    // Each location gets its own XPaths, which can't be cloned
+    let request_checker = |client| -> anyhow::Result<Checker> {
+        Ok(match site.kind {
+            SiteKind::Graphql => Checker::Graphql(GraphqlChecker::new(client, timeout)),
+            _ => Checker::Http(HttpChecker::new(
+                client,
+                config.settings.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT),
+                timeout,
+                config.settings.inspect_tls || site.min_tls_version.is_some(),
+                site.check_revocation.unwrap_or(config.settings.check_revocation),
+                body_regex.clone(),
+                xml::compile_assertions(site)
+                    .with_context(|| format!("Site '{}' has an invalid xml_assertions XPath", site.name))?,
+            )),
+        })
+    };
+    if site.egress.is_empty() {
+        let checker = request_checker(http_clients.for_site(site, None)?)?;
+        return Ok(match http_clients.tunnel_error(site) {
+            Some(tunnel) => Checker::Via(Box::new(checker), tunnel),
+            None => checker,
+        });
+    }
+    let mut locations = Vec::new();
+    for egress in &site.egress {
+        locations.push((egress.clone(), request_checker(http_clients.for_site(site, Some(egress))?)?));
+    }
+    Ok(Checker::Egress(locations))
+}
+
+/// Render the current view, then any chart image over it
+fn draw(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    app: &App,
+    chart_painter: &mut Option<ChartPainter>,
+) -> anyhow::Result<()> {
+    loop {
+        app.clear_click_regions();
+        terminal.draw(|frame| {
+            match &app.current_view {
+                View::Dashboard => ui::dashboard::render_dashboard(frame, app),
+                View::Detail(site_name) => ui::detail::render_detail(frame, app, site_name),
+                View::Alerts => ui::alerts::render_alerts(frame, app),
+                View::AlertDetail(index) => ui::alert_detail::render_alert_detail(frame, app, *index),
+                View::Help => ui::help::render_help(frame, app),
+                View::Server => ui::server::render_server(frame, app),
+                View::Overview => ui::overview::render_overview(frame, app),
+                View::Slo => ui::slo::render_slo(frame, app),
+                View::AlertStats => ui::alert_stats::render_alert_stats(frame, app),
+            }
+            if let Some(palette) = &app.palette {
+                ui::palette::render_palette(frame, app, palette);
+            }
+            if let Some(input) = &app.silence_input {
+                ui::silence::render_silence_input(frame, app, input);
+            }
+            if let Some(input) = &app.tag_input {
+                ui::tag::render_tag_input(frame, app, input);
+            }
+            if let Some(confirmation) = &app.confirmation {
+                ui::confirm::render_confirmation(frame, app, confirmation);
+            }
+        })?;
+
+        let redraw = match chart_painter {
+            Some(painter) => painter.paint(terminal, app)?,
+            None => false,
+        };
+        if !redraw {
+            return Ok(());
+        }
+    }
+}
+
+/// Note a check going out, or handle the result of one that finished
+fn handle_event(
+    app: &mut App,
+    site_name: String,
+    event: CheckEvent,
+    mqtt_publisher: Option<&MqttPublisher>,
+    alert_notifier: &AlertNotifier,
+) {
+    match event {
+        CheckEvent::Started => app.mark_checking(site_name),
+        CheckEvent::Finished(result, next_check) => {
+            app.expect_result(&site_name, next_check);
+            handle_result(app, site_name, *result, mqtt_publisher, alert_notifier);
+        }
+        CheckEvent::Diagnosed(checked_at, findings) => {
+            // The alert went out as soon as the check failed; the findings follow it
+            if let Some(follow_up) = app.attach_diagnostics(&site_name, checked_at, findings) {
+                alert_notifier.notify(&follow_up);
+            }
+        }
+    }
+}
+
+/// Record a check result, publish it, and fire any resulting alert
+fn handle_result(
+    app: &mut App,
+    site_name: String,
+    result: CheckResult,
+    mqtt_publisher: Option<&MqttPublisher>,
+    alert_notifier: &AlertNotifier,
+) {
+    if let Some(publisher) = mqtt_publisher {
+        publisher.publish_result(&site_name, &result);
+    }
+    if let Some(alert) = app.handle_check_result(site_name.clone(), result)
+        && !alert.silenced
+    {
+        // Queued per notification method, so a slow one never blocks the loop
+        alert_notifier.notify(&alert);
+    }
+
+    // Composites containing this site take the same path with their new status
+    for (composite, result) in app.composite_results(&site_name) {
+        handle_result(app, composite, result, mqtt_publisher, alert_notifier);
+    }
+}
+
+/// Wait until a local time, or forever without one
+async fn sleep_until_optional(at: Option<DateTime<Local>>) {
+    match at {
+        Some(at) => tokio::time::sleep((at - Local::now()).to_std().unwrap_or_default()).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Next item of an optional stream; pends forever when there is none
+async fn next_optional<S: futures::Stream + Unpin>(stream: &mut Option<S>) -> Option<S::Item> {
+    match stream {
+        Some(stream) => stream.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Resolves on Ctrl+C or SIGTERM, e.g. from `systemctl stop`
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending().await,
+        }
+    };
+    // Only unix has SIGTERM
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = terminate => {}
+    }
+}
+
+/// Receive from an optional channel; pends forever when there is none
+async fn recv_optional<T>(rx: &mut Option<mpsc::Receiver<T>>) -> Option<T> {
+    match rx {
+        Some(rx) => rx.recv().await,
+        None => std::future::pending().await,
+    }
+}
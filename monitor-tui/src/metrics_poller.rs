@@ -1,3 +1,4 @@
+use crate::checker::http::DEFAULT_USER_AGENT;
 use crate::config::ServerMetricsConfig;
 use reqwest::Client;
 use serde::Deserialize;
@@ -12,6 +13,7 @@ pub struct LoadAvg {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
 pub struct MemoryStats {
     pub total_kb: u64,
     pub available_kb: u64,
@@ -20,6 +22,7 @@ pub struct MemoryStats {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
 pub struct CpuStats {
     pub user_pct: f64,
     pub system_pct: f64,
@@ -28,6 +31,7 @@ pub struct CpuStats {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
 pub struct DiskStats {
     pub total_kb: u64,
     pub used_kb: u64,
@@ -44,6 +48,7 @@ pub struct SystemStats {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
 pub struct AppTraffic {
     pub window_seconds: u64,
     pub requests_total: u64,
@@ -81,7 +86,7 @@ pub fn spawn_metrics_task(
     tokio::spawn(async move {
         let client = Client::builder()
             .timeout(Duration::from_secs(10))
-            .user_agent("monitor-tui/0.1.0")
+            .user_agent(DEFAULT_USER_AGENT)
             .build()
             .expect("Failed to build metrics HTTP client");
 
@@ -0,0 +1,137 @@
+use crate::alerts::NotificationStats;
+use crate::checker::QueueStats;
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::AbortHandle;
+
+/// A UI loop that hasn't drawn for this long is considered stuck
+const RENDER_STALE_AFTER: Duration = Duration::from_secs(30);
+
+/// When the running config was read, and how the last reload went
+struct ConfigLoads {
+    loaded_at: DateTime<Utc>,
+    last_reload_at: Option<DateTime<Utc>>,
+    /// Why the last reload failed, leaving the running config behind the file
+    last_reload_error: Option<String>,
+}
+
+/// The monitor's own state, kept current by the main loop and read by `/healthz`
+pub struct MonitorHealth {
+    started: DateTime<Utc>,
+    /// Checker task of each site, to tell whether it is still running
+    tasks: Mutex<BTreeMap<String, AbortHandle>>,
+    last_render: Mutex<Option<DateTime<Utc>>>,
+    /// Results waiting for the UI, and how many can wait
+    result_backlog: AtomicUsize,
+    result_capacity: AtomicUsize,
+    /// Config file in use; it loaded at startup or the monitor wouldn't be running
+    config_path: PathBuf,
+    config_loads: Mutex<ConfigLoads>,
+    queue_stats: Arc<QueueStats>,
+    notification_stats: Arc<NotificationStats>,
+}
+
+impl MonitorHealth {
+    pub fn new(config_path: PathBuf, queue_stats: Arc<QueueStats>, notification_stats: Arc<NotificationStats>) -> Self {
+        Self {
+            started: Utc::now(),
+            tasks: Mutex::new(BTreeMap::new()),
+            last_render: Mutex::new(None),
+            result_backlog: AtomicUsize::new(0),
+            result_capacity: AtomicUsize::new(0),
+            config_path,
+            config_loads: Mutex::new(ConfigLoads { loaded_at: Utc::now(), last_reload_at: None, last_reload_error: None }),
+            queue_stats,
+            notification_stats,
+        }
+    }
+
+    /// Watch a site's checker task
+    pub fn track_task(&self, site_name: &str, task: AbortHandle) {
+        self.tasks.lock().unwrap().insert(site_name.to_string(), task);
+    }
+
+    /// Stop watching a site's checker task, e.g. when the site goes away
+    pub fn untrack_task(&self, site_name: &str) {
+        self.tasks.lock().unwrap().remove(site_name);
+    }
+
+    /// Note a drawn frame and the result queue as it stood
+    pub fn record_frame(&self, backlog: usize, capacity: usize) {
+        *self.last_render.lock().unwrap() = Some(Utc::now());
+        self.result_backlog.store(backlog, Ordering::Relaxed);
+        self.result_capacity.store(capacity, Ordering::Relaxed);
+    }
+
+    /// Sites whose checker task has stopped
+    pub fn dead_tasks(&self) -> Vec<String> {
+        self.tasks
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, task)| task.is_finished())
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Note a reload of the config file, whether it took or not
+    pub fn record_config_load<T>(&self, result: &anyhow::Result<T>) {
+        let now = Utc::now();
+        let mut loads = self.config_loads.lock().unwrap();
+        loads.last_reload_at = Some(now);
+        match result {
+            Ok(_) => {
+                loads.loaded_at = now;
+                loads.last_reload_error = None;
+            }
+            Err(e) => loads.last_reload_error = Some(format!("{:#}", e)),
+        }
+    }
+
+    /// Status report and whether everything is healthy
+    pub fn report(&self) -> (bool, Value) {
+        let now = Utc::now();
+        let dead = self.dead_tasks();
+        let total_tasks = self.tasks.lock().unwrap().len();
+        let last_render = *self.last_render.lock().unwrap();
+        let render_age = last_render.map(|at| (now - at).to_std().unwrap_or_default());
+        let render_ok = render_age.is_some_and(|age| age < RENDER_STALE_AFTER);
+        let healthy = dead.is_empty() && render_ok;
+        let loads = self.config_loads.lock().unwrap();
+
+        let body = json!({
+            "status": if healthy { "ok" } else { "degraded" },
+            "uptime_seconds": (now - self.started).num_seconds(),
+            "checkers": {
+                "running": total_tasks - dead.len(),
+                "total": total_tasks,
+                "dead": dead,
+            },
+            "result_queue": {
+                "backlog": self.result_backlog.load(Ordering::Relaxed),
+                "capacity": self.result_capacity.load(Ordering::Relaxed),
+                "dropped": self.queue_stats.dropped.load(Ordering::Relaxed),
+                "delayed": self.queue_stats.delayed.load(Ordering::Relaxed),
+            },
+            "notifications": {
+                "dropped": self.notification_stats.dropped.load(Ordering::Relaxed),
+                "failed": self.notification_stats.failed.load(Ordering::Relaxed),
+                "last_error": self.notification_stats.last_error.lock().unwrap().clone(),
+            },
+            "last_render": last_render.map(|at| at.to_rfc3339()),
+            "last_render_age_seconds": render_age.map(|age| age.as_secs()),
+            "config": {
+                "path": self.config_path.display().to_string(),
+                "loaded_at": loads.loaded_at.to_rfc3339(),
+                "last_reload_at": loads.last_reload_at.map(|at| at.to_rfc3339()),
+                "last_reload_error": loads.last_reload_error,
+            },
+        });
+        (healthy, body)
+    }
+}
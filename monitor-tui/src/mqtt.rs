@@ -0,0 +1,206 @@
+use crate::checker::CheckResult;
+use crate::config::{slugify, Config, MqttConfig};
+use rumqttc::{AsyncClient, Event, LastWill, MqttOptions, Outgoing, Packet, QoS};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Publishes per-site state to an MQTT broker
+#[derive(Clone)]
+pub struct MqttPublisher {
+    client: AsyncClient,
+    config: MqttConfig,
+    /// Sites shown now, announced again on every (re)connect
+    site_names: Arc<Mutex<Vec<String>>>,
+}
+
+impl MqttPublisher {
+    /// Connect to the broker and spawn the task that drives the connection
+    pub fn spawn(
+        config: &Config,
+        mqtt_config: MqttConfig,
+        mut shutdown: watch::Receiver<bool>,
+    ) -> (Self, tokio::task::JoinHandle<()>) {
+        let mut options = MqttOptions::new(&mqtt_config.client_id, &mqtt_config.host, mqtt_config.port);
+        options.set_keep_alive(Duration::from_secs(30));
+        if let Some(username) = &mqtt_config.username {
+            options.set_credentials(username, mqtt_config.password.clone().unwrap_or_default());
+        }
+        // Broker marks us offline if the connection drops without a goodbye
+        options.set_last_will(LastWill::new(
+            availability_topic(&mqtt_config),
+            "offline",
+            QoS::AtLeastOnce,
+            true,
+        ));
+
+        // Room for a full round of results plus discovery payloads
+        let capacity = (config.sites.len() * 4).max(32);
+        let (client, mut eventloop) = AsyncClient::new(options, capacity);
+        let publisher = Self {
+            client: client.clone(),
+            config: mqtt_config,
+            site_names: Arc::new(Mutex::new(config.sites.iter().map(|site| site.name.clone()).collect())),
+        };
+
+        let offline_topic = availability_topic(&publisher.config);
+        let announcer = publisher.clone();
+        let handle = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    event = eventloop.poll() => match event {
+                        // The broker published our last will if the connection
+                        // dropped, so every (re)connect says we're back
+                        Ok(Event::Incoming(Packet::ConnAck(_))) => announcer.announce(),
+                        Ok(_) => {}
+                        Err(_) => {
+                            // rumqttc reconnects on the next poll; don't spin while the broker is away
+                            tokio::time::sleep(Duration::from_secs(5)).await;
+                        }
+                    },
+                    _ = shutdown.changed() => break,
+                }
+            }
+
+            // Say goodbye cleanly, then flush until the disconnect goes out
+            let _ = client.try_publish(offline_topic, QoS::AtLeastOnce, true, "offline");
+            let _ = client.try_disconnect();
+            let _ = tokio::time::timeout(Duration::from_secs(2), async {
+                while let Ok(event) = eventloop.poll().await {
+                    if matches!(event, Event::Outgoing(Outgoing::Disconnect)) {
+                        break;
+                    }
+                }
+            })
+            .await;
+        });
+
+        (publisher, handle)
+    }
+
+    /// Publish the latest check result for a site (non-blocking)
+    pub fn publish_result(&self, site_name: &str, result: &CheckResult) {
+        let base = self.site_topic(site_name);
+
+        let state = result.status.key();
+        let latency = result
+            .response_time_ms
+            .map(|ms| ms.to_string())
+            .unwrap_or_default();
+        let attributes = json!({
+            "status": state,
+            "response_time_ms": result.response_time_ms,
+            "http_status": result.http_status,
+            "error": result.error_message,
+            "timestamp": result.timestamp.to_rfc3339(),
+        });
+
+        self.publish_raw(format!("{}/state", base), self.config.retain, state);
+        self.publish_raw(format!("{}/latency_ms", base), self.config.retain, latency);
+        self.publish_raw(format!("{}/attributes", base), self.config.retain, attributes.to_string());
+    }
+
+    /// Mark the monitor online and, for Home Assistant, describe each site's entities
+    fn announce(&self) {
+        self.publish_raw(availability_topic(&self.config), true, "online");
+        if self.config.home_assistant_discovery {
+            for name in self.site_names.lock().unwrap().iter() {
+                self.publish_discovery(name);
+            }
+        }
+    }
+
+    /// Describe a site added while running, found by discovery or a reload
+    pub fn add_site(&self, site_name: &str) {
+        self.site_names.lock().unwrap().push(site_name.to_string());
+        if self.config.home_assistant_discovery {
+            self.publish_discovery(site_name);
+        }
+    }
+
+    /// Retract a site that went away: an empty retained config removes its
+    /// Home Assistant entities, and empty retained state clears its topics
+    pub fn remove_site(&self, site_name: &str) {
+        self.site_names.lock().unwrap().retain(|name| name != site_name);
+        if self.config.home_assistant_discovery {
+            let (connectivity, latency) = self.discovery_topics(site_name);
+            self.publish_raw(connectivity, true, "");
+            self.publish_raw(latency, true, "");
+        }
+        if self.config.retain {
+            let base = self.site_topic(site_name);
+            for topic in ["state", "latency_ms", "attributes"] {
+                self.publish_raw(format!("{}/{}", base, topic), true, "");
+            }
+        }
+    }
+
+    /// Home Assistant MQTT discovery: a connectivity binary sensor and a latency sensor per site
+    fn publish_discovery(&self, site_name: &str) {
+        let id = self.object_id(site_name);
+        let base = self.site_topic(site_name);
+        let availability = availability_topic(&self.config);
+        let device = json!({
+            "identifiers": [format!("monitor_tui_{}", slugify(&self.config.client_id))],
+            "name": "Monitor TUI",
+        });
+
+        let connectivity = json!({
+            "name": site_name,
+            "unique_id": id,
+            "device_class": "connectivity",
+            "state_topic": format!("{}/state", base),
+            "payload_on": "up",
+            "payload_off": "down",
+            // Only up is connected; down and warning aren't, and a skipped or
+            // rate-limited check says neither, which Home Assistant shows as unknown
+            "value_template": "{{ 'up' if value == 'up' else 'down' if value in ['down', 'warning'] else 'None' }}",
+            "json_attributes_topic": format!("{}/attributes", base),
+            "availability_topic": availability,
+            "device": device,
+        });
+        let latency = json!({
+            "name": format!("{} latency", site_name),
+            "unique_id": format!("{}_latency", id),
+            "state_topic": format!("{}/latency_ms", base),
+            "unit_of_measurement": "ms",
+            "state_class": "measurement",
+            "availability_topic": availability,
+            "device": device,
+        });
+
+        let (connectivity_topic, latency_topic) = self.discovery_topics(site_name);
+        self.publish_raw(connectivity_topic, true, connectivity.to_string());
+        self.publish_raw(latency_topic, true, latency.to_string());
+    }
+
+    /// Discovery config topics of a site's connectivity and latency entities
+    fn discovery_topics(&self, site_name: &str) -> (String, String) {
+        let id = self.object_id(site_name);
+        let prefix = &self.config.discovery_prefix;
+        (
+            format!("{}/binary_sensor/{}/config", prefix, id),
+            format!("{}/sensor/{}_latency/config", prefix, id),
+        )
+    }
+
+    /// Id of a site's entities, unique across every instance publishing to
+    /// the broker as long as each has its own client_id
+    fn object_id(&self, site_name: &str) -> String {
+        format!("monitor_tui_{}_{}", slugify(&self.config.client_id), slugify(site_name))
+    }
+
+    fn site_topic(&self, site_name: &str) -> String {
+        format!("{}/{}", self.config.topic_prefix, slugify(site_name))
+    }
+
+    fn publish_raw(&self, topic: String, retain: bool, payload: impl Into<Vec<u8>>) {
+        // Dropping a sample when the queue is full beats stalling the UI
+        let _ = self.client.try_publish(topic, QoS::AtLeastOnce, retain, payload);
+    }
+}
+
+fn availability_topic(config: &MqttConfig) -> String {
+    format!("{}/status", config.topic_prefix)
+}
@@ -0,0 +1,155 @@
+use crate::app::{App, AppAction, View};
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Most commands the palette lists at once
+pub const MAX_SHOWN: usize = 12;
+
+/// An action offered in the command palette
+pub struct Command {
+    pub name: &'static str,
+    /// Key that does the same outside the palette
+    pub keys: &'static str,
+    /// Whether the command makes sense in the app's current state
+    available: fn(&App) -> bool,
+    run: fn(&mut App) -> AppAction,
+}
+
+/// Everything the palette can do, in the order listed before anything is typed
+pub const COMMANDS: &[Command] = &[
+    Command::new("Show dashboard", "ESC", not_on_dashboard, |app| app.show_view(View::Dashboard)),
+    Command::new("Show alert history", "a", always, |app| press(app, 'a')),
+    Command::new("Show alert statistics", "S", |app| app.current_view != View::AlertStats, |app| {
+        app.show_view(View::AlertStats)
+    }),
+    Command::new("Show fleet overview", "o", always, |app| press(app, 'o')),
+    Command::new("Show error budgets", "b", always, |app| press(app, 'b')),
+    Command::new("Show server metrics", "s", |app| app.current_view != View::Server, |app| press(app, 's')),
+    Command::new("Show help", "?", always, |app| press(app, '?')),
+    Command::new("Open selected site's details", "Enter", selection, |app| {
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter))
+    }),
+    Command::new("Refresh all sites", "r", always, |app| press(app, 'r')),
+    Command::new("Re-check this site", "R", site, |app| press(app, 'R')),
+    Command::new("Open site URL in browser", "O", site, |app| press(app, 'O')),
+    Command::new("Copy view as text", "y", copyable, |app| press(app, 'y')),
+    Command::new("Pause / resume checks", "P", always, |app| press(app, 'P')),
+    Command::new("Pause / resume checks of site(s)", "p", site, |app| press(app, 'p')),
+    Command::new("Cycle theme", "t", always, |app| press(app, 't')),
+    Command::new("Start / stop carousel", "c", always, |app| press(app, 'c')),
+    Command::new("Pin / unpin selected site", "f", selection, |app| press(app, 'f')),
+    Command::new("Expand / collapse composite", "z", selection, |app| press(app, 'z')),
+    Command::new("Mark / unmark selected site", "Space", selection, |app| press(app, ' ')),
+    Command::new("Clear marks", "ESC", |app| app.current_view == View::Dashboard && !app.marked.is_empty(), |app| {
+        app.handle_key_event(KeyEvent::from(KeyCode::Esc))
+    }),
+    Command::new("Tag site(s)", "T", site, |app| press(app, 'T')),
+    Command::new("Check site(s) less often", "+", site, |app| press(app, '+')),
+    Command::new("Check site(s) more often", "-", site, |app| press(app, '-')),
+    Command::new("Sort by health score / by hand", "H", |app| app.current_view == View::Dashboard, |app| press(app, 'H')),
+    Command::new("Export chart as SVG", "e", detail, |app| press(app, 'e')),
+    Command::new("Export chart as PNG", "E", detail, |app| press(app, 'E')),
+    Command::new("Change uptime window", "u", detail, |app| press(app, 'u')),
+    Command::new("Acknowledge alert", "A", |app| matches!(app.current_view, View::Alerts | View::AlertDetail(_)), |app| {
+        press(app, 'A')
+    }),
+    Command::new("Hide / show acknowledged alerts", "F", alerts, |app| press(app, 'F')),
+    Command::new("Clear alert history", "x", alerts, |app| press(app, 'x')),
+    Command::new("Clear recovery alerts", "X", alerts, |app| press(app, 'X')),
+    Command::new("Silence alerts", "m", always, |app| press(app, 'm')),
+    Command::new("Lift silences", "M", |app| !app.silences.is_empty(), |app| press(app, 'M')),
+    Command::new("Comment on alert", "n", |app| matches!(app.current_view, View::AlertDetail(_)), |app| press(app, 'n')),
+    Command::new("Quit", "q", always, |app| press(app, 'q')),
+];
+
+fn always(_: &App) -> bool {
+    true
+}
+
+fn not_on_dashboard(app: &App) -> bool {
+    app.current_view != View::Dashboard
+}
+
+/// A site is selected on the dashboard
+fn selection(app: &App) -> bool {
+    app.current_view == View::Dashboard && app.selected_site().is_some()
+}
+
+/// There's a site to act on, selected or in the detail view
+fn site(app: &App) -> bool {
+    app.current_site().is_some()
+}
+
+/// The view is one `y` can copy
+fn copyable(app: &App) -> bool {
+    matches!(app.current_view, View::Dashboard | View::Detail(_) | View::Alerts | View::AlertDetail(_))
+}
+
+fn alerts(app: &App) -> bool {
+    app.current_view == View::Alerts
+}
+
+fn detail(app: &App) -> bool {
+    matches!(app.current_view, View::Detail(_))
+}
+
+/// Run a command through its keybinding
+fn press(app: &mut App, key: char) -> AppAction {
+    app.handle_key_event(KeyEvent::from(KeyCode::Char(key)))
+}
+
+/// The open palette: what's been typed and which match is highlighted
+#[derive(Debug, Default)]
+pub struct Palette {
+    pub query: String,
+    pub selected: usize,
+}
+
+impl Palette {
+    /// Commands available in `app` that match the query, best match first
+    pub fn matches(&self, app: &App) -> Vec<&'static Command> {
+        let mut scored: Vec<(i32, &Command)> = COMMANDS
+            .iter()
+            .filter(|command| (command.available)(app))
+            .filter_map(|command| Some((fuzzy_score(&self.query, command.name)?, command)))
+            .collect();
+        scored.sort_by_key(|(score, _)| -score);
+        scored.into_iter().map(|(_, command)| command).take(MAX_SHOWN).collect()
+    }
+}
+
+impl Command {
+    const fn new(
+        name: &'static str,
+        keys: &'static str,
+        available: fn(&App) -> bool,
+        run: fn(&mut App) -> AppAction,
+    ) -> Self {
+        Self { name, keys, available, run }
+    }
+
+    pub fn run(&self, app: &mut App) -> AppAction {
+        (self.run)(app)
+    }
+}
+
+/// How well `query` matches `text` with its characters in order but not
+/// necessarily together; None if it doesn't. Runs of matched characters and
+/// matches at the start of words score higher.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let mut score = 0;
+    let mut query = query.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).peekable();
+    let mut previous_matched = false;
+    let mut word_start = true;
+    for c in text.chars().flat_map(char::to_lowercase) {
+        let Some(&wanted) = query.peek() else { break };
+        if c == wanted {
+            query.next();
+            score += 1 + if previous_matched { 3 } else { 0 } + if word_start { 2 } else { 0 };
+            previous_matched = true;
+        } else {
+            previous_matched = false;
+        }
+        word_start = !c.is_alphanumeric();
+    }
+    query.peek().is_none().then_some(score)
+}
@@ -0,0 +1,68 @@
+use crate::alerts::Silence;
+use crate::ui::theme::ThemeName;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// UI choices made at runtime, kept across sessions when persist_preferences is on
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Preferences {
+    /// Theme picked with 't'; overrides the configured theme
+    #[serde(default)]
+    pub theme: Option<ThemeName>,
+    /// Composite sites expanded on the dashboard
+    #[serde(default)]
+    pub expanded: Vec<String>,
+    /// Dashboard order set with J/K; sites not listed follow in config order
+    #[serde(default)]
+    pub order: Vec<String>,
+    /// Sites pinned to the top of the dashboard with 'f'
+    #[serde(default)]
+    pub pinned: Vec<String>,
+    /// Dashboard sorted by health score with 'H'
+    #[serde(default)]
+    pub sort_by_health: bool,
+    /// Silences added with 'm' that hadn't lifted yet
+    #[serde(default)]
+    pub silences: Vec<Silence>,
+    /// Tags added with 'T', by site
+    #[serde(default)]
+    pub tags: BTreeMap<String, Vec<String>>,
+    /// Acknowledged alerts hidden from the alerts view with 'F'
+    #[serde(default)]
+    pub hide_acknowledged: bool,
+}
+
+impl Preferences {
+    /// Default file location: the platform state directory
+    /// (~/.local/state/monitor/preferences.json on Linux)
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .map(|dir| dir.join("monitor").join("preferences.json"))
+    }
+
+    /// Read saved preferences; a missing or unreadable file means none saved yet
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the preferences, replacing the file in one step so a crash
+    /// mid-write can't leave it truncated
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        }
+        let temp = path.with_extension("json.tmp");
+        std::fs::write(&temp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write preferences: {}", temp.display()))?;
+        std::fs::rename(&temp, path)
+            .with_context(|| format!("Failed to write preferences: {}", path.display()))?;
+        Ok(())
+    }
+}
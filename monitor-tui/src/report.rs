@@ -0,0 +1,157 @@
+use crate::alerts::AlertSeverity;
+use crate::app::App;
+use crate::config::{ReportConfig, ReportPeriod, SmtpConfig, SmtpSecurity};
+use crate::stats::SessionStats;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Days, Local, Months, NaiveTime, TimeZone, Utc, Weekday};
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::fmt::Write;
+
+/// When the next scheduled report is due after `after`
+pub fn next_due(config: &ReportConfig, after: DateTime<Local>) -> DateTime<Local> {
+    let send_at = NaiveTime::parse_from_str(&config.send_at, "%H:%M").unwrap_or_default();
+    let mut date = after.date_naive();
+    loop {
+        let due_day = match config.period {
+            ReportPeriod::Weekly => date.weekday() == Weekday::Mon,
+            ReportPeriod::Monthly => date.day() == 1,
+        };
+        // A send time skipped by a DST change moves to the next due day
+        if due_day
+            && let Some(due) = Local.from_local_datetime(&date.and_time(send_at)).earliest()
+            && due > after
+        {
+            return due;
+        }
+        date = match config.period {
+            ReportPeriod::Weekly => date + Days::new(1),
+            ReportPeriod::Monthly => (date.with_day(1).unwrap_or(date)) + Months::new(1),
+        };
+    }
+}
+
+/// Subject and plain-text body of a report on `stats`, covering from when
+/// they started until `until`
+pub fn compose(app: &App, stats: &SessionStats, until: DateTime<Local>) -> (String, String) {
+    let since = stats.started;
+    let span = format!("{} to {}", since.format("%Y-%m-%d %H:%M"), until.format("%Y-%m-%d %H:%M"));
+    let subject = match stats.availability() {
+        Some(pct) => format!("Availability report: {:.2}% ({})", pct, span),
+        None => format!("Availability report ({})", span),
+    };
+
+    let mut body = String::new();
+    let _ = writeln!(body, "Availability report, {}", span);
+    let _ = writeln!(body, "(counts cover only the time monitor-tui was running)");
+    let _ = writeln!(body);
+    let incidents: u64 = stats.sites.values().map(|t| t.incidents).sum();
+    let _ = match stats.availability() {
+        Some(pct) => writeln!(
+            body,
+            "Overall: {:.3}% of {} checks up, {} timed out, {} incident{}",
+            pct,
+            stats.total_checks(),
+            stats.total_timeouts(),
+            incidents,
+            if incidents == 1 { "" } else { "s" }
+        ),
+        None => writeln!(body, "Overall: no checks yet"),
+    };
+    let _ = writeln!(body);
+
+    // Least available sites first
+    let mut sites: Vec<(&str, Option<f64>)> = app
+        .sites
+        .keys()
+        .map(|name| {
+            let uptime = stats
+                .sites
+                .get(name)
+                .filter(|t| t.checks > 0)
+                .map(|t| t.up as f64 / t.checks as f64 * 100.0);
+            (name.as_str(), uptime)
+        })
+        .collect();
+    sites.sort_by(|a, b| {
+        a.1.unwrap_or(f64::INFINITY)
+            .total_cmp(&b.1.unwrap_or(f64::INFINITY))
+            .then(a.0.cmp(b.0))
+    });
+
+    let _ = writeln!(
+        body,
+        "{:<32} {:>9} {:>8} {:>9} {:>10} {:>8} {:>9}",
+        "Site", "Uptime", "Checks", "Timeouts", "Incidents", "Avg ms", "Worst ms"
+    );
+    let dash = || "--".to_string();
+    for (name, uptime) in sites {
+        let totals = stats.sites.get(name);
+        let _ = writeln!(
+            body,
+            "{:<32} {:>9} {:>8} {:>9} {:>10} {:>8} {:>9}",
+            name,
+            uptime.map_or_else(dash, |pct| format!("{:.3}%", pct)),
+            totals.map_or(0, |t| t.checks),
+            totals.map_or(0, |t| t.timeouts),
+            totals.map_or(0, |t| t.incidents),
+            totals.and_then(|t| t.avg_response_time()).map_or_else(dash, |ms| ms.to_string()),
+            totals.and_then(|t| t.max_response_ms).map_or_else(dash, |ms| ms.to_string()),
+        );
+    }
+
+    let since_utc = since.with_timezone(&Utc);
+    let down_alerts: Vec<_> = app
+        .alert_history
+        .all_alerts()
+        .iter()
+        .filter(|a| a.severity == AlertSeverity::Critical && a.timestamp >= since_utc)
+        .collect();
+    if !down_alerts.is_empty() {
+        let _ = writeln!(body);
+        let _ = writeln!(body, "Incidents");
+        for alert in down_alerts {
+            let _ = write!(
+                body,
+                "  {}  {}",
+                alert.timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S"),
+                alert.message
+            );
+            if let Some(comment) = &alert.comment {
+                let _ = write!(body, " ({})", comment);
+            }
+            let _ = writeln!(body);
+        }
+    }
+
+    (subject, body)
+}
+
+/// Email a report to the configured recipients
+pub async fn send(smtp: &SmtpConfig, subject: String, body: String) -> Result<()> {
+    let mut message = Message::builder().from(smtp.from.parse()?).subject(subject);
+    for to in &smtp.to {
+        message = message.to(to.parse()?);
+    }
+    let message = message.header(ContentType::TEXT_PLAIN).body(body)?;
+
+    let mut transport = match smtp.security {
+        SmtpSecurity::Starttls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp.host)?,
+        SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&smtp.host)?,
+        SmtpSecurity::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&smtp.host),
+    };
+    if let Some(port) = smtp.port {
+        transport = transport.port(port);
+    }
+    if let Some(username) = &smtp.username {
+        let password = smtp.password.clone().unwrap_or_default();
+        transport = transport.credentials(Credentials::new(username.clone(), password));
+    }
+    transport
+        .build()
+        .send(message)
+        .await
+        .with_context(|| format!("sending via {}", smtp.host))?;
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use crate::alerts::Alert;
+use crate::checker::CheckResult;
+use anyhow::{Context, Result};
+use chrono::Local;
+use std::cell::RefCell;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+/// Check results, alerts and messages written a line each, for running
+/// without the TUI; under systemd, stdout goes to the journal
+pub struct ResultLog {
+    out: RefCell<Box<dyn Write>>,
+}
+
+impl ResultLog {
+    pub fn stdout() -> Self {
+        Self { out: RefCell::new(Box::new(std::io::stdout())) }
+    }
+
+    /// Append to the file at `path`, creating it if needed
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open log file: {}", path.display()))?;
+        Ok(Self { out: RefCell::new(Box::new(file)) })
+    }
+
+    /// e.g. "api UP 120ms HTTP 200" or "db DOWN Connection failed: refused"
+    pub fn result(&self, site_name: &str, result: &CheckResult) {
+        let mut line = format!("{} {}", site_name, result.status.label());
+        if let Some(ms) = result.response_time_ms {
+            line.push_str(&format!(" {}ms", ms));
+        }
+        if let Some(code) = result.http_status {
+            line.push_str(&format!(" HTTP {}", code));
+        }
+        if let Some(offset) = result.clock_offset_ms {
+            line.push_str(&format!(" offset {:+}ms", offset));
+        }
+        if let Some(error) = &result.error_message {
+            line.push_str(&format!(" {}", error));
+        }
+        self.line(&line);
+    }
+
+    /// e.g. "ALERT CRITICAL api is DOWN - Connection failed: refused"
+    pub fn alert(&self, alert: &Alert) {
+        let mut line = format!("ALERT {} {}", alert.severity.label(), alert.message);
+        if let Some(error) = alert.error() {
+            line.push_str(&format!(" - {}", error));
+        }
+        if alert.silenced {
+            line.push_str(" (silenced)");
+        }
+        self.line(&line);
+    }
+
+    /// What the TUI would show in its message bar
+    pub fn message(&self, message: &str) {
+        self.line(message);
+    }
+
+    fn line(&self, text: &str) {
+        let mut out = self.out.borrow_mut();
+        let _ = writeln!(out, "{} {}", Local::now().format("%Y-%m-%dT%H:%M:%S%:z"), text);
+        let _ = out.flush();
+    }
+}
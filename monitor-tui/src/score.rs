@@ -0,0 +1,38 @@
+use crate::alerts::{Alert, AlertSeverity};
+use crate::config::UptimeWindow;
+use crate::history::SiteHistory;
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// Alerts older than this no longer weigh on the score
+pub const ALERT_WINDOW: TimeDelta = TimeDelta::hours(24);
+
+/// Alerts within the window that use up the alert share of the score
+const ALERTS_FOR_ZERO: f64 = 5.0;
+
+/// Single 0-100 number to triage sites by, blending uptime over the window (60%), average
+/// latency against the warning threshold (25%) and alerts over the last day
+/// (15%); None until the site has been checked
+pub fn health_score(
+    history: &SiteHistory,
+    uptime_window: UptimeWindow,
+    warning_threshold_ms: Option<u64>,
+    alerts: &[&Alert],
+    now: DateTime<Utc>,
+) -> Option<u8> {
+    let uptime = history.uptime(uptime_window)? / 100.0;
+
+    // Half the threshold or better scores full marks, one and a half times it nothing;
+    // without a threshold there's nothing to judge latency against
+    let latency = match (warning_threshold_ms.filter(|&t| t > 0), history.avg_response_time()) {
+        (Some(threshold), Some(avg)) => (1.5 - avg as f64 / threshold as f64).clamp(0.0, 1.0),
+        _ => 1.0,
+    };
+
+    let recent = alerts
+        .iter()
+        .filter(|a| a.severity != AlertSeverity::Recovery && now - a.timestamp <= ALERT_WINDOW)
+        .count();
+    let alerting = (1.0 - recent as f64 / ALERTS_FOR_ZERO).max(0.0);
+
+    Some((100.0 * (0.6 * uptime + 0.25 * latency + 0.15 * alerting)).round() as u8)
+}
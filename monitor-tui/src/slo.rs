@@ -0,0 +1,56 @@
+use crate::history::{month_end, month_start, SiteHistory};
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// Window the burn rate is measured over
+pub const BURN_RATE_WINDOW: TimeDelta = TimeDelta::hours(1);
+
+/// This month's error budget for a site with an SLO target
+pub struct ErrorBudget {
+    /// Availability target as a percentage, e.g. 99.9
+    pub target: f64,
+    /// Downtime the target allows over the whole month
+    pub allowed: TimeDelta,
+    /// Downtime so far this month
+    pub used: TimeDelta,
+    /// Percentage of the observed part of the month the site was not Down
+    pub availability: Option<f64>,
+    /// How fast the last hour spent budget: 1.0 spends exactly all of it by
+    /// month end, 2.0 twice as fast
+    pub burn_rate: Option<f64>,
+}
+
+impl ErrorBudget {
+    pub fn new(history: &SiteHistory, target: f64, now: DateTime<Utc>) -> Self {
+        let start = month_start(now);
+        let month = month_end(start) - start;
+        let allowed_fraction = 1.0 - target / 100.0;
+        let allowed = TimeDelta::milliseconds((month.num_milliseconds() as f64 * allowed_fraction) as i64);
+
+        // Nothing recorded yet this month leaves last month's figures behind
+        let downtime = history.month_downtime();
+        let (used, observed) = if downtime.start == start {
+            (downtime.down, downtime.observed)
+        } else {
+            (TimeDelta::zero(), TimeDelta::zero())
+        };
+        let availability = (observed > TimeDelta::zero())
+            .then(|| (1.0 - used.num_milliseconds() as f64 / observed.num_milliseconds() as f64) * 100.0);
+
+        let burn_rate = history
+            .down_fraction_since(now - BURN_RATE_WINDOW)
+            .map(|fraction| fraction / allowed_fraction);
+
+        Self {
+            target,
+            allowed,
+            used,
+            availability,
+            burn_rate,
+        }
+    }
+
+    /// Downtime left before the target is missed; negative once it has been
+    pub fn remaining(&self) -> TimeDelta {
+        self.allowed - self.used
+    }
+}
@@ -0,0 +1,167 @@
+use crate::alerts::AlertSeverity;
+use crate::checker::{CheckResult, Status};
+use chrono::{DateTime, Local, Timelike};
+use std::collections::{HashMap, VecDeque};
+
+/// Minutes of fleet latency kept for the overview chart
+const FLEET_LATENCY_MINUTES: usize = 120;
+
+/// Fleet-wide counts since startup (or since the last report), kept apart
+/// from the bounded histories so they cover the whole span
+pub struct SessionStats {
+    pub started: DateTime<Local>,
+    pub sites: HashMap<String, SiteTotals>,
+    /// Down and Warning results by broad cause, e.g. "Timeout"
+    pub failure_kinds: HashMap<&'static str, u64>,
+    /// Response times of Up checks across all sites, by minute, oldest first
+    pub fleet_latency: VecDeque<LatencyBucket>,
+}
+
+/// Response times of the Up checks of every site within one minute
+pub struct LatencyBucket {
+    pub minute: DateTime<Local>,
+    samples: Vec<u64>,
+}
+
+impl LatencyBucket {
+    pub fn average(&self) -> u64 {
+        self.samples.iter().sum::<u64>() / self.samples.len().max(1) as u64
+    }
+
+    /// Response time that `pct` percent of the checks came in under
+    pub fn percentile(&self, pct: usize) -> u64 {
+        let mut sorted = self.samples.clone();
+        sorted.sort_unstable();
+        let rank = (sorted.len() * pct).div_ceil(100).max(1);
+        sorted.get(rank - 1).copied().unwrap_or_default()
+    }
+}
+
+/// One site's counts since startup
+#[derive(Debug, Default)]
+pub struct SiteTotals {
+    pub checks: u64,
+    pub up: u64,
+    pub response_ms_total: u64,
+    pub responses: u64,
+    pub max_response_ms: Option<u64>,
+    /// Checks that gave up waiting, whether they counted as Down or Warning
+    pub timeouts: u64,
+    pub alerts: u64,
+    /// Alerts that were Critical, i.e. the site went Down
+    pub incidents: u64,
+}
+
+impl SiteTotals {
+    pub fn avg_response_time(&self) -> Option<u64> {
+        self.response_ms_total.checked_div(self.responses)
+    }
+}
+
+impl SessionStats {
+    pub fn new() -> Self {
+        Self {
+            started: Local::now(),
+            sites: HashMap::new(),
+            failure_kinds: HashMap::new(),
+            fleet_latency: VecDeque::new(),
+        }
+    }
+
+    /// Count a check result; skipped and rate-limited checks aren't counted
+    pub fn record_check(&mut self, site_name: &str, result: &CheckResult, expected_status: u16) {
+        if !result.status.is_conclusive() {
+            return;
+        }
+        let totals = self.sites.entry(site_name.to_string()).or_default();
+        totals.checks += 1;
+        if result.status == Status::Up {
+            totals.up += 1;
+        } else if result.timed_out() {
+            totals.timeouts += 1;
+        }
+        if let Some(ms) = result.response_time_ms {
+            totals.response_ms_total += ms;
+            totals.responses += 1;
+            totals.max_response_ms = totals.max_response_ms.max(Some(ms));
+            if result.status == Status::Up {
+                self.record_latency(result.timestamp.with_timezone(&Local), ms);
+            }
+        }
+        if let Some(kind) = result.failure_kind(expected_status) {
+            *self.failure_kinds.entry(kind).or_default() += 1;
+        }
+    }
+
+    /// Add a response time to the fleet latency of its minute
+    fn record_latency(&mut self, at: DateTime<Local>, ms: u64) {
+        let minute = at.with_second(0).and_then(|t| t.with_nanosecond(0)).unwrap_or(at);
+        // Results can arrive a little out of order, so look back for their minute
+        if let Some(bucket) = self.fleet_latency.iter_mut().rev().find(|b| b.minute <= minute)
+            && bucket.minute == minute
+        {
+            bucket.samples.push(ms);
+            return;
+        }
+        let position = self.fleet_latency.partition_point(|b| b.minute < minute);
+        self.fleet_latency.insert(position, LatencyBucket { minute, samples: vec![ms] });
+        while self.fleet_latency.len() > FLEET_LATENCY_MINUTES {
+            self.fleet_latency.pop_front();
+        }
+    }
+
+    pub fn record_alert(&mut self, site_name: &str, severity: &AlertSeverity) {
+        let totals = self.sites.entry(site_name.to_string()).or_default();
+        totals.alerts += 1;
+        if *severity == AlertSeverity::Critical {
+            totals.incidents += 1;
+        }
+    }
+
+    pub fn total_checks(&self) -> u64 {
+        self.sites.values().map(|t| t.checks).sum()
+    }
+
+    pub fn total_timeouts(&self) -> u64 {
+        self.sites.values().map(|t| t.timeouts).sum()
+    }
+
+    /// Share of all checks that found their site Up, as a percentage
+    pub fn availability(&self) -> Option<f64> {
+        let checks = self.total_checks();
+        let up: u64 = self.sites.values().map(|t| t.up).sum();
+        (checks > 0).then(|| up as f64 / checks as f64 * 100.0)
+    }
+
+    /// Up to `limit` sites with the highest average response time, slowest first
+    pub fn slowest(&self, limit: usize) -> Vec<(&str, u64)> {
+        let mut sites: Vec<(&str, u64)> = self
+            .sites
+            .iter()
+            .filter_map(|(name, t)| Some((name.as_str(), t.avg_response_time()?)))
+            .collect();
+        sites.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        sites.truncate(limit);
+        sites
+    }
+
+    /// Up to `limit` sites that raised the most alerts, most first
+    pub fn most_alerting(&self, limit: usize) -> Vec<(&str, u64)> {
+        let mut sites: Vec<(&str, u64)> = self
+            .sites
+            .iter()
+            .filter(|(_, t)| t.alerts > 0)
+            .map(|(name, t)| (name.as_str(), t.alerts))
+            .collect();
+        sites.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        sites.truncate(limit);
+        sites
+    }
+
+    /// Failure kinds with their counts, most frequent first
+    pub fn failure_breakdown(&self) -> Vec<(&'static str, u64)> {
+        let mut kinds: Vec<(&'static str, u64)> = self.failure_kinds.iter().map(|(k, n)| (*k, *n)).collect();
+        kinds.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        kinds
+    }
+}
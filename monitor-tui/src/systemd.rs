@@ -0,0 +1,100 @@
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+use std::time::{Duration, Instant};
+
+/// Minimal sd_notify client for running under a `Type=notify` systemd unit.
+///
+/// Does nothing unless systemd passed `NOTIFY_SOCKET`. When the unit sets
+/// `WatchdogSec=`, `watchdog_tick` must be called from the main loop so a
+/// stalled loop stops pinging and systemd restarts the service.
+pub struct SystemdNotifier {
+    socket_path: Option<String>,
+    watchdog_interval: Option<Duration>,
+    last_ping: Instant,
+}
+
+impl SystemdNotifier {
+    pub fn from_env() -> Self {
+        let socket_path = std::env::var("NOTIFY_SOCKET").ok().filter(|s| !s.is_empty());
+
+        // WATCHDOG_PID, when present, must name this process
+        let watchdog_for_us = std::env::var("WATCHDOG_PID")
+            .ok()
+            .and_then(|pid| pid.parse::<u32>().ok())
+            .is_none_or(|pid| pid == std::process::id());
+
+        // Ping at half the configured timeout, as recommended by sd_watchdog_enabled(3)
+        let watchdog_interval = std::env::var("WATCHDOG_USEC")
+            .ok()
+            .and_then(|usec| usec.parse::<u64>().ok())
+            .filter(|&usec| usec > 0 && watchdog_for_us)
+            .map(|usec| Duration::from_micros(usec / 2));
+
+        Self {
+            socket_path,
+            watchdog_interval,
+            last_ping: Instant::now(),
+        }
+    }
+
+    /// Tell systemd startup is complete
+    pub fn ready(&self) {
+        self.send("READY=1");
+    }
+
+    /// Tell systemd a clean shutdown is in progress
+    pub fn stopping(&self) {
+        self.send("STOPPING=1");
+    }
+
+    /// How often the watchdog must be pinged, if systemd enabled it
+    pub fn watchdog_interval(&self) -> Option<Duration> {
+        self.watchdog_interval
+    }
+
+    /// Ping the watchdog if the interval has elapsed; call on every loop iteration
+    pub fn watchdog_tick(&mut self) {
+        if let Some(interval) = self.watchdog_interval
+            && self.last_ping.elapsed() >= interval
+        {
+            self.send("WATCHDOG=1");
+            self.last_ping = Instant::now();
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn send(&self, _state: &str) {}
+
+    #[cfg(unix)]
+    fn send(&self, state: &str) {
+        let Some(path) = &self.socket_path else {
+            return;
+        };
+
+        let Ok(socket) = UnixDatagram::unbound() else {
+            return;
+        };
+
+        // Notifications are best-effort; never take the monitor down over them.
+        // A leading '@' means a Linux abstract namespace socket.
+        let _ = if let Some(name) = path.strip_prefix('@') {
+            Self::send_abstract(&socket, name, state)
+        } else {
+            socket.send_to(state.as_bytes(), path).map(|_| ())
+        };
+    }
+
+    #[cfg(target_os = "linux")]
+    fn send_abstract(socket: &UnixDatagram, name: &str, state: &str) -> std::io::Result<()> {
+        use std::os::linux::net::SocketAddrExt;
+        use std::os::unix::net::SocketAddr;
+
+        let addr = SocketAddr::from_abstract_name(name.as_bytes())?;
+        socket.send_to_addr(state.as_bytes(), &addr).map(|_| ())
+    }
+
+    #[cfg(all(unix, not(target_os = "linux")))]
+    fn send_abstract(_socket: &UnixDatagram, _name: &str, _state: &str) -> std::io::Result<()> {
+        Ok(())
+    }
+}
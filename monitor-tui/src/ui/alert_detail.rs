@@ -1,6 +1,7 @@
 use crate::alerts::{Alert, AlertSeverity};
 use crate::app::App;
 use crate::checker::Status;
+use crate::i18n::t;
 use crate::ui::theme::Theme;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -12,14 +13,8 @@ use ratatui::{
 
 /// Render the alert detail view
 pub fn render_alert_detail(frame: &mut Frame, app: &App, alert_index: usize) {
-    // Convert reversed display index to original VecDeque index
-    // Display shows alerts in reverse order (most recent first)
-    // Index 0 in display = last alert in VecDeque (index N-1)
-    let total_alerts = app.alert_history.len();
-    let original_index = total_alerts.saturating_sub(1).saturating_sub(alert_index);
-
-    // Try to retrieve the alert using the original index
-    let alert = match app.alert_history.all_alerts().get(original_index) {
+    // The index is a position in the alerts view's list, most recent first
+    let alert = match app.listed_alerts().get(alert_index).copied() {
         Some(a) => a,
         None => {
             // Invalid index - show error message
@@ -31,11 +26,25 @@ pub fn render_alert_detail(frame: &mut Frame, app: &App, alert_index: usize) {
     // Determine if we need an error bar
     let has_error = app.error_message.is_some();
 
+    // Diagnostics get a heading line, a blank line, and one line per finding
+    let details_height = if alert.diagnostics.is_empty() {
+        6
+    } else {
+        8 + alert.diagnostics.len() as u16
+    };
+    let details_height = details_height + alert.check_context().len() as u16;
+    // A comment, or one being typed, gets a blank line and its own
+    let details_height = if alert.comment.is_some() || app.comment_input.is_some() {
+        details_height + 2
+    } else {
+        details_height
+    };
+
     let constraints = if has_error {
         vec![
             Constraint::Length(3),  // Header
             Constraint::Length(10), // Alert info & status change
-            Constraint::Length(6),  // Timestamp & message
+            Constraint::Length(details_height), // Timestamp, message & diagnostics
             Constraint::Min(5),     // Related site info
             Constraint::Length(1),  // Error bar
             Constraint::Length(1),  // Footer
@@ -44,7 +53,7 @@ pub fn render_alert_detail(frame: &mut Frame, app: &App, alert_index: usize) {
         vec![
             Constraint::Length(3),  // Header
             Constraint::Length(10), // Alert info & status change
-            Constraint::Length(6),  // Timestamp & message
+            Constraint::Length(details_height), // Timestamp, message & diagnostics
             Constraint::Min(5),     // Related site info
             Constraint::Length(1),  // Footer
         ]
@@ -98,12 +107,14 @@ fn render_header(frame: &mut Frame, app: &App, alert: &Alert, area: Rect) {
         AlertSeverity::Critical => "CRITICAL ALERT",
         AlertSeverity::Warning => "WARNING ALERT",
         AlertSeverity::Recovery => "RECOVERY ALERT",
+        AlertSeverity::Info => "INFO ALERT",
     };
 
     let severity_color = match alert.severity {
         AlertSeverity::Critical => theme.alert_critical,
         AlertSeverity::Warning => theme.alert_warning,
         AlertSeverity::Recovery => theme.alert_recovery,
+        AlertSeverity::Info => theme.alert_info,
     };
 
     let timestamp_str = alert.timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string();
@@ -128,6 +139,10 @@ fn render_alert_info(frame: &mut Frame, app: &App, alert: &Alert, area: Rect) {
     let theme = &app.theme;
     let (prev_color, prev_text) = status_display(theme, &alert.previous_status);
     let (curr_color, curr_text) = status_display(theme, &alert.current_status);
+    let acknowledged = match alert.acknowledged_at.filter(|_| alert.acknowledged) {
+        Some(at) => Span::styled(at.format("%Y-%m-%d %H:%M:%S UTC").to_string(), Style::default().fg(theme.status_up)),
+        None => Span::styled("no (A acknowledges it)", Style::default().fg(theme.text_muted)),
+    };
 
     let lines = vec![
         Line::from(vec![
@@ -162,6 +177,16 @@ fn render_alert_info(frame: &mut Frame, app: &App, alert: &Alert, area: Rect) {
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                "Acknowledged: ",
+                Style::default()
+                    .fg(theme.text_primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            acknowledged,
+        ]),
     ];
 
     let info = Paragraph::new(lines).block(
@@ -178,7 +203,7 @@ fn render_alert_info(frame: &mut Frame, app: &App, alert: &Alert, area: Rect) {
 fn render_message(frame: &mut Frame, app: &App, alert: &Alert, area: Rect) {
     let theme = &app.theme;
 
-    let lines = vec![
+    let mut lines = vec![
         Line::from(vec![
             Span::styled(
                 "Timestamp: ",
@@ -202,6 +227,64 @@ fn render_message(frame: &mut Frame, app: &App, alert: &Alert, area: Rect) {
             Span::styled(&alert.message, Style::default().fg(theme.text_secondary)),
         ]),
     ];
+    // What the check saw: a summary, the error and the start of the body
+    for line in alert.check_context() {
+        let (label, value) = line.split_once(": ").unwrap_or(("", &line));
+        let color = if label == "Error" { theme.status_down } else { theme.text_secondary };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{}: ", label),
+                Style::default()
+                    .fg(theme.text_primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(value.to_string(), Style::default().fg(color)),
+        ]));
+    }
+
+    let comment = match &app.comment_input {
+        Some(input) => Some(Line::from(vec![
+            Span::styled(
+                "Comment: ",
+                Style::default()
+                    .fg(theme.text_primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(input.text.as_str(), Style::default().fg(theme.text_primary)),
+            Span::styled("█", Style::default().fg(theme.text_muted)),
+        ])),
+        None => alert.comment.as_ref().map(|comment| {
+            Line::from(vec![
+                Span::styled(
+                    "Comment: ",
+                    Style::default()
+                        .fg(theme.text_primary)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(comment.as_str(), Style::default().fg(theme.text_secondary)),
+            ])
+        }),
+    };
+    if let Some(comment) = comment {
+        lines.push(Line::from(""));
+        lines.push(comment);
+    }
+
+    if !alert.diagnostics.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            "Diagnostics:",
+            Style::default()
+                .fg(theme.text_primary)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        for finding in &alert.diagnostics {
+            lines.push(Line::from(vec![Span::styled(
+                format!("  {}", finding),
+                Style::default().fg(theme.text_secondary),
+            )]));
+        }
+    }
 
     let message = Paragraph::new(lines).block(
         Block::default()
@@ -217,8 +300,11 @@ fn render_message(frame: &mut Frame, app: &App, alert: &Alert, area: Rect) {
 fn render_site_info(frame: &mut Frame, app: &App, alert: &Alert, area: Rect) {
     let theme = &app.theme;
 
-    let lines = if let Some(history) = app.sites.get(&alert.site_name) {
-        let uptime = history.uptime_percentage();
+    let mut lines = if let Some(history) = app.sites.get(&alert.site_name) {
+        let uptime = history
+            .uptime(app.uptime_window)
+            .map(|uptime| format!("{:.1}%", uptime))
+            .unwrap_or_else(|| "N/A".to_string());
         let avg_response = history.avg_response_time();
 
         let avg_response_str = if let Some(avg) = avg_response {
@@ -236,8 +322,8 @@ fn render_site_info(frame: &mut Frame, app: &App, alert: &Alert, area: Rect) {
             )]),
             Line::from(""),
             Line::from(vec![
-                Span::styled("  Uptime: ", Style::default().fg(theme.text_secondary)),
-                Span::styled(format!("{:.1}%", uptime), Style::default().fg(theme.status_up)),
+                Span::styled(format!("  Uptime ({}): ", app.uptime_window.label()), Style::default().fg(theme.text_secondary)),
+                Span::styled(uptime, Style::default().fg(theme.status_up)),
             ]),
             Line::from(vec![
                 Span::styled("  Avg Response Time: ", Style::default().fg(theme.text_secondary)),
@@ -264,6 +350,17 @@ fn render_site_info(frame: &mut Frame, app: &App, alert: &Alert, area: Rect) {
         ]
     };
 
+    // Description, owner and runbook as they were when the alert fired
+    if !alert.notes.is_empty() {
+        lines.push(Line::from(""));
+        for note in &alert.notes {
+            lines.push(Line::from(vec![Span::styled(
+                format!("  {}", note),
+                Style::default().fg(theme.text_secondary),
+            )]));
+        }
+    }
+
     let site_info = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
@@ -286,8 +383,12 @@ fn render_error_bar(frame: &mut Frame, app: &App, area: Rect) {
 /// Render footer with keyboard shortcuts
 fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
     let theme = &app.theme;
-    let footer_text = " ESC: Back to Alerts | a: Alerts List | r: Refresh | ?/h: Help | q: Quit";
-    let footer = Paragraph::new(footer_text).style(Style::default().fg(theme.footer_fg));
+    let footer_text = if app.comment_input.is_some() {
+        t("footer.comment")
+    } else {
+        t("footer.alert_detail")
+    };
+    let footer = Paragraph::new(format!(" {}", footer_text)).style(Style::default().fg(theme.footer_fg));
     frame.render_widget(footer, area);
 }
 
@@ -297,6 +398,8 @@ fn status_display(theme: &Theme, status: &Status) -> (ratatui::style::Color, &'s
         Status::Up => (theme.status_up, "UP"),
         Status::Down => (theme.status_down, "DOWN"),
         Status::Warning => (theme.status_warning, "WARNING"),
+        Status::Skipped => (theme.status_unknown, "SKIPPED"),
+        Status::RateLimited => (theme.status_warning, "RATE LIMITED"),
     }
 }
 
@@ -0,0 +1,165 @@
+use crate::alerts::AlertStats;
+use crate::app::App;
+use crate::i18n::t;
+use crate::ui::format_duration;
+use crate::ui::status_bar::render_status_bar;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{BarChart, Block, Borders, Paragraph},
+    Frame,
+};
+
+/// Days shown in the alerts-per-day chart
+const CHART_DAYS: u32 = 14;
+
+/// Recurring errors listed
+const TOP_ERRORS: usize = 10;
+
+/// Render aggregates over the alert history
+pub fn render_alert_stats(frame: &mut Frame, app: &App) {
+    let stats = app.alert_history.stats(CHART_DAYS);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Panels
+            Constraint::Length(1), // Status bar
+            Constraint::Length(1), // Footer
+        ])
+        .split(frame.size());
+
+    render_header(frame, app, &stats, chunks[0]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(12), Constraint::Min(0)])
+        .split(columns[1]);
+
+    render_sites(frame, app, &stats, columns[0]);
+    render_per_day(frame, app, &stats, right[0]);
+    render_top_errors(frame, app, &stats, right[1]);
+
+    render_status_bar(frame, app, chunks[2]);
+    render_footer(frame, app, chunks[3]);
+}
+
+fn render_header(frame: &mut Frame, app: &App, stats: &AlertStats, area: Rect) {
+    let theme = &app.theme;
+    let mut header_text = format!("Alert Statistics ({} alerts", app.alert_history.len());
+    if let Some(since) = stats.since {
+        header_text.push_str(&format!(" since {}", since.format("%Y-%m-%d %H:%M")));
+    }
+    header_text.push(')');
+    if let Some(mtbf) = stats.mtbf {
+        header_text.push_str(&format!("  |  Fleet MTBF: {}", format_duration(mtbf)));
+    }
+    header_text.push_str("          Press ESC to return");
+
+    let header = Paragraph::new(header_text)
+        .block(Block::default().borders(Borders::ALL))
+        .style(theme.header_style());
+
+    frame.render_widget(header, area);
+}
+
+/// Alerts per site, most first, with how often each went down
+fn render_sites(frame: &mut Frame, app: &App, stats: &AlertStats, area: Rect) {
+    let theme = &app.theme;
+    let lines: Vec<Line> = if stats.sites.is_empty() {
+        vec![Line::from(Span::styled(" No alerts yet", Style::default().fg(theme.text_muted)))]
+    } else {
+        let heading = Line::from(Span::styled(
+            format!(" {:<24} {:>6} {:>6} {:>6} {:>9}", "Site", "Alerts", "Down", "Warn", "MTBF"),
+            Style::default().fg(theme.text_muted).add_modifier(Modifier::BOLD),
+        ));
+        std::iter::once(heading)
+            .chain(stats.sites.iter().map(|site| {
+                let name: String = site.site_name.chars().take(24).collect();
+                Line::from(vec![
+                    Span::styled(format!(" {:<24} {:>6}", name, site.total), Style::default().fg(theme.text_primary)),
+                    Span::styled(format!(" {:>6}", site.failures), Style::default().fg(theme.status_down)),
+                    Span::styled(format!(" {:>6}", site.warnings), Style::default().fg(theme.status_warning)),
+                    Span::styled(
+                        format!(" {:>9}", site.mtbf.map_or("--".to_string(), format_duration)),
+                        Style::default().fg(theme.text_secondary),
+                    ),
+                ])
+            }))
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Alerts per Site")
+            .border_style(Style::default().fg(theme.border_fg)),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+/// Bar per day, labelled with the day of the month
+fn render_per_day(frame: &mut Frame, app: &App, stats: &AlertStats, area: Rect) {
+    let theme = &app.theme;
+    let labels: Vec<String> = stats.per_day.iter().map(|(day, _)| day.format("%d").to_string()).collect();
+    let data: Vec<(&str, u64)> = labels
+        .iter()
+        .zip(&stats.per_day)
+        .map(|(label, (_, count))| (label.as_str(), *count))
+        .collect();
+
+    let chart = BarChart::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Alerts per Day (last {} days)", CHART_DAYS))
+                .border_style(Style::default().fg(theme.border_fg)),
+        )
+        .data(&data)
+        .bar_width(3)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(theme.status_warning))
+        .value_style(Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD))
+        .label_style(Style::default().fg(theme.text_muted));
+    frame.render_widget(chart, area);
+}
+
+/// Errors behind the alerts, most frequent first
+fn render_top_errors(frame: &mut Frame, app: &App, stats: &AlertStats, area: Rect) {
+    let theme = &app.theme;
+    let lines: Vec<Line> = if stats.top_errors.is_empty() {
+        vec![Line::from(Span::styled(" No errors recorded", Style::default().fg(theme.text_muted)))]
+    } else {
+        stats
+            .top_errors
+            .iter()
+            .take(TOP_ERRORS)
+            .map(|(error, count)| {
+                Line::from(vec![
+                    Span::styled(format!(" {:>4}× ", count), Style::default().fg(theme.status_down)),
+                    Span::styled(error.clone(), Style::default().fg(theme.text_primary)),
+                ])
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Top Recurring Errors")
+            .border_style(Style::default().fg(theme.border_fg)),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let footer = Paragraph::new(format!(" {}", t("footer.alert_stats")))
+        .style(Style::default().fg(theme.footer_fg));
+    frame.render_widget(footer, area);
+}
@@ -1,6 +1,8 @@
 use crate::alerts::AlertSeverity;
-use crate::app::App;
+use crate::app::{App, ClickTarget};
+use crate::i18n::t;
 use crate::ui::status_bar::render_status_bar;
+use crate::ui::{list_item_rect, scroll_offset};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -31,10 +33,13 @@ pub fn render_alerts(frame: &mut Frame, app: &App) {
 fn render_header(frame: &mut Frame, app: &App, area: Rect) {
     let theme = &app.theme;
     let total_alerts = app.alert_history.len();
-    let header_text = format!(
-        "Alert History ({} total)          Press ESC to return",
-        total_alerts
-    );
+    let acknowledged = app.alert_history.acknowledged_count();
+    let counts = match (acknowledged, app.hide_acknowledged) {
+        (0, _) => format!("{} total", total_alerts),
+        (n, true) => format!("{} total, {} acknowledged hidden", total_alerts, n),
+        (n, false) => format!("{} total, {} acknowledged", total_alerts, n),
+    };
+    let header_text = format!("Alert History ({})          Press ESC to return", counts);
 
     let header = Paragraph::new(header_text)
         .block(Block::default().borders(Borders::ALL))
@@ -45,10 +50,11 @@ fn render_header(frame: &mut Frame, app: &App, area: Rect) {
 
 fn render_alert_list(frame: &mut Frame, app: &App, area: Rect) {
     let theme = &app.theme;
-    let alerts = app.alert_history.all_alerts();
+    let alerts = app.listed_alerts();
 
     if alerts.is_empty() {
-        let empty_message = Paragraph::new("No alerts yet")
+        let text = if app.alert_history.is_empty() { "No alerts yet" } else { "Every alert is acknowledged (F shows them)" };
+        let empty_message = Paragraph::new(text)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -60,16 +66,25 @@ fn render_alert_list(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
+    // Each alert takes two lines; scroll to keep the selection in view
+    let visible_rows = (area.height.saturating_sub(2) as usize / 2).max(1);
+    let offset = scroll_offset(app.alert_scroll.get(), app.alert_selected_index, visible_rows, alerts.len());
+    app.alert_scroll.set(offset);
+
     let items: Vec<ListItem> = alerts
         .iter()
-        .rev() // Most recent first
         .enumerate()
+        .skip(offset)
+        .take(visible_rows)
         .map(|(idx, alert)| {
+            app.register_click(list_item_rect(area, idx - offset, 2), ClickTarget::Alert(idx));
+
             // Color based on severity
             let (color, severity_text) = match alert.severity {
                 AlertSeverity::Critical => (theme.alert_critical, "CRITICAL"),
                 AlertSeverity::Warning => (theme.alert_warning, "WARNING "),
                 AlertSeverity::Recovery => (theme.alert_recovery, "RECOVERY"),
+                AlertSeverity::Info => (theme.alert_info, "INFO    "),
             };
 
             let timestamp = alert.timestamp.format("%Y-%m-%d %H:%M:%S").to_string();
@@ -86,10 +101,31 @@ fn render_alert_list(frame: &mut Frame, app: &App, area: Rect) {
                 ),
             ]);
 
-            let line2 = Line::from(vec![Span::styled(
+            let mut line2 = vec![Span::styled(
                 format!("  {}", alert.message),
                 Style::default().fg(theme.text_secondary),
-            )]);
+            )];
+            if alert.silenced {
+                line2.push(Span::styled("  (silenced)", Style::default().fg(theme.text_muted)));
+            }
+            if let Some(comment) = &alert.comment {
+                line2.push(Span::styled(
+                    format!("  — {}", comment),
+                    Style::default().fg(theme.text_muted),
+                ));
+            }
+            let mut lines = vec![line1, Line::from(line2)];
+
+            // Handled alerts recede so the ones still needing attention stand out
+            if let Some(at) = alert.acknowledged_at.filter(|_| alert.acknowledged) {
+                for span in lines.iter_mut().flat_map(|line| line.spans.iter_mut()) {
+                    span.style = span.style.fg(theme.text_muted).remove_modifier(Modifier::BOLD);
+                }
+                lines[0].spans.push(Span::styled(
+                    format!("  ✔ acknowledged {}", at.format("%H:%M:%S")),
+                    Style::default().fg(theme.text_muted),
+                ));
+            }
 
             // Apply selection highlighting
             let style = if app.alert_selected_index == Some(idx) {
@@ -98,7 +134,7 @@ fn render_alert_list(frame: &mut Frame, app: &App, area: Rect) {
                 Style::default()
             };
 
-            ListItem::new(vec![line1, line2]).style(style)
+            ListItem::new(lines).style(style)
         })
         .collect();
 
@@ -124,7 +160,9 @@ fn render_alert_summary(frame: &mut Frame, app: &App, area: Rect) {
         .by_severity(AlertSeverity::Recovery)
         .len();
 
-    let summary = Line::from(vec![
+    let info_count = app.alert_history.by_severity(AlertSeverity::Info).len();
+
+    let mut summary = vec![
         Span::styled("Summary:  ", Style::default().fg(theme.text_primary)),
         Span::styled(
             format!("{} Critical", critical_count),
@@ -146,7 +184,26 @@ fn render_alert_summary(frame: &mut Frame, app: &App, area: Rect) {
                 .fg(theme.alert_recovery)
                 .add_modifier(Modifier::BOLD),
         ),
-    ]);
+    ];
+    // Only configured severity mappings produce info alerts
+    if info_count > 0 {
+        summary.push(Span::styled("  |  ", Style::default().fg(theme.text_muted)));
+        summary.push(Span::styled(
+            format!("{} Info", info_count),
+            Style::default()
+                .fg(theme.alert_info)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    let silences = app.active_silences().len();
+    if silences > 0 {
+        summary.push(Span::styled("  |  ", Style::default().fg(theme.text_muted)));
+        summary.push(Span::styled(
+            format!("{} silence{} active (m/M)", silences, if silences == 1 { "" } else { "s" }),
+            Style::default().fg(theme.text_muted),
+        ));
+    }
+    let summary = Line::from(summary);
 
     let paragraph = Paragraph::new(summary).block(
         Block::default()
@@ -159,10 +216,7 @@ fn render_alert_summary(frame: &mut Frame, app: &App, area: Rect) {
 
 fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
     let theme = &app.theme;
-    let footer = Paragraph::new(
-        " ↑↓: Navigate | Enter: Details | ESC: Dashboard | r: Refresh | ?/h: Help | q: Quit",
-    )
-    .style(Style::default().fg(theme.footer_fg));
+    let footer = Paragraph::new(format!(" {}", t("footer.alerts"))).style(Style::default().fg(theme.footer_fg));
 
     frame.render_widget(footer, area);
 }
@@ -0,0 +1,56 @@
+use crate::app::{App, Confirmation};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Widest the dialog gets, in columns
+const MAX_WIDTH: u16 = 60;
+
+/// Draw a yes/no question centered over the current view
+pub fn render_confirmation(frame: &mut Frame, app: &App, confirmation: &Confirmation) {
+    let theme = &app.theme;
+    let screen = frame.size();
+    let width = MAX_WIDTH.min(screen.width);
+    let prompt_lines = (confirmation.prompt.chars().count() as u16).div_ceil(width.saturating_sub(4).max(1));
+    // Borders, padding, the prompt and the answer keys
+    let height = (prompt_lines + 5).min(screen.height);
+    let area = Rect {
+        x: screen.x + (screen.width - width) / 2,
+        y: screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    };
+
+    let key = Style::default().fg(theme.status_up).add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(theme.text_primary);
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(confirmation.prompt.as_str(), text)),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("y", key),
+            Span::styled(": Yes    ", text),
+            Span::styled("n", key),
+            Span::styled(" / ", text),
+            Span::styled("ESC", key),
+            Span::styled(": No", text),
+        ]),
+    ];
+
+    let dialog = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Confirm ")
+                .border_style(Style::default().fg(theme.status_warning).add_modifier(Modifier::BOLD)),
+        );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(dialog, area);
+}
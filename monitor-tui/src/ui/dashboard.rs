@@ -1,6 +1,11 @@
-use crate::app::App;
+use crate::app::{App, ClickTarget};
+use crate::config::{DownCheckStyle, SiteKind};
+use crate::history::SiteHistory;
+use crate::i18n::t;
 use crate::ui::status_bar::render_status_bar;
-use crate::ui::theme::ResponsiveLayout;
+use crate::ui::theme::{ResponsiveLayout, ThemeName};
+use crate::ui::{list_item_rect, scroll_offset, streak_text};
+use chrono::{DateTime, Local, Utc};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::Style,
@@ -8,6 +13,7 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
+use std::time::Duration;
 
 /// Render the main dashboard view
 pub fn render_dashboard(frame: &mut Frame, app: &App) {
@@ -51,128 +57,161 @@ pub fn render_dashboard(frame: &mut Frame, app: &App) {
 fn render_header(frame: &mut Frame, app: &App, area: Rect) {
     let theme = &app.theme;
     let last_update_str = app.last_update.format("%Y-%m-%d %H:%M:%S UTC").to_string();
-    let header_text = format!("Monitor TUI          Last Update: {}", last_update_str);
+    // While paused, the header becomes a banner so stale statuses aren't mistaken for live ones
+    let (header_text, style) = match app.paused_since {
+        Some(since) => (
+            format!(
+                "Monitor TUI          CHECKS PAUSED since {} - press P to resume",
+                since.format("%H:%M:%S")
+            ),
+            theme.error_style(),
+        ),
+        None => {
+            let checking = match app.checking_count() {
+                0 => String::new(),
+                1 => "   (checking 1 site)".to_string(),
+                n => format!("   (checking {} sites)", n),
+            };
+            (format!("Monitor TUI          Last Update: {}{}", last_update_str, checking), theme.header_style())
+        }
+    };
 
     let header = Paragraph::new(header_text)
         .block(Block::default().borders(Borders::ALL))
-        .style(theme.header_style());
+        .style(style);
 
     frame.render_widget(header, area);
 }
 
+/// Frames of the in-flight indicator, one per second a check is out
+const SPINNER: [char; 4] = ['◐', '◓', '◑', '◒'];
+
+/// A dashboard row as last built, reused until its inputs change
+pub struct CachedRow {
+    key: RowKey,
+    lines: Vec<Line<'static>>,
+    /// Whether the first line ends with how long the status has held, added
+    /// each frame since it counts up with the clock
+    streak: bool,
+}
+
+/// Everything a row's content depends on besides static config
+type RowKey = (
+    Option<DateTime<Utc>>,
+    u16,
+    ThemeName,
+    Option<String>,
+    Option<u8>,
+    Option<DateTime<Local>>,
+    bool,
+    bool,
+    Option<(Duration, bool)>,
+);
+
 /// Render the list of sites with their status
 fn render_site_list(frame: &mut Frame, app: &App, area: Rect) {
     let theme = &app.theme;
     let responsive = ResponsiveLayout::new(area.width);
 
-    let items: Vec<ListItem> = app
-        .sites
-        .iter()
+    // Only build rows that fit; large fleets scroll instead of rendering everything
+    let lines_per_item = responsive.lines_per_site_item() as usize;
+    let visible_rows = (area.height.saturating_sub(2) as usize / lines_per_item).max(1);
+    let order = app.dashboard_order();
+    let selected_row = app.dashboard_position(&order);
+    let offset = scroll_offset(app.dashboard_scroll.get(), selected_row, visible_rows, order.len());
+    app.dashboard_scroll.set(offset);
+
+    let visible = || {
+        order.iter().skip(offset).take(visible_rows).filter_map(|&(index, depth)| {
+            let (site_name, history) = app.sites.get_index(index)?;
+            Some((index, depth, site_name, history))
+        })
+    };
+    let has_composites = app.config.sites.iter().any(|s| s.kind == SiteKind::Composite);
+    let has_pinned = app.pinned.iter().any(|name| app.sites.contains_key(name));
+    let has_marked = !app.marked.is_empty();
+
+    // Rebuild only rows whose latest result, width or theme changed
+    let mut cache = app.dashboard_rows.borrow_mut();
+    for (_, _, site_name, history) in visible() {
+        let blocked_by = app.blocked_by(site_name).map(str::to_string);
+        let score = app.health_score(site_name);
+        let stalled = app.stalled_since(site_name);
+        let stale = app.stale_since(site_name).is_some();
+        let paused = app.site_paused(site_name);
+        let interval = app.site_interval(site_name);
+        let key = (
+            history.latest().map(|r| r.timestamp),
+            area.width,
+            app.theme_name,
+            blocked_by,
+            score,
+            stalled,
+            stale,
+            paused,
+            interval,
+        );
+        if cache.get(site_name).is_none_or(|row| row.key != key) {
+            let (lines, streak) = build_site_lines(app, site_name, history, &responsive);
+            cache.insert(site_name.clone(), CachedRow { key, lines, streak });
+        }
+    }
+
+    let items: Vec<ListItem> = visible()
         .enumerate()
-        .map(|(idx, (site_name, history))| {
-            let latest = history.latest();
-
-            // Determine status color and text
-            let (status_color, status_text) = if let Some(result) = latest {
-                let color = theme.status_color(&result.status);
-                let text = match result.status {
-                    crate::checker::Status::Up => "UP  ",
-                    crate::checker::Status::Down => "DOWN",
-                    crate::checker::Status::Warning => "WARN",
-                };
-                (color, text)
-            } else {
-                (theme.status_unknown, "----")
-            };
+        .map(|(row, (idx, depth, site_name, history))| {
+            let row_area = list_item_rect(area, row, lines_per_item as u16);
+            app.register_click(row_area, ClickTarget::Site(idx));
 
-            // Get metrics
-            let response_time_str = latest
-                .and_then(|r| r.response_time_ms)
-                .map(|ms| format!("{}ms", ms))
-                .unwrap_or_else(|| "--".to_string());
+            let cached = &cache[site_name];
+            let mut lines: Vec<Line> = cached.lines.iter().map(borrow_line).collect();
+            if cached.streak
+                && let Some(streak) = streak_text(history)
+                && let Some(line) = lines.first_mut()
+            {
+                line.spans.push(Span::styled(format!("  {}", streak), Style::default().fg(theme.text_muted)));
+            }
 
-            let http_status_str = latest
-                .and_then(|r| r.http_status)
-                .map(|s| s.to_string())
-                .unwrap_or_else(|| "--".to_string());
+            // A site with a check out spins in place of its status dot
+            if let Some(started) = app.checking_since(site_name)
+                && let Some(dot) = lines.first_mut().and_then(|line| line.spans.first_mut())
+            {
+                let frame = SPINNER[started.elapsed().as_secs() as usize % SPINNER.len()];
+                *dot = Span::styled(format!("{} ", frame), dot.style);
+            }
 
-            let uptime = if history.len() > 0 {
-                format!("{:.1}%", history.uptime_percentage())
-            } else {
-                "--".to_string()
-            };
+            // Composites get an expand marker and their members an indent
+            if has_composites {
+                let is_composite = app.config.site(site_name).is_some_and(|s| s.kind == SiteKind::Composite);
+                for (i, line) in lines.iter_mut().enumerate() {
+                    let prefix = match (is_composite, i) {
+                        (true, 0) if app.expanded.contains(site_name) => "▾ ".to_string(),
+                        (true, 0) => "▸ ".to_string(),
+                        _ => "  ".to_string(),
+                    };
+                    line.spans.insert(0, Span::raw(format!("{}{}", "  ".repeat(depth), prefix)));
+                }
+            }
 
-            // Get URL from config
-            let url = app
-                .config
-                .sites
-                .iter()
-                .find(|s| &s.name == site_name)
-                .map(|s| s.url.as_str())
-                .unwrap_or("");
-
-            // Truncate based on terminal width
-            let name_width = responsive.site_name_width();
-            let display_name = ResponsiveLayout::truncate(site_name, name_width);
-            let display_url = ResponsiveLayout::truncate(url, responsive.url_max_len());
-
-            // Build the display lines
-            let line1 = Line::from(vec![
-                Span::styled("● ", Style::default().fg(status_color)),
-                Span::raw(format!("{:width$}", display_name, width = name_width)),
-                Span::styled(
-                    format!("{:4}", status_text),
-                    Style::default().fg(status_color),
-                ),
-            ]);
-
-            let line2 = Line::from(vec![
-                Span::styled(format!("  {}", display_url), Style::default().fg(theme.text_secondary)),
-            ]);
-
-            let mut lines = vec![line1, line2];
-
-            // Add metrics line if width allows
-            if responsive.show_detailed_metrics() {
-                let line3 = Line::from(vec![
-                    Span::styled("  Response: ", Style::default().fg(theme.text_muted)),
-                    Span::styled(format!("{:>6}", response_time_str), Style::default().fg(theme.text_primary)),
-                    Span::styled("  |  HTTP: ", Style::default().fg(theme.text_muted)),
-                    Span::styled(format!("{:>3}", http_status_str), Style::default().fg(theme.text_primary)),
-                    Span::styled("  |  Uptime: ", Style::default().fg(theme.text_muted)),
-                    Span::styled(format!("{:>5}", uptime), Style::default().fg(theme.text_primary)),
-                ]);
-                lines.push(line3);
+            // Pinned sites get a star; the rest line up with them
+            if has_pinned {
+                let marker = if depth == 0 && app.pinned.contains(site_name) { "★ " } else { "  " };
+                for (i, line) in lines.iter_mut().enumerate() {
+                    line.spans.insert(0, Span::raw(if i == 0 { marker } else { "  " }));
+                }
             }
 
-            // Add sparkline if width allows
-            if responsive.show_sparkline() {
-                let sparkline_data = history.recent_response_times(30);
-                let sparkline_str = if !sparkline_data.is_empty() {
-                    let max_val = sparkline_data.iter().max().unwrap_or(&1);
-                    let min_val = sparkline_data.iter().min().unwrap_or(&0);
-                    let range = max_val.saturating_sub(*min_val).max(1);
-
-                    let chars = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
-                    let sparkline: String = sparkline_data
-                        .iter()
-                        .map(|&val| {
-                            let normalized = if range == 0 {
-                                0
-                            } else {
-                                ((val.saturating_sub(*min_val)) * (chars.len() as u64 - 1) / range) as usize
-                            };
-                            chars[normalized.min(chars.len() - 1)]
-                        })
-                        .collect();
-                    format!("  Last checks: {}", sparkline)
-                } else {
-                    "  Last checks: (no data)".to_string()
-                };
-
-                lines.push(Line::from(vec![
-                    Span::styled(sparkline_str, Style::default().fg(theme.text_secondary)),
-                ]));
+            // Marked sites get a check mark while any are marked
+            if has_marked {
+                let marker = if app.marked.contains(site_name) { "✔ " } else { "  " };
+                for (i, line) in lines.iter_mut().enumerate() {
+                    let span = if i == 0 {
+                        Span::styled(marker, Style::default().fg(theme.status_warning))
+                    } else {
+                        Span::raw("  ")
+                    };
+                    line.spans.insert(0, span);
+                }
             }
 
             // Apply selection highlighting
@@ -186,16 +225,239 @@ fn render_site_list(frame: &mut Frame, app: &App, area: Rect) {
         })
         .collect();
 
+    let mut title = if order.len() > visible_rows {
+        let last = (offset + visible_rows).min(order.len());
+        format!("Sites ({}-{} of {})", offset + 1, last, order.len())
+    } else {
+        "Sites".to_string()
+    };
+    if app.sort_by_health {
+        title.push_str(" - worst health first");
+    }
+    if has_marked {
+        title.push_str(&format!(" - {} marked", app.marked.len()));
+    }
+
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .title("Sites")
+            .title(title)
             .border_style(Style::default().fg(theme.border_fg)),
     );
 
     frame.render_widget(list, area);
 }
 
+/// View a cached line without cloning its text
+fn borrow_line<'a>(line: &'a Line<'static>) -> Line<'a> {
+    line.spans
+        .iter()
+        .map(|span| Span::styled(span.content.as_ref(), span.style))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+/// Build the text of one site row
+fn build_site_lines(
+    app: &App,
+    site_name: &str,
+    history: &SiteHistory,
+    responsive: &ResponsiveLayout,
+) -> (Vec<Line<'static>>, bool) {
+    let theme = &app.theme;
+    let latest = history.latest();
+
+    // A failing site behind a down dependency is BLOCKED rather than DOWN
+    let blocked_by = app
+        .blocked_by(site_name)
+        .filter(|_| latest.is_some_and(|r| r.status != crate::checker::Status::Up));
+
+    // Determine status color and text; an overdue result makes the last
+    // status too old to show as current
+    let stale_since = app.stale_since(site_name);
+    let (status_color, status_text) = if stale_since.is_some() {
+        (theme.status_unknown, "STALE")
+    } else if blocked_by.is_some() {
+        (theme.status_unknown, "BLOCKED")
+    } else if let Some(result) = latest {
+        let color = theme.status_color(&result.status);
+        let text = match result.status {
+            crate::checker::Status::Up => "UP  ",
+            crate::checker::Status::Down => "DOWN",
+            crate::checker::Status::Warning => "WARN",
+            crate::checker::Status::Skipped => "SKIP",
+            crate::checker::Status::RateLimited => "RATE",
+        };
+        (color, text)
+    } else {
+        (theme.status_unknown, "----")
+    };
+
+    // Get metrics
+    let response_time_str = latest
+        .and_then(|r| r.response_time_ms)
+        .map(|ms| format!("{}ms", ms))
+        .unwrap_or_else(|| "--".to_string());
+
+    let http_status_str = latest
+        .and_then(|r| r.http_status)
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "--".to_string());
+    let http_status_color = latest
+        .and_then(|r| r.http_status)
+        .and_then(|code| app.config.site(site_name)?.code_color(code))
+        .unwrap_or(theme.text_primary);
+
+    let uptime = history
+        .uptime(app.uptime_window)
+        .map(|uptime| format!("{:.1}%", uptime))
+        .unwrap_or_else(|| "--".to_string());
+
+    // Get URL from config
+    let url = app.config.site(site_name).map(|s| s.target()).unwrap_or_default();
+
+    // Truncate based on terminal width
+    let name_width = responsive.site_name_width();
+    let display_name = ResponsiveLayout::truncate(site_name, name_width);
+    let display_url = ResponsiveLayout::truncate(&url, responsive.url_max_len());
+
+    // Build the display lines
+    let mut line1 = Line::from(vec![
+        Span::styled("● ", Style::default().fg(status_color)),
+        Span::raw(format!("{:width$}", display_name, width = name_width)),
+        Span::styled(
+            format!("{:4}", status_text),
+            Style::default().fg(status_color),
+        ),
+    ]);
+
+    // Health score, colored by how much attention it calls for
+    if let Some(score) = app.health_score(site_name) {
+        let color = match score {
+            80.. => theme.status_up,
+            50.. => theme.status_warning,
+            _ => theme.status_down,
+        };
+        line1.spans.push(Span::styled(format!("  ♥{:>3}", score), Style::default().fg(color)));
+    }
+
+    // One note follows the score: a stalled check, a pause, a missing result,
+    // a down dependency or the failures counted toward an alert, whichever
+    // applies first; a row with none of them shows its status streak instead
+    let mut streak = false;
+    if let Some(since) = app.stalled_since(site_name) {
+        line1.spans.push(Span::styled(
+            format!("  check stalled since {}", since.format("%H:%M")),
+            Style::default().fg(theme.status_down),
+        ));
+    } else if app.site_paused(site_name) {
+        line1.spans.push(Span::styled("  paused", Style::default().fg(theme.text_muted)));
+    } else if let Some(since) = stale_since {
+        line1.spans.push(Span::styled(
+            format!("  no result since {}", since.format("%H:%M:%S")),
+            Style::default().fg(theme.status_unknown),
+        ));
+    } else if let Some(dependency) = blocked_by {
+        line1.spans.push(Span::styled(
+            format!("  by {}", dependency),
+            Style::default().fg(theme.text_muted),
+        ));
+    } else if let Some((failures, threshold)) = app.failure_progress(site_name) {
+        line1.spans.push(Span::styled(
+            format!("  failing {}/{}", failures, threshold),
+            Style::default().fg(theme.text_muted),
+        ));
+    } else {
+        streak = true;
+    }
+
+    let line2 = Line::from(vec![
+        Span::styled(format!("  {}", display_url), Style::default().fg(theme.text_secondary)),
+    ]);
+
+    let mut lines = vec![line1, line2];
+
+    // Add metrics line if width allows
+    if responsive.show_detailed_metrics() {
+        let mut line3 = Line::from(vec![
+            Span::styled("  Response: ", Style::default().fg(theme.text_muted)),
+            Span::styled(format!("{:>6}", response_time_str), Style::default().fg(theme.text_primary)),
+            Span::styled("  |  HTTP: ", Style::default().fg(theme.text_muted)),
+            Span::styled(format!("{:>3}", http_status_str), Style::default().fg(http_status_color)),
+            Span::styled("  |  Uptime: ", Style::default().fg(theme.text_muted)),
+            Span::styled(format!("{:>5}", uptime), Style::default().fg(theme.text_primary)),
+        ]);
+        // Intervals set with +/- stand out, being easy to forget
+        if let Some((interval, set)) = app.site_interval(site_name) {
+            let color = if set { theme.status_warning } else { theme.text_primary };
+            line3.spans.push(Span::styled("  |  Every: ", Style::default().fg(theme.text_muted)));
+            line3.spans.push(Span::styled(format!("{}s", interval.as_secs()), Style::default().fg(color)));
+        }
+        lines.push(line3);
+    }
+
+    // Add sparkline if width allows
+    if responsive.show_sparkline() {
+        lines.push(sparkline(app, history));
+    }
+
+    // Grey out a paused site, whose results are only getting older
+    if app.site_paused(site_name) {
+        for span in lines.iter_mut().flat_map(|line| line.spans.iter_mut()) {
+            span.style = span.style.fg(theme.text_muted);
+        }
+    }
+
+    (lines, streak)
+}
+
+/// "Last checks" line: one bar per check, scaled between the fastest and
+/// slowest response, with checks that got no response drawn per the chart settings
+fn sparkline(app: &App, history: &SiteHistory) -> Line<'static> {
+    let theme = &app.theme;
+    let style = &app.config.settings.chart;
+    let checks = history.recent_checks(style.sparkline_length);
+    if checks.is_empty() {
+        return Line::from(Span::styled("  Last checks: (no data)", Style::default().fg(theme.text_secondary)));
+    }
+
+    // Drawn at zero, outages count toward the scale
+    let down_value = (style.down_checks == DownCheckStyle::Zero).then_some(0);
+    let values = || checks.iter().filter_map(|&check| check.or(down_value));
+    let max_val = values().max().unwrap_or(1);
+    let min_val = values().min().unwrap_or(0);
+    let range = max_val.saturating_sub(min_val).max(1);
+
+    let chars = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let bar = |val: u64| {
+        let normalized = ((val.saturating_sub(min_val)) * (chars.len() as u64 - 1))
+            .checked_div(range)
+            .unwrap_or(0) as usize;
+        chars[normalized.min(chars.len() - 1)]
+    };
+
+    let normal = Style::default().fg(theme.text_secondary);
+    let down = Style::default().fg(theme.status_down);
+    let mut spans = vec![Span::styled("  Last checks: ", normal)];
+    let mut run = String::new();
+    let mut run_style = normal;
+    for check in checks {
+        let (c, c_style) = match (check, style.down_checks) {
+            (Some(val), _) => (bar(val), normal),
+            (None, DownCheckStyle::Gap) => (' ', normal),
+            (None, DownCheckStyle::Zero) => (bar(0), normal),
+            (None, DownCheckStyle::Series) => ('▁', down),
+        };
+        if c_style != run_style && !run.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut run), run_style));
+        }
+        run_style = c_style;
+        run.push(c);
+    }
+    spans.push(Span::styled(run, run_style));
+    Line::from(spans)
+}
+
 /// Render the error status bar
 fn render_error_bar(frame: &mut Frame, app: &App, area: Rect) {
     let theme = &app.theme;
@@ -210,7 +472,7 @@ fn render_error_bar(frame: &mut Frame, app: &App, area: Rect) {
 fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
     let theme = &app.theme;
     let footer =
-        Paragraph::new(" ↑↓: Navigate | Enter: Details | a: Alerts | s: Server | r: Refresh | ?/h: Help | q: Quit")
+        Paragraph::new(format!(" {}", t("footer.dashboard")))
             .style(Style::default().fg(theme.footer_fg));
 
     frame.render_widget(footer, area);
@@ -1,7 +1,12 @@
 use crate::app::App;
+use crate::config::{ChartMarker, DownCheckStyle, SiteConfig};
+use crate::checker::types::Revocation;
 use crate::checker::Status;
 use crate::history::SiteHistory;
+use crate::i18n::t;
+use crate::ui::graphics::ChartPlacement;
 use crate::ui::status_bar::render_status_bar;
+use crate::ui::streak_text;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -10,16 +15,38 @@ use ratatui::{
     widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, List, ListItem, Paragraph},
     Frame,
 };
+use std::time::Instant;
 
 /// Render the site detail view
 pub fn render_detail(frame: &mut Frame, app: &App, site_name: &str) {
     let has_error = app.error_message.is_some();
+    // One more line for per-location results
+    let info_height = if app.config.site(site_name).is_some_and(|s| !s.egress.is_empty()) { 9 } else { 8 };
+    // And one for the certificate's revocation status
+    let revocation = app
+        .sites
+        .get(site_name)
+        .and_then(|h| h.latest())
+        .and_then(|r| r.tls.as_ref())
+        .is_some_and(|tls| tls.revocation.is_some());
+    let info_height = info_height + revocation as u16;
+    // And one per diagnostic finding while the site is Down
+    let diagnostics = app.sites.get(site_name).and_then(|h| h.current_diagnostics()).map_or(0, |d| d.len());
+    let info_height = info_height + diagnostics as u16;
+    // And one each for the description and the owner/runbook line
+    let info_height = info_height
+        + app.config.site(site_name).map_or(0, |s| {
+            s.description.is_some() as u16 + (s.owner.is_some() || s.runbook_url.is_some()) as u16
+        });
+
+    // One more line once status codes have been seen
+    let stats_height = if app.sites.get(site_name).is_some_and(|h| !h.status_codes(app.uptime_window).is_empty()) { 6 } else { 5 };
 
     let constraints = if has_error {
         vec![
             Constraint::Length(3),  // Header
-            Constraint::Length(8),  // Site info & current status
-            Constraint::Length(5),  // Statistics
+            Constraint::Length(info_height), // Site info & current status
+            Constraint::Length(stats_height), // Statistics
             Constraint::Min(10),    // Chart
             Constraint::Length(8),  // Recent checks
             Constraint::Length(1),  // Status bar
@@ -29,8 +56,8 @@ pub fn render_detail(frame: &mut Frame, app: &App, site_name: &str) {
     } else {
         vec![
             Constraint::Length(3),  // Header
-            Constraint::Length(8),  // Site info & current status
-            Constraint::Length(5),  // Statistics
+            Constraint::Length(info_height), // Site info & current status
+            Constraint::Length(stats_height), // Statistics
             Constraint::Min(10),    // Chart
             Constraint::Length(8),  // Recent checks
             Constraint::Length(1),  // Status bar
@@ -46,11 +73,11 @@ pub fn render_detail(frame: &mut Frame, app: &App, site_name: &str) {
     render_header(frame, app, site_name, chunks[0]);
 
     if let Some(history) = app.sites.get(site_name) {
-        let site_config = app.config.sites.iter().find(|s| &s.name == site_name);
+        let site_config = app.config.site(site_name);
         if let Some(config) = site_config {
             render_site_info(frame, app, config, history, chunks[1]);
-            render_statistics(frame, app, history, chunks[2]);
-            render_chart(frame, app, history, chunks[3]);
+            render_statistics(frame, app, config, history, chunks[2]);
+            render_chart(frame, app, site_name, history, chunks[3]);
             render_recent_checks(frame, app, history, chunks[4]);
         }
     }
@@ -68,7 +95,16 @@ pub fn render_detail(frame: &mut Frame, app: &App, site_name: &str) {
 /// Render the header
 fn render_header(frame: &mut Frame, app: &App, site_name: &str, area: Rect) {
     let theme = &app.theme;
-    let header_text = format!("Site Details: {}          Press ESC to return", site_name);
+    let header_text = match (app.carousel_position(), app.carousel.as_ref().map(|c| c.next_at)) {
+        (Some((position, total)), Some(next_at)) => {
+            let timer = match next_at {
+                Some(at) => format!("next in {}s", at.saturating_duration_since(Instant::now()).as_secs() + 1),
+                None => "held".to_string(),
+            };
+            format!("Site Details: {}          Carousel {}/{}, {}", site_name, position, total, timer)
+        }
+        _ => format!("Site Details: {}          Press ESC to return", site_name),
+    };
 
     let header = Paragraph::new(header_text)
         .block(Block::default().borders(Borders::ALL))
@@ -88,13 +124,23 @@ fn render_site_info(
     let theme = &app.theme;
     let latest = history.latest();
 
+    let blocked_by = app
+        .blocked_by(&config.name)
+        .filter(|_| latest.is_some_and(|r| r.status != Status::Up));
+
     // Determine status color and text
-    let (status_color, status_text) = if let Some(result) = latest {
+    let (status_color, status_text) = if app.stale_since(&config.name).is_some() {
+        (theme.status_unknown, "STALE")
+    } else if blocked_by.is_some() {
+        (theme.status_unknown, "BLOCKED")
+    } else if let Some(result) = latest {
         let color = theme.status_color(&result.status);
         let text = match result.status {
             Status::Up => "UP",
             Status::Down => "DOWN",
             Status::Warning => "WARNING",
+            Status::Skipped => "SKIPPED",
+            Status::RateLimited => "RATE LIMITED",
         };
         (color, text)
     } else {
@@ -114,28 +160,94 @@ fn render_site_info(
         .and_then(|r| r.http_status)
         .map(|s| s.to_string())
         .unwrap_or_else(|| "N/A".to_string());
+    let http_status_color = latest
+        .and_then(|r| r.http_status)
+        .and_then(|code| config.code_color(code))
+        .unwrap_or(theme.text_secondary);
+
+    let streak = streak_text(history).map(|s| format!("  {}", s)).unwrap_or_default();
+
+    let failure_progress = match blocked_by {
+        _ if app.site_paused(&config.name) => "  (checks paused, p resumes them)".to_string(),
+        Some(dependency) => format!("  ({} is down, alerts suppressed)", dependency),
+        None => app
+            .failure_progress(&config.name)
+            .map(|(failures, threshold)| format!("  (failing {}/{} checks before alerting)", failures, threshold))
+            .unwrap_or_default(),
+    };
 
-    let check_interval = config
-        .check_interval
-        .map(|i| format!("{}s", i))
-        .unwrap_or_else(|| "default".to_string());
+    let check_interval = match (&config.schedule, app.site_interval(&config.name)) {
+        (Some(schedule), _) => format!("cron {}", schedule),
+        (None, Some((interval, true))) => match config.check_interval {
+            Some(i) => format!("{}s (set with +/-, configured {}s)", interval.as_secs(), i),
+            None => format!("{}s (set with +/-, configured default)", interval.as_secs()),
+        },
+        (None, _) => match config.check_interval {
+            Some(i) => format!("{}s", i),
+            None => format!("default ({}s)", app.config.settings.refresh_interval),
+        },
+    };
 
-    let lines = vec![
+    let (tls_separator, tls_label, tls) = match latest.and_then(|r| r.tls.as_ref()) {
+        Some(info) => ("  |  ", "TLS: ", format!("{} {}", info.version, info.cipher)),
+        None => ("", "", String::new()),
+    };
+    let (offset_separator, offset_label, offset) = match latest.and_then(|r| r.clock_offset_ms) {
+        Some(ms) => ("  |  ", "Clock Offset: ", format!("{:+}ms", ms)),
+        None => ("", "", String::new()),
+    };
+
+    let mut config_line = vec![
+        Span::styled("Expected Status: ", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
+        Span::styled(config.expected_status.to_string(), Style::default().fg(theme.text_secondary)),
+        Span::styled("  |  ", Style::default().fg(theme.text_muted)),
+        Span::styled("Check Interval: ", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
+        Span::styled(check_interval, Style::default().fg(theme.text_secondary)),
+    ];
+    let tags = app.site_tags(&config.name);
+    if !tags.is_empty() {
+        config_line.push(Span::styled("  |  ", Style::default().fg(theme.text_muted)));
+        config_line.push(Span::styled("Tags: ", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)));
+        config_line.push(Span::styled(tags.join(", "), Style::default().fg(theme.text_secondary)));
+    }
+
+    let mut lines = vec![
         Line::from(vec![
             Span::styled("URL: ", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
-            Span::styled(&config.url, Style::default().fg(theme.text_secondary)),
-        ]),
-        Line::from(vec![
-            Span::styled("Expected Status: ", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
-            Span::styled(config.expected_status.to_string(), Style::default().fg(theme.text_secondary)),
-            Span::styled("  |  ", Style::default().fg(theme.text_muted)),
-            Span::styled("Check Interval: ", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
-            Span::styled(check_interval, Style::default().fg(theme.text_secondary)),
+            Span::styled(config.target(), Style::default().fg(theme.text_secondary)),
         ]),
+        Line::from(config_line),
+    ];
+
+    if let Some(description) = &config.description {
+        lines.push(Line::from(vec![
+            Span::styled("Description: ", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
+            Span::styled(description.as_str(), Style::default().fg(theme.text_secondary)),
+        ]));
+    }
+    let mut ownership = Vec::new();
+    if let Some(owner) = &config.owner {
+        ownership.push(Span::styled("Owner: ", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)));
+        ownership.push(Span::styled(owner.as_str(), Style::default().fg(theme.text_secondary)));
+    }
+    if let Some(runbook) = &config.runbook_url {
+        if !ownership.is_empty() {
+            ownership.push(Span::styled("  |  ", Style::default().fg(theme.text_muted)));
+        }
+        ownership.push(Span::styled("Runbook: ", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)));
+        ownership.push(Span::styled(runbook.as_str(), Style::default().fg(theme.text_secondary)));
+    }
+    if !ownership.is_empty() {
+        lines.push(Line::from(ownership));
+    }
+
+    lines.extend([
         Line::from(""),
         Line::from(vec![
             Span::styled("Current Status: ", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
             Span::styled(status_text, Style::default().fg(status_color).add_modifier(Modifier::BOLD)),
+            Span::styled(streak, Style::default().fg(theme.text_muted)),
+            Span::styled(failure_progress, Style::default().fg(theme.text_muted)),
         ]),
         Line::from(vec![
             Span::styled("Last Checked: ", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
@@ -146,9 +258,59 @@ fn render_site_info(
             Span::styled(response_time, Style::default().fg(theme.text_secondary)),
             Span::styled("  |  ", Style::default().fg(theme.text_muted)),
             Span::styled("HTTP Status: ", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
-            Span::styled(http_status, Style::default().fg(theme.text_secondary)),
+            Span::styled(http_status, Style::default().fg(http_status_color)),
+            Span::styled(tls_separator, Style::default().fg(theme.text_muted)),
+            Span::styled(tls_label, Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
+            Span::styled(tls, Style::default().fg(theme.text_secondary)),
+            Span::styled(offset_separator, Style::default().fg(theme.text_muted)),
+            Span::styled(offset_label, Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
+            Span::styled(offset, Style::default().fg(theme.text_secondary)),
         ]),
-    ];
+    ]);
+
+    if let Some(revocation) = latest.and_then(|r| r.tls.as_ref()).and_then(|tls| tls.revocation.as_ref()) {
+        let color = match revocation {
+            Revocation::Good { .. } => theme.status_up,
+            Revocation::Revoked { .. } => theme.status_down,
+            Revocation::Unverifiable(_) => theme.status_warning,
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Certificate: ", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
+            Span::styled(revocation.describe(), Style::default().fg(color)),
+        ]));
+    }
+
+    // Each egress profile's latest result, to tell "down for me" from "down for everyone"
+    if !config.egress.is_empty() {
+        let mut spans = vec![Span::styled(
+            "Locations: ",
+            Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD),
+        )];
+        for name in &config.egress {
+            let location = latest.and_then(|r| r.locations.iter().find(|(n, _)| n == name)).map(|(_, r)| r);
+            let color = location.map(|r| theme.status_color(&r.status)).unwrap_or(theme.status_unknown);
+            let detail = match location {
+                // Full errors are in Recent Checks; keep this line short
+                Some(r) => r
+                    .response_time_ms
+                    .map(|ms| format!("{}ms", ms))
+                    .unwrap_or_else(|| "failed".to_string()),
+                None => "--".to_string(),
+            };
+            spans.push(Span::styled("● ", Style::default().fg(color)));
+            spans.push(Span::styled(format!("{} {}   ", name, detail), Style::default().fg(theme.text_secondary)));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    // What the diagnostics found when the current outage began
+    for (i, finding) in history.current_diagnostics().unwrap_or_default().iter().enumerate() {
+        let label = if i == 0 { "Diagnostics: " } else { "             " };
+        lines.push(Line::from(vec![
+            Span::styled(label, Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
+            Span::styled(finding.as_str(), Style::default().fg(theme.text_secondary)),
+        ]));
+    }
 
     let paragraph = Paragraph::new(lines).block(
         Block::default()
@@ -161,14 +323,13 @@ fn render_site_info(
 }
 
 /// Render statistics
-fn render_statistics(frame: &mut Frame, app: &App, history: &SiteHistory, area: Rect) {
+fn render_statistics(frame: &mut Frame, app: &App, config: &SiteConfig, history: &SiteHistory, area: Rect) {
     let theme = &app.theme;
 
-    let uptime = if !history.is_empty() {
-        format!("{:.1}%", history.uptime_percentage())
-    } else {
-        "N/A".to_string()
-    };
+    let uptime = history
+        .uptime(app.uptime_window)
+        .map(|uptime| format!("{:.1}%", uptime))
+        .unwrap_or_else(|| "N/A".to_string());
 
     let avg_response = history
         .avg_response_time()
@@ -186,15 +347,20 @@ fn render_statistics(frame: &mut Frame, app: &App, history: &SiteHistory, area:
         .unwrap_or_else(|| "N/A".to_string());
 
     let total_checks = history.len();
+    // Told apart from connection failures, which the status codes lump in as "no response"
+    let timeouts = app.session.sites.get(&config.name).map_or(0, |t| t.timeouts);
+    let timeout_color = if timeouts > 0 { theme.status_warning } else { theme.text_primary };
 
-    let lines = vec![
+    let mut lines = vec![
         Line::from(vec![Span::styled(
             format!("Statistics (Last {} checks)", total_checks),
             Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  Uptime: ", Style::default().fg(theme.text_secondary)),
+            Span::styled(format!("  Uptime ({}): ", app.uptime_window.label()), Style::default().fg(theme.text_secondary)),
             Span::styled(uptime, Style::default().fg(theme.status_up)),
+            Span::styled("  |  Timeouts since start: ", Style::default().fg(theme.text_secondary)),
+            Span::styled(timeouts.to_string(), Style::default().fg(timeout_color)),
         ]),
         Line::from(vec![
             Span::styled("  Response Times: ", Style::default().fg(theme.text_secondary)),
@@ -205,6 +371,31 @@ fn render_statistics(frame: &mut Frame, app: &App, history: &SiteHistory, area:
         ]),
     ];
 
+    // How often each code came back, to catch intermittent errors between glances
+    let codes = history.status_codes(app.uptime_window);
+    if !codes.is_empty() {
+        let mut spans = vec![Span::styled(
+            format!("  Status Codes ({}): ", app.uptime_window.label()),
+            Style::default().fg(theme.text_secondary),
+        )];
+        for (i, (code, count)) in codes.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(", ", Style::default().fg(theme.text_muted)));
+            }
+            let (label, color) = match *code {
+                Some(code) => (code.to_string(), config.code_color(code).unwrap_or(match code {
+                    200..=299 => theme.status_up,
+                    400..=499 => theme.status_warning,
+                    500.. => theme.status_down,
+                    _ => theme.text_primary,
+                })),
+                None => ("no response".to_string(), theme.status_down),
+            };
+            spans.push(Span::styled(format!("{} ×{}", label, count), Style::default().fg(color)));
+        }
+        lines.push(Line::from(spans));
+    }
+
     let paragraph = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
@@ -215,11 +406,26 @@ fn render_statistics(frame: &mut Frame, app: &App, history: &SiteHistory, area:
 }
 
 /// Render response time chart
-fn render_chart(frame: &mut Frame, app: &App, history: &SiteHistory, area: Rect) {
+fn render_chart(frame: &mut Frame, app: &App, site_name: &str, history: &SiteHistory, area: Rect) {
     let theme = &app.theme;
-    let chart_data = history.chart_data();
+    let style = &app.config.settings.chart;
+    // Braille markers give two dots per cell, so more points than that are wasted
+    let (marker, dots_per_cell) = match style.marker {
+        ChartMarker::Braille => (symbols::Marker::Braille, 2),
+        ChartMarker::Block => (symbols::Marker::Block, 1),
+        ChartMarker::Dot => (symbols::Marker::Dot, 1),
+    };
+    let plot_width = area.width.saturating_sub(2) as usize * dots_per_cell;
 
-    if chart_data.is_empty() {
+    // Response-time lines, plus outage markers when they are their own series
+    let (lines, down_times) = match style.down_checks {
+        DownCheckStyle::Gap => (history.chart_segments(plot_width), Vec::new()),
+        DownCheckStyle::Zero => (vec![history.chart_data(plot_width, true)], Vec::new()),
+        DownCheckStyle::Series => (vec![history.chart_data(plot_width, false)], history.down_times()),
+    };
+    let lines: Vec<Vec<(f64, f64)>> = lines.into_iter().filter(|l| !l.is_empty()).collect();
+
+    if lines.is_empty() && down_times.is_empty() {
         let paragraph = Paragraph::new("No data available for chart")
             .block(
                 Block::default()
@@ -232,29 +438,61 @@ fn render_chart(frame: &mut Frame, app: &App, history: &SiteHistory, area: Rect)
         return;
     }
 
-    // Find bounds for axes
-    let (min_time, max_time) = chart_data
-        .iter()
-        .fold((f64::MAX, f64::MIN), |(min_t, max_t), (t, _)| {
-            (min_t.min(*t), max_t.max(*t))
+    // With terminal graphics, leave the inside blank for the image painted after the frame
+    if app.chart_graphics.get() {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Response Time History")
+            .border_style(Style::default().fg(theme.border_fg));
+        *app.chart_placement.borrow_mut() = Some(ChartPlacement {
+            area: block.inner(area),
+            site: site_name.to_string(),
         });
+        frame.render_widget(block, area);
+        return;
+    }
 
-    let (min_response, max_response) = chart_data
+    // Find bounds for axes
+    let times = lines.iter().flatten().map(|&(t, _)| t).chain(down_times.iter().copied());
+    let (min_time, max_time) = times.fold((f64::MAX, f64::MIN), |(min_t, max_t), t| (min_t.min(t), max_t.max(t)));
+
+    let (min_response, max_response) = lines
         .iter()
-        .fold((f64::MAX, f64::MIN), |(min_r, max_r), (_, r)| {
-            (min_r.min(*r), max_r.max(*r))
-        });
+        .flatten()
+        .fold((f64::MAX, f64::MIN), |(min_r, max_r), &(_, r)| (min_r.min(r), max_r.max(r)));
+    // Only outages so far: any scale will do
+    let (min_response, max_response) = if lines.is_empty() { (0.0, 1.0) } else { (min_response, max_response) };
 
     // Add some padding to the y-axis
     let y_min = (min_response * 0.9).max(0.0);
     let y_max = max_response * 1.1;
 
-    let dataset = Dataset::default()
-        .name("Response Time")
-        .marker(symbols::Marker::Braille)
-        .graph_type(GraphType::Line)
-        .style(Style::default().fg(theme.chart_line))
-        .data(&chart_data);
+    // Only the first segment is named so the legend shows one entry
+    let mut datasets: Vec<Dataset> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let dataset = Dataset::default()
+                .marker(marker)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.chart_line))
+                .data(line);
+            if i == 0 { dataset.name("Response Time") } else { dataset }
+        })
+        .collect();
+
+    // Outages sit along the bottom of the chart
+    let down_points: Vec<(f64, f64)> = down_times.iter().map(|&t| (t, y_min)).collect();
+    if !down_points.is_empty() {
+        datasets.push(
+            Dataset::default()
+                .name("Down")
+                .marker(marker)
+                .graph_type(GraphType::Scatter)
+                .style(Style::default().fg(theme.status_down))
+                .data(&down_points),
+        );
+    }
 
     let x_axis = Axis::default()
         .style(Style::default().fg(theme.chart_axis))
@@ -269,7 +507,7 @@ fn render_chart(frame: &mut Frame, app: &App, history: &SiteHistory, area: Rect)
             Span::styled(format!("{:.0}ms", y_max), Style::default().fg(theme.text_secondary)),
         ]);
 
-    let chart = Chart::new(vec![dataset])
+    let chart = Chart::new(datasets)
         .block(
             Block::default()
                 .borders(Borders::ALL)
@@ -354,7 +592,12 @@ fn render_error_bar(frame: &mut Frame, app: &App, area: Rect) {
 /// Render the footer
 fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
     let theme = &app.theme;
-    let footer = Paragraph::new(" ESC: Back to Dashboard | a: Alerts | r: Refresh | ?/h: Help | q: Quit")
+    let text = if app.carousel.is_some() {
+        t("footer.carousel")
+    } else {
+        t("footer.detail")
+    };
+    let footer = Paragraph::new(format!(" {}", text))
         .style(Style::default().fg(theme.footer_fg));
 
     frame.render_widget(footer, area);
@@ -0,0 +1,266 @@
+use crate::app::App;
+use crate::config::ChartGraphics;
+use crate::export;
+use anyhow::Result;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::{DateTime, Utc};
+use crossterm::{cursor::MoveTo, queue};
+use image::{ColorType, ImageEncoder, codecs::png::PngEncoder};
+use ratatui::{Terminal, backend::CrosstermBackend, layout::Rect};
+use std::fmt::Write as _;
+use std::io::{Stdout, Write};
+
+/// Kitty image id for the chart, so each new image replaces the last
+const KITTY_IMAGE_ID: u32 = 7;
+
+/// Largest base64 payload kitty accepts per escape sequence
+const KITTY_CHUNK: usize = 4096;
+
+/// Cell size in pixels when the terminal doesn't report one
+const DEFAULT_CELL: (u32, u32) = (8, 16);
+
+/// Terminal graphics protocols the chart can be drawn with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Protocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+}
+
+impl Protocol {
+    /// Protocol for the configured setting, if the chart should be an image
+    pub fn resolve(setting: ChartGraphics) -> Option<Self> {
+        match setting {
+            ChartGraphics::Off => None,
+            ChartGraphics::Auto => Self::detect(),
+            ChartGraphics::Kitty => Some(Protocol::Kitty),
+            ChartGraphics::Iterm2 => Some(Protocol::Iterm2),
+            ChartGraphics::Sixel => Some(Protocol::Sixel),
+        }
+    }
+
+    /// Guess the protocol from the environment terminals set
+    fn detect() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+        let term = var("TERM");
+
+        // Multiplexers don't pass graphics through without extra setup
+        if !var("TMUX").is_empty() || term.starts_with("screen") {
+            return None;
+        }
+        if !var("KITTY_WINDOW_ID").is_empty() || term == "xterm-kitty" || term == "xterm-ghostty" {
+            return Some(Protocol::Kitty);
+        }
+        if matches!(var("TERM_PROGRAM").as_str(), "iTerm.app" | "WezTerm") {
+            return Some(Protocol::Iterm2);
+        }
+        if term.starts_with("foot") || term.starts_with("mlterm") || term.contains("sixel") {
+            return Some(Protocol::Sixel);
+        }
+        None
+    }
+}
+
+/// Where the detail view left room for the chart image this frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartPlacement {
+    pub area: Rect,
+    pub site: String,
+}
+
+/// Draws the detail chart as an image over the text frame, sending a new
+/// image only when the placement or the site's history changes
+pub struct ChartPainter {
+    protocol: Protocol,
+    /// What is on screen: placement and the latest result it shows
+    shown: Option<(ChartPlacement, Option<DateTime<Utc>>)>,
+}
+
+impl ChartPainter {
+    pub fn new(protocol: Protocol) -> Self {
+        Self { protocol, shown: None }
+    }
+
+    /// Paint the chart the last frame asked for. Returns true when the frame
+    /// must be drawn again: to wipe a stale image, or to fall back to the text
+    /// chart after the image failed.
+    pub fn paint(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &App) -> Result<bool> {
+        let wanted = app.chart_placement.take().map(|placement| {
+            let latest = app.sites.get(&placement.site).and_then(|h| h.latest()).map(|r| r.timestamp);
+            (placement, latest)
+        });
+        if wanted == self.shown {
+            return Ok(false);
+        }
+
+        // Kitty images float above the text and are deleted by id; other protocols
+        // leave pixels in the cells, which only a full redraw clears
+        if let Some((shown, _)) = self.shown.take() {
+            if self.protocol == Protocol::Kitty {
+                write!(terminal.backend_mut(), "\x1b_Ga=d,d=I,i={},q=2\x1b\\", KITTY_IMAGE_ID)?;
+            } else if wanted.as_ref().is_none_or(|(placement, _)| placement.area != shown.area) {
+                terminal.clear()?;
+                return Ok(true);
+            }
+        }
+
+        let Some((placement, latest)) = wanted else {
+            terminal.backend_mut().flush()?;
+            return Ok(false);
+        };
+        let Some(history) = app.sites.get(&placement.site) else {
+            return Ok(false);
+        };
+
+        let cell = cell_size();
+        let size = (placement.area.width as u32 * cell.0, placement.area.height as u32 * cell.1);
+        let image = match export::render_rgb(history, size).and_then(|rgb| self.encode(&rgb, size, placement.area)) {
+            Ok(image) => image,
+            Err(_) => {
+                // Fall back to the text chart for the rest of the session
+                app.chart_graphics.set(false);
+                return Ok(true);
+            }
+        };
+
+        let backend = terminal.backend_mut();
+        queue!(backend, MoveTo(placement.area.x, placement.area.y))?;
+        backend.write_all(image.as_bytes())?;
+        backend.flush()?;
+        self.shown = Some((placement, latest));
+        Ok(false)
+    }
+
+    /// Escape sequence that draws an RGB image into `area`
+    fn encode(&self, rgb: &[u8], size: (u32, u32), area: Rect) -> Result<String> {
+        match self.protocol {
+            Protocol::Kitty => {
+                let png = BASE64.encode(encode_png(rgb, size)?);
+                let chunks: Vec<&[u8]> = png.as_bytes().chunks(KITTY_CHUNK).collect();
+                let mut out = String::new();
+                for (i, chunk) in chunks.iter().enumerate() {
+                    let more = u8::from(i + 1 < chunks.len());
+                    let chunk = std::str::from_utf8(chunk)?;
+                    if i == 0 {
+                        write!(
+                            out,
+                            "\x1b_Gf=100,a=T,i={},q=2,C=1,c={},r={},m={};{}\x1b\\",
+                            KITTY_IMAGE_ID, area.width, area.height, more, chunk
+                        )?;
+                    } else {
+                        write!(out, "\x1b_Gm={};{}\x1b\\", more, chunk)?;
+                    }
+                }
+                Ok(out)
+            }
+            Protocol::Iterm2 => {
+                let png = encode_png(rgb, size)?;
+                Ok(format!(
+                    "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=0:{}\x07",
+                    png.len(),
+                    area.width,
+                    area.height,
+                    BASE64.encode(&png)
+                ))
+            }
+            Protocol::Sixel => Ok(encode_sixel(rgb, size)),
+        }
+    }
+}
+
+/// Pixel size of one terminal cell
+fn cell_size() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => (
+            (size.width / size.columns) as u32,
+            (size.height / size.rows) as u32,
+        ),
+        _ => DEFAULT_CELL,
+    }
+}
+
+fn encode_png(rgb: &[u8], (width, height): (u32, u32)) -> Result<Vec<u8>> {
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png).write_image(rgb, width, height, ColorType::Rgb8)?;
+    Ok(png)
+}
+
+/// Encode an RGB image as sixels, quantized to a 6x6x6 color cube
+fn encode_sixel(rgb: &[u8], (width, height): (u32, u32)) -> String {
+    let (width, height) = (width as usize, height as usize);
+    let level = |value: u8| (value as usize * 5 + 127) / 255;
+    let indexes: Vec<usize> = rgb
+        .chunks_exact(3)
+        .map(|p| level(p[0]) * 36 + level(p[1]) * 6 + level(p[2]))
+        .collect();
+
+    let mut out = format!("\x1bPq\"1;1;{};{}", width, height);
+    let mut used = [false; 216];
+    for &index in &indexes {
+        used[index] = true;
+    }
+    for (index, _) in used.iter().enumerate().filter(|&(_, &used)| used) {
+        let percent = |component: usize| component * 100 / 5;
+        let _ = write!(
+            out,
+            "#{};2;{};{};{}",
+            index,
+            percent(index / 36),
+            percent(index / 6 % 6),
+            percent(index % 6)
+        );
+    }
+
+    // Each band is six pixel rows; each color present in it is drawn as one
+    // pass over the band, returning to its start ('$') between colors
+    for band in (0..height).step_by(6) {
+        let rows = band..(band + 6).min(height);
+        let mut colors: Vec<usize> = rows
+            .clone()
+            .flat_map(|y| indexes[y * width..(y + 1) * width].iter().copied())
+            .collect();
+        colors.sort_unstable();
+        colors.dedup();
+
+        for (n, &color) in colors.iter().enumerate() {
+            if n > 0 {
+                out.push('$');
+            }
+            let _ = write!(out, "#{}", color);
+            let mut run: Option<(char, usize)> = None;
+            for x in 0..width {
+                let bits = rows
+                    .clone()
+                    .enumerate()
+                    .filter(|&(_, y)| indexes[y * width + x] == color)
+                    .fold(0u8, |bits, (bit, _)| bits | 1 << bit);
+                let sixel = (b'?' + bits) as char;
+                run = match run {
+                    Some((c, count)) if c == sixel => Some((c, count + 1)),
+                    Some((c, count)) => {
+                        push_run(&mut out, c, count);
+                        Some((sixel, 1))
+                    }
+                    None => Some((sixel, 1)),
+                };
+            }
+            if let Some((c, count)) = run {
+                push_run(&mut out, c, count);
+            }
+        }
+        out.push('-');
+    }
+
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Append a run of one sixel character, run-length encoded when shorter
+fn push_run(out: &mut String, sixel: char, count: usize) {
+    if count > 3 {
+        let _ = write!(out, "!{}{}", count, sixel);
+    } else {
+        out.extend(std::iter::repeat_n(sixel, count));
+    }
+}
@@ -1,4 +1,6 @@
 use crate::app::App;
+use crate::config::Config;
+use crate::i18n::t;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -38,206 +40,146 @@ pub fn render_help(frame: &mut Frame, app: &App) {
 /// Render the help content
 fn render_help_content(frame: &mut Frame, app: &App, area: Rect) {
     let theme = &app.theme;
-
-    let lines = vec![
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Monitor TUI - Keyboard Shortcuts",
-            Style::default()
-                .fg(theme.header_fg)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(""),
+    let section = |key: &'static str| {
         Line::from(vec![Span::styled(
-            "Global Commands",
+            t(key),
             Style::default()
                 .fg(theme.status_warning)
                 .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![
-            Span::styled("  q", Style::default().fg(theme.status_up)),
-            Span::styled("         Quit the application", Style::default().fg(theme.text_primary)),
-        ]),
+        )])
+    };
+    let shortcut = |keys: &'static str, key: &'static str| {
         Line::from(vec![
-            Span::styled("  Ctrl+C", Style::default().fg(theme.status_up)),
-            Span::styled("    Exit the application", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  ?  or  h", Style::default().fg(theme.status_up)),
-            Span::styled("  Show this help screen", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  ESC", Style::default().fg(theme.status_up)),
-            Span::styled("       Return to dashboard", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  a", Style::default().fg(theme.status_up)),
-            Span::styled("         View alert history", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  s", Style::default().fg(theme.status_up)),
-            Span::styled("         View server metrics", Style::default().fg(theme.text_primary)),
-        ]),
+            Span::styled(format!("  {:<10}", keys), Style::default().fg(theme.status_up)),
+            Span::styled(t(key), Style::default().fg(theme.text_primary)),
+        ])
+    };
+    let indicator = |symbol: &'static str, label: &'static str, color, key: &'static str| {
         Line::from(vec![
-            Span::styled("  t", Style::default().fg(theme.status_up)),
-            Span::styled("         Cycle theme (Dark/Light/High-Contrast)", Style::default().fg(theme.text_primary)),
-        ]),
+            Span::styled(format!("  {} ", symbol), Style::default().fg(color)),
+            Span::styled(format!("{:<8}", label), Style::default().fg(color)),
+            Span::styled(t(key), Style::default().fg(theme.text_primary)),
+        ])
+    };
+
+    let mut lines = vec![
         Line::from(""),
         Line::from(vec![Span::styled(
-            "Dashboard View",
+            t("help.title"),
             Style::default()
-                .fg(theme.status_warning)
+                .fg(theme.header_fg)
                 .add_modifier(Modifier::BOLD),
         )]),
-        Line::from(vec![
-            Span::styled("  ↑  or  k", Style::default().fg(theme.status_up)),
-            Span::styled("  Navigate up (select previous site)", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  ↓  or  j", Style::default().fg(theme.status_up)),
-            Span::styled("  Navigate down (select next site)", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  Enter", Style::default().fg(theme.status_up)),
-            Span::styled("     Open detail view for selected site", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  r", Style::default().fg(theme.status_up)),
-            Span::styled("         Force refresh all sites immediately", Style::default().fg(theme.text_primary)),
-        ]),
         Line::from(""),
-        Line::from(vec![Span::styled(
-            "Detail View",
-            Style::default()
-                .fg(theme.status_warning)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![
-            Span::styled("  ESC", Style::default().fg(theme.status_up)),
-            Span::styled("       Return to dashboard", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  r", Style::default().fg(theme.status_up)),
-            Span::styled("         Force refresh all sites immediately", Style::default().fg(theme.text_primary)),
-        ]),
+        section("help.section.global"),
+        shortcut("q", "help.quit"),
+        shortcut("Ctrl+C", "help.exit"),
+        shortcut("?  or  h", "help.help"),
+        shortcut("ESC", "help.dashboard"),
+        shortcut("Ctrl+P", "help.palette"),
+        shortcut("a", "help.alerts"),
+        shortcut("s", "help.server"),
+        shortcut("o", "help.overview"),
+        shortcut("b", "help.budgets"),
+        shortcut("t", "help.theme"),
+        shortcut("P", "help.pause"),
+        shortcut("m  /  M", "help.silence"),
+        shortcut("c", "help.carousel"),
+        shortcut("y", "help.copy"),
         Line::from(""),
-        Line::from(vec![Span::styled(
-            "Alert History View",
-            Style::default()
-                .fg(theme.status_warning)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![
-            Span::styled("  ↑  or  k", Style::default().fg(theme.status_up)),
-            Span::styled("  Navigate up (previous alert)", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  ↓  or  j", Style::default().fg(theme.status_up)),
-            Span::styled("  Navigate down (next alert)", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  Enter", Style::default().fg(theme.status_up)),
-            Span::styled("     View details for selected alert", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  ESC", Style::default().fg(theme.status_up)),
-            Span::styled("       Return to dashboard", Style::default().fg(theme.text_primary)),
-        ]),
+        section("help.section.dashboard"),
+        shortcut("↑  or  k", "help.previous_site"),
+        shortcut("↓  or  j", "help.next_site"),
+        shortcut("g  /  G", "help.jump"),
+        shortcut("z", "help.expand"),
+        shortcut("Space", "help.mark"),
+        shortcut("p P R m T + -", "help.bulk"),
+        shortcut("T", "help.tag"),
+        shortcut("K  /  J", "help.move"),
+        shortcut("H", "help.health_sort"),
+        shortcut("f", "help.pin"),
+        shortcut("O", "help.open_url"),
+        shortcut("Enter", "help.open_detail"),
+        shortcut("r", "help.refresh"),
+        shortcut("R", "help.recheck_selected"),
+        shortcut("p", "help.pause_site"),
+        shortcut("+  /  -", "help.interval"),
         Line::from(""),
-        Line::from(vec![Span::styled(
-            "Alert Detail View",
-            Style::default()
-                .fg(theme.status_warning)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![
-            Span::styled("  ESC", Style::default().fg(theme.status_up)),
-            Span::styled("       Return to alert history", Style::default().fg(theme.text_primary)),
-        ]),
+        section("help.section.detail"),
+        shortcut("ESC", "help.dashboard"),
+        shortcut("r", "help.refresh"),
+        shortcut("R", "help.recheck_site"),
+        shortcut("p", "help.pause_site"),
+        shortcut("+  /  -", "help.interval"),
+        shortcut("u", "help.uptime_window"),
+        shortcut("e  /  E", "help.export"),
+        shortcut("←  /  →", "help.carousel_step"),
+        shortcut("Space", "help.carousel_hold"),
         Line::from(""),
-        Line::from(vec![Span::styled(
-            "Server View",
-            Style::default()
-                .fg(theme.status_warning)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![
-            Span::styled("  s", Style::default().fg(theme.status_up)),
-            Span::styled("         Force refresh metrics immediately", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  ↑  or  k", Style::default().fg(theme.status_up)),
-            Span::styled("  Scroll apps up", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  ↓  or  j", Style::default().fg(theme.status_up)),
-            Span::styled("  Scroll apps down", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  ESC", Style::default().fg(theme.status_up)),
-            Span::styled("       Return to dashboard", Style::default().fg(theme.text_primary)),
-        ]),
+        section("help.section.alerts"),
+        shortcut("↑  or  k", "help.previous_alert"),
+        shortcut("↓  or  j", "help.next_alert"),
+        shortcut("Enter", "help.open_alert"),
+        shortcut("A", "help.acknowledge"),
+        shortcut("F", "help.hide_acknowledged"),
+        shortcut("x  /  X", "help.clear_alerts"),
+        shortcut("S", "help.alert_stats"),
+        shortcut("ESC", "help.dashboard"),
         Line::from(""),
-        Line::from(vec![Span::styled(
-            "Status Indicators",
-            Style::default()
-                .fg(theme.status_warning)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![
-            Span::styled("  ● ", Style::default().fg(theme.status_up)),
-            Span::styled("UP  ", Style::default().fg(theme.status_up)),
-            Span::styled("    Site is responding with expected HTTP status", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  ● ", Style::default().fg(theme.status_down)),
-            Span::styled("DOWN", Style::default().fg(theme.status_down)),
-            Span::styled("    Site is not responding or connection failed", Style::default().fg(theme.text_primary)),
-        ]),
-        Line::from(vec![
-            Span::styled("  ● ", Style::default().fg(theme.status_warning)),
-            Span::styled("WARN", Style::default().fg(theme.status_warning)),
-            Span::styled("    Site is responding but with wrong HTTP status", Style::default().fg(theme.text_primary)),
-        ]),
+        section("help.section.alert_detail"),
+        shortcut("ESC", "help.back_to_alerts"),
+        shortcut("A", "help.acknowledge"),
+        shortcut("n", "help.comment"),
         Line::from(""),
+        section("help.section.server"),
+        shortcut("s", "help.refresh_metrics"),
+        shortcut("↑  or  k", "help.scroll_up"),
+        shortcut("↓  or  j", "help.scroll_down"),
+        shortcut("ESC", "help.dashboard"),
+        Line::from(""),
+        section("help.section.status"),
+        indicator("●", "UP", theme.status_up, "help.status_up"),
+        indicator("●", "DOWN", theme.status_down, "help.status_down"),
+        indicator("●", "WARN", theme.status_warning, "help.status_warning"),
+        indicator("♥", "", theme.status_up, "help.health"),
+        indicator("◐", "", theme.text_primary, "help.checking"),
+        Line::from(""),
+        section("help.section.config"),
         Line::from(vec![Span::styled(
-            "Configuration",
-            Style::default()
-                .fg(theme.status_warning)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![Span::styled(
-            "  Config file locations (in priority order):",
+            format!("  {}", t("help.config_paths")),
             Style::default().fg(theme.text_primary),
         )]),
-        Line::from(vec![Span::styled(
-            "    1. ./sites.toml",
-            Style::default().fg(theme.text_secondary),
-        )]),
-        Line::from(vec![Span::styled(
-            "    2. ~/.config/monitor/sites.toml",
-            Style::default().fg(theme.text_secondary),
-        )]),
-        Line::from(vec![Span::styled(
-            "    3. /etc/monitor/sites.toml",
-            Style::default().fg(theme.text_secondary),
-        )]),
+    ];
+
+    for (idx, path) in Config::get_config_paths().iter().enumerate() {
+        let loaded = *path == app.config.source_path;
+        let marker = if loaded { format!("  {}", t("help.loaded")) } else { String::new() };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("    {}. {}", idx + 1, path.display()),
+                Style::default().fg(theme.text_secondary),
+            ),
+            Span::styled(marker, Style::default().fg(theme.status_up)),
+        ]));
+    }
+
+    lines.extend([
         Line::from(""),
         Line::from(""),
         Line::from(vec![Span::styled(
-            "Press ESC to return to dashboard",
+            t("help.close"),
             Style::default()
                 .fg(theme.footer_fg)
                 .add_modifier(Modifier::ITALIC),
         )]),
-    ];
+    ]);
 
     let paragraph = Paragraph::new(lines)
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(theme.header_fg))
-                .title(" Help "),
+                .title(format!(" {} ", t("help.block_title"))),
         )
         .alignment(Alignment::Left)
         .wrap(Wrap { trim: false });
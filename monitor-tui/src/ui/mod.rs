@@ -1,8 +1,72 @@
 pub mod alert_detail;
+pub mod alert_stats;
 pub mod alerts;
+pub mod confirm;
 pub mod dashboard;
 pub mod detail;
+pub mod graphics;
 pub mod help;
+pub mod overview;
+pub mod palette;
 pub mod server;
+pub mod silence;
+pub mod slo;
 pub mod status_bar;
+pub mod tag;
 pub mod theme;
+pub mod title;
+
+use crate::checker::Status;
+use crate::history::SiteHistory;
+use chrono::TimeDelta;
+use ratatui::layout::Rect;
+
+/// How long the site has had its current status, e.g. "up for 12d 4h"
+pub fn streak_text(history: &SiteHistory) -> Option<String> {
+    let (status, duration) = history.current_streak()?;
+    let status = match status {
+        Status::Up => "up",
+        Status::Down => "down",
+        Status::Warning => "warn",
+        Status::Skipped | Status::RateLimited => return None,
+    };
+    Some(format!("{} for {}", status, format_duration(duration)))
+}
+
+/// A duration in its two largest units: "12d 4h", "3h 20m", "8m", "45s"
+pub fn format_duration(duration: TimeDelta) -> String {
+    let secs = duration.num_seconds().max(0);
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// First visible row of a scrolling list: keep the previous offset unless the
+/// selection left the window
+pub fn scroll_offset(previous: usize, selected: Option<usize>, visible_rows: usize, total: usize) -> usize {
+    let max_offset = total.saturating_sub(visible_rows);
+    let offset = match selected {
+        Some(idx) if idx < previous => idx,
+        Some(idx) if idx >= previous + visible_rows => idx + 1 - visible_rows,
+        _ => previous,
+    };
+    offset.min(max_offset)
+}
+
+/// Screen area of the `row`-th visible item of a bordered list, clipped to the list
+pub fn list_item_rect(area: Rect, row: usize, lines_per_item: u16) -> Rect {
+    let inner = area.inner(&ratatui::layout::Margin::new(1, 1));
+    let item = Rect {
+        y: inner.y.saturating_add(row as u16 * lines_per_item),
+        height: lines_per_item,
+        ..inner
+    };
+    item.intersection(inner)
+}
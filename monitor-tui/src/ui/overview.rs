@@ -0,0 +1,270 @@
+use crate::app::App;
+use crate::config::ChartMarker;
+use crate::i18n::t;
+use crate::ui::format_duration;
+use crate::ui::status_bar::render_status_bar;
+use chrono::Local;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    Frame,
+};
+
+/// Sites listed in each ranking
+const RANKING_LENGTH: usize = 10;
+
+/// Percentile drawn above the fleet's average latency
+const LATENCY_PERCENTILE: usize = 95;
+
+/// Render fleet-wide statistics for the session
+pub fn render_overview(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Panels
+            Constraint::Length(1), // Status bar
+            Constraint::Length(1), // Footer
+        ])
+        .split(frame.size());
+
+    render_header(frame, app, chunks[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(12), Constraint::Length(10)])
+        .split(chunks[1]);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(body[0]);
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(7), Constraint::Min(0)])
+        .split(columns[0]);
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(columns[1]);
+
+    render_summary(frame, app, left[0]);
+    render_failure_kinds(frame, app, left[1]);
+    let slowest = app.session.slowest(RANKING_LENGTH);
+    let slowest: Vec<(&str, String)> = slowest.iter().map(|&(name, ms)| (name, format!("{}ms avg", ms))).collect();
+    render_ranking(frame, app, "Slowest Sites", &slowest, right[0]);
+    let alerting = app.session.most_alerting(RANKING_LENGTH);
+    let alerting: Vec<(&str, String)> = alerting
+        .iter()
+        .map(|&(name, count)| (name, format!("{} alert{}", count, if count == 1 { "" } else { "s" })))
+        .collect();
+    render_ranking(frame, app, "Most Alerting Sites", &alerting, right[1]);
+    render_fleet_latency(frame, app, body[1]);
+
+    render_status_bar(frame, app, chunks[2]);
+    render_footer(frame, app, chunks[3]);
+}
+
+fn render_header(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let started = app.session.started;
+    let header_text = format!(
+        "Overview: session since {} ({})          Press ESC to return",
+        started.format("%Y-%m-%d %H:%M:%S"),
+        format_duration(Local::now().signed_duration_since(started))
+    );
+
+    let header = Paragraph::new(header_text)
+        .block(Block::default().borders(Borders::ALL))
+        .style(theme.header_style());
+
+    frame.render_widget(header, area);
+}
+
+fn render_summary(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let session = &app.session;
+    let (up, down, warn, unknown) = app.status_counts();
+    let failed: u64 = session.failure_kinds.values().sum();
+    let alerts: u64 = session.sites.values().map(|t| t.alerts).sum();
+
+    let availability = session.availability();
+    let availability_color = match availability {
+        Some(pct) if pct >= 99.0 => theme.status_up,
+        Some(pct) if pct >= 95.0 => theme.status_warning,
+        Some(_) => theme.status_down,
+        None => theme.text_muted,
+    };
+
+    let row = |label: &'static str, value: String, style: Style| {
+        Line::from(vec![
+            Span::styled(format!(" {:<14}", label), Style::default().fg(theme.text_muted)),
+            Span::styled(value, style),
+        ])
+    };
+    let primary = Style::default().fg(theme.text_primary);
+    let lines = vec![
+        row(
+            "Availability:",
+            availability.map_or("--".to_string(), |pct| format!("{:.2}%", pct)),
+            Style::default().fg(availability_color).add_modifier(Modifier::BOLD),
+        ),
+        row(
+            "Checks:",
+            format!("{} ({} failed, {} timed out)", session.total_checks(), failed, session.total_timeouts()),
+            primary,
+        ),
+        row(
+            "Sites:",
+            format!("{} ({} up, {} down, {} warn, {} unknown)", app.sites.len(), up, down, warn, unknown),
+            primary,
+        ),
+        row("Alerts:", alerts.to_string(), primary),
+    ];
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Fleet")
+            .border_style(Style::default().fg(theme.border_fg)),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+fn render_failure_kinds(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let kinds = app.session.failure_breakdown();
+    let total: u64 = kinds.iter().map(|(_, n)| n).sum();
+
+    let lines: Vec<Line> = if kinds.is_empty() {
+        vec![Line::from(Span::styled(" No failed checks", Style::default().fg(theme.text_muted)))]
+    } else {
+        kinds
+            .iter()
+            .map(|&(kind, count)| {
+                Line::from(vec![
+                    Span::styled(format!(" {:<18}", kind), Style::default().fg(theme.text_primary)),
+                    Span::styled(format!("{:>6}", count), Style::default().fg(theme.status_down)),
+                    Span::styled(
+                        format!("  {:>5.1}%", count as f64 / total as f64 * 100.0),
+                        Style::default().fg(theme.text_secondary),
+                    ),
+                ])
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Failures by Kind")
+            .border_style(Style::default().fg(theme.border_fg)),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+/// A titled panel of sites with a value each, in the order given
+fn render_ranking(frame: &mut Frame, app: &App, title: &str, rows: &[(&str, String)], area: Rect) {
+    let theme = &app.theme;
+    let lines: Vec<Line> = if rows.is_empty() {
+        vec![Line::from(Span::styled(" No data yet", Style::default().fg(theme.text_muted)))]
+    } else {
+        rows.iter()
+            .enumerate()
+            .map(|(i, (name, value))| {
+                Line::from(vec![
+                    Span::styled(format!(" {:>2}. ", i + 1), Style::default().fg(theme.text_muted)),
+                    Span::styled(format!("{:<24}", name), Style::default().fg(theme.text_primary)),
+                    Span::styled(value.clone(), Style::default().fg(theme.text_secondary)),
+                ])
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title.to_string())
+            .border_style(Style::default().fg(theme.border_fg)),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+/// Average and percentile response time of all Up sites, minute by minute,
+/// so slowdowns hitting every site at once stand out
+fn render_fleet_latency(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let buckets = &app.session.fleet_latency;
+    let title = format!("Fleet Latency (average and p{} of Up sites, per minute)", LATENCY_PERCENTILE);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(theme.border_fg));
+
+    let (Some(first), Some(last)) = (buckets.front(), buckets.back()) else {
+        let paragraph = Paragraph::new(" No data yet").block(block).style(Style::default().fg(theme.text_muted));
+        frame.render_widget(paragraph, area);
+        return;
+    };
+
+    let x = |minute: chrono::DateTime<Local>| (minute - first.minute).num_minutes() as f64;
+    let average: Vec<(f64, f64)> = buckets.iter().map(|b| (x(b.minute), b.average() as f64)).collect();
+    let percentile: Vec<(f64, f64)> = buckets
+        .iter()
+        .map(|b| (x(b.minute), b.percentile(LATENCY_PERCENTILE) as f64))
+        .collect();
+    let y_max = percentile.iter().map(|&(_, ms)| ms).fold(1.0, f64::max) * 1.1;
+    // A single minute still gets a width to draw across
+    let x_max = x(last.minute).max(1.0);
+
+    let marker = match app.config.settings.chart.marker {
+        ChartMarker::Braille => symbols::Marker::Braille,
+        ChartMarker::Block => symbols::Marker::Block,
+        ChartMarker::Dot => symbols::Marker::Dot,
+    };
+    let percentile_name = format!("p{}", LATENCY_PERCENTILE);
+    let datasets = vec![
+        Dataset::default()
+            .name(percentile_name)
+            .marker(marker)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.status_warning))
+            .data(&percentile),
+        Dataset::default()
+            .name("Average")
+            .marker(marker)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.chart_line))
+            .data(&average),
+    ];
+
+    let label = |text: String| Span::styled(text, Style::default().fg(theme.text_secondary));
+    let x_axis = Axis::default()
+        .style(Style::default().fg(theme.chart_axis))
+        .bounds([0.0, x_max])
+        .labels(vec![
+            label(first.minute.format("%H:%M").to_string()),
+            label(last.minute.format("%H:%M").to_string()),
+        ]);
+    let y_axis = Axis::default()
+        .style(Style::default().fg(theme.chart_axis))
+        .bounds([0.0, y_max])
+        .labels(vec![label("0ms".to_string()), label(format!("{:.0}ms", y_max))]);
+
+    // The panel is short, so let the legend take more of its height than the default
+    let chart = Chart::new(datasets)
+        .block(block)
+        .x_axis(x_axis)
+        .y_axis(y_axis)
+        .hidden_legend_constraints((Constraint::Ratio(1, 4), Constraint::Ratio(1, 1)));
+    frame.render_widget(chart, area);
+}
+
+fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let footer = Paragraph::new(format!(" {}", t("footer.overview")))
+        .style(Style::default().fg(theme.footer_fg));
+    frame.render_widget(footer, area);
+}
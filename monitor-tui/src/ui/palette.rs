@@ -0,0 +1,65 @@
+use crate::app::App;
+use crate::i18n::t;
+use crate::palette::{Palette, MAX_SHOWN};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Width of the palette box, in columns
+const WIDTH: u16 = 60;
+
+/// Draw the command palette over the current view, near the top like an editor's
+pub fn render_palette(frame: &mut Frame, app: &App, palette: &Palette) {
+    let theme = &app.theme;
+    let matches = palette.matches(app);
+    let selected = palette.selected.min(matches.len().saturating_sub(1));
+
+    let screen = frame.size();
+    let width = WIDTH.min(screen.width);
+    // Query line, a line per command (or the no-match note), and the borders
+    let height = (matches.len().max(1) as u16 + 3).min(screen.height);
+    let area = Rect {
+        x: screen.x + (screen.width - width) / 2,
+        y: screen.y + screen.height.saturating_sub(height).min(2),
+        width,
+        height,
+    };
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled("> ", Style::default().fg(theme.header_fg)),
+        Span::styled(palette.query.as_str(), Style::default().fg(theme.text_primary)),
+        Span::styled("█", Style::default().fg(theme.text_muted)),
+    ])];
+    if matches.is_empty() {
+        lines.push(Line::from(Span::styled("  No matching commands", Style::default().fg(theme.text_muted))));
+    }
+    let inner = width.saturating_sub(2) as usize;
+    for (i, command) in matches.iter().enumerate() {
+        let gap = inner.saturating_sub(command.name.chars().count() + command.keys.chars().count() + 3);
+        let style = if i == selected { theme.selection_style() } else { Style::default() };
+        lines.push(
+            Line::from(vec![
+                Span::styled(format!(" {}", command.name), Style::default().fg(theme.text_primary)),
+                Span::raw(" ".repeat(gap)),
+                Span::styled(format!(" {} ", command.keys), Style::default().fg(theme.text_muted)),
+            ])
+            .style(style),
+        );
+    }
+
+    let title = if matches.len() == MAX_SHOWN { " Commands (type to narrow) " } else { " Commands " };
+    let paragraph = Paragraph::new(lines).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_bottom(Line::from(format!(" {} ", t("footer.palette"))).style(Style::default().fg(theme.footer_fg)))
+            .border_style(Style::default().fg(theme.header_fg).add_modifier(Modifier::BOLD)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
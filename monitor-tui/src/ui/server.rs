@@ -1,4 +1,5 @@
 use crate::app::App;
+use crate::i18n::t;
 use crate::metrics_poller::MetricsSnapshot;
 use crate::ui::status_bar::render_status_bar;
 use chrono::Utc;
@@ -47,28 +48,28 @@ fn render_header(frame: &mut Frame, app: &App, area: Rect) {
         Span::styled(url.to_string(), Style::default().fg(theme.text_secondary)),
     ];
 
-    if let Some(snapshot) = &app.server_metrics {
-        if let Ok(ts) = chrono::DateTime::parse_from_rfc3339(&snapshot.collected_at) {
-            let local_time = ts
-                .with_timezone(&chrono::Local)
-                .format("%H:%M:%S")
-                .to_string();
-            spans.push(Span::styled("  Last: ", Style::default().fg(theme.text_muted)));
-            spans.push(Span::styled(
-                local_time,
-                Style::default().fg(theme.text_primary),
-            ));
-
-            if let Some(config) = &app.config.server_metrics {
-                let age = Utc::now().signed_duration_since(ts.with_timezone(&Utc));
-                if age.num_seconds() > (config.poll_interval * 2) as i64 {
-                    spans.push(Span::styled(
-                        "  STALE",
-                        Style::default()
-                            .fg(theme.status_warning)
-                            .add_modifier(Modifier::BOLD),
-                    ));
-                }
+    if let Some(snapshot) = &app.server_metrics
+        && let Ok(ts) = chrono::DateTime::parse_from_rfc3339(&snapshot.collected_at)
+    {
+        let local_time = ts
+            .with_timezone(&chrono::Local)
+            .format("%H:%M:%S")
+            .to_string();
+        spans.push(Span::styled("  Last: ", Style::default().fg(theme.text_muted)));
+        spans.push(Span::styled(
+            local_time,
+            Style::default().fg(theme.text_primary),
+        ));
+
+        if let Some(config) = &app.config.server_metrics {
+            let age = Utc::now().signed_duration_since(ts.with_timezone(&Utc));
+            if age.num_seconds() > (config.poll_interval * 2) as i64 {
+                spans.push(Span::styled(
+                    "  STALE",
+                    Style::default()
+                        .fg(theme.status_warning)
+                        .add_modifier(Modifier::BOLD),
+                ));
             }
         }
     }
@@ -303,7 +304,7 @@ fn render_apps_panel(frame: &mut Frame, app: &App, area: Rect, snapshot: &Metric
 fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
     let theme = &app.theme;
     let footer =
-        Paragraph::new(" s: Refresh | ↑↓/jk: Scroll | ESC: Dashboard | q: Quit")
+        Paragraph::new(format!(" {}", t("footer.server")))
             .style(Style::default().fg(theme.footer_fg));
     frame.render_widget(footer, area);
 }
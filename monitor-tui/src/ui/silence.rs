@@ -0,0 +1,69 @@
+use crate::app::App;
+use crate::i18n::t;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Widest the dialog gets, in columns
+const MAX_WIDTH: u16 = 96;
+
+/// Active silences listed under the input
+const MAX_LISTED: usize = 8;
+
+/// Draw the silence being typed centered over the current view, with the
+/// silences already active under it
+pub fn render_silence_input(frame: &mut Frame, app: &App, input: &str) {
+    let theme = &app.theme;
+    let active = app.active_silences();
+    let listed = active.len().min(MAX_LISTED);
+
+    let hint = format!(" {}", t("footer.silence"));
+    let screen = frame.size();
+    let width = MAX_WIDTH.min(screen.width);
+    // Borders, input, the hint as wrapped, and a heading plus one line per listed silence
+    let hint_lines = (hint.chars().count() as u16).div_ceil(width.saturating_sub(2).max(1));
+    let height = (3 + hint_lines + if listed > 0 { 2 + listed as u16 } else { 0 }).min(screen.height);
+    let area = Rect {
+        x: screen.x + (screen.width - width) / 2,
+        y: screen.y + (screen.height - height) / 3,
+        width,
+        height,
+    };
+
+    let muted = Style::default().fg(theme.text_muted);
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(" > ", Style::default().fg(theme.status_warning).add_modifier(Modifier::BOLD)),
+            Span::styled(input, Style::default().fg(theme.text_primary)),
+            Span::styled("█", muted),
+        ]),
+        Line::from(Span::styled(hint, muted)),
+    ];
+    if listed > 0 {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            " Active silences:",
+            Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD),
+        )));
+        for silence in active.iter().take(MAX_LISTED) {
+            lines.push(Line::from(Span::styled(
+                format!("   {}", silence.describe()),
+                Style::default().fg(theme.text_secondary),
+            )));
+        }
+    }
+
+    let dialog = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Silence alerts ")
+            .border_style(Style::default().fg(theme.status_warning).add_modifier(Modifier::BOLD)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(dialog, area);
+}
@@ -0,0 +1,131 @@
+use crate::app::App;
+use crate::i18n::t;
+use crate::slo::ErrorBudget;
+use crate::ui::format_duration;
+use crate::ui::status_bar::render_status_bar;
+use crate::ui::theme::ResponsiveLayout;
+use chrono::{TimeDelta, Utc};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+/// Burn rate that spends a month's budget in about two days
+const FAST_BURN_RATE: f64 = 14.4;
+
+/// Render the error budget of each site with an SLO target
+pub fn render_slo(frame: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Budgets
+            Constraint::Length(1), // Status bar
+            Constraint::Length(1), // Footer
+        ])
+        .split(frame.size());
+
+    render_header(frame, app, chunks[0]);
+    render_budgets(frame, app, chunks[1]);
+    render_status_bar(frame, app, chunks[2]);
+    render_footer(frame, app, chunks[3]);
+}
+
+fn render_header(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let header_text = format!(
+        "Error Budgets: {}          Press ESC to return",
+        Utc::now().format("%B %Y (UTC)")
+    );
+
+    let header = Paragraph::new(header_text)
+        .block(Block::default().borders(Borders::ALL))
+        .style(theme.header_style());
+
+    frame.render_widget(header, area);
+}
+
+fn render_budgets(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let now = Utc::now();
+
+    // Sites closest to missing their target first
+    let mut budgets: Vec<(&str, ErrorBudget)> = app
+        .sites
+        .iter()
+        .filter_map(|(name, history)| {
+            let target = app.config.site(name)?.slo_target?;
+            Some((name.as_str(), ErrorBudget::new(history, target, now)))
+        })
+        .collect();
+    let left = |b: &ErrorBudget| b.remaining().num_milliseconds() as f64 / b.allowed.num_milliseconds().max(1) as f64;
+    budgets.sort_by(|a, b| left(&a.1).total_cmp(&left(&b.1)).then(a.0.cmp(b.0)));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Sites with an slo_target")
+        .border_style(Style::default().fg(theme.border_fg));
+
+    if budgets.is_empty() {
+        let message = Paragraph::new(" No sites set slo_target. Add e.g. slo_target = 99.9 to a [[sites]] entry.")
+            .block(block)
+            .style(Style::default().fg(theme.text_muted));
+        frame.render_widget(message, area);
+        return;
+    }
+
+    let muted = Style::default().fg(theme.text_muted);
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            " {:<24} {:>8} {:>12} {:>10} {:>10} {:>14} {:>10}",
+            "Site", "Target", "This month", "Budget", "Used", "Remaining", "Burn (1h)"
+        ),
+        muted.add_modifier(Modifier::BOLD),
+    ))];
+
+    for (name, budget) in &budgets {
+        let remaining = budget.remaining();
+        let remaining_style = if remaining <= TimeDelta::zero() {
+            Style::default().fg(theme.status_down).add_modifier(Modifier::BOLD)
+        } else if left(budget) < 0.25 {
+            Style::default().fg(theme.status_warning)
+        } else {
+            Style::default().fg(theme.status_up)
+        };
+        let remaining_text = if remaining <= TimeDelta::zero() {
+            format!("over by {}", format_duration(-remaining))
+        } else {
+            format!("{} ({:.0}%)", format_duration(remaining), left(budget) * 100.0)
+        };
+
+        let (burn_text, burn_style) = match budget.burn_rate {
+            Some(rate) if rate >= FAST_BURN_RATE => (format!("{:.1}x", rate), Style::default().fg(theme.status_down)),
+            Some(rate) if rate > 1.0 => (format!("{:.1}x", rate), Style::default().fg(theme.status_warning)),
+            Some(rate) => (format!("{:.1}x", rate), Style::default().fg(theme.text_primary)),
+            None => ("--".to_string(), muted),
+        };
+
+        let availability = budget.availability.map_or("--".to_string(), |pct| format!("{:.3}%", pct));
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {:<24}", ResponsiveLayout::truncate(name, 24)), Style::default().fg(theme.text_primary)),
+            Span::styled(format!(" {:>7}%", budget.target), Style::default().fg(theme.text_secondary)),
+            Span::styled(format!(" {:>12}", availability), Style::default().fg(theme.text_primary)),
+            Span::styled(format!(" {:>10}", format_duration(budget.allowed)), Style::default().fg(theme.text_secondary)),
+            Span::styled(format!(" {:>10}", format_duration(budget.used)), Style::default().fg(theme.text_primary)),
+            Span::styled(format!(" {:>14}", remaining_text), remaining_style),
+            Span::styled(format!(" {:>10}", burn_text), burn_style),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let footer = Paragraph::new(format!(" {}", t("footer.slo")))
+        .style(Style::default().fg(theme.footer_fg));
+    frame.render_widget(footer, area);
+}
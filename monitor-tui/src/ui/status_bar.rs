@@ -1,5 +1,6 @@
 use crate::app::App;
 use chrono::Local;
+use std::sync::atomic::Ordering;
 use ratatui::{
     layout::Rect,
     style::Style,
@@ -15,7 +16,11 @@ pub fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
 
     let last_update = app.last_update.with_timezone(&Local).format("%H:%M:%S");
 
-    let line = Line::from(vec![
+    let mut spans = Vec::new();
+    if app.paused_since.is_some() {
+        spans.push(Span::styled(" PAUSED ", theme.error_style()));
+    }
+    spans.extend([
         Span::styled(
             format!(" {}/{} UP", up, total),
             Style::default().fg(if up == total {
@@ -52,6 +57,54 @@ pub fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         ),
     ]);
 
+    // Alerts matching these go unnotified, which is easy to forget
+    let silences = app.active_silences().len();
+    if silences > 0 {
+        spans.push(Span::styled("  |  ", Style::default().fg(theme.text_muted)));
+        spans.push(Span::styled(
+            format!("{} silenced", silences),
+            Style::default().fg(theme.status_warning),
+        ));
+    }
+
+    // Likewise sites paused for maintenance that's since finished
+    let paused = app.paused_site_count();
+    if paused > 0 {
+        spans.push(Span::styled("  |  ", Style::default().fg(theme.text_muted)));
+        spans.push(Span::styled(format!("{} paused", paused), Style::default().fg(theme.status_warning)));
+    }
+
+    // Surface result queue overflow so a backlog isn't silent
+    let dropped = app.queue_stats.dropped.load(Ordering::Relaxed);
+    let delayed = app.queue_stats.delayed.load(Ordering::Relaxed);
+    if dropped > 0 || delayed > 0 {
+        spans.push(Span::styled("  |  ", Style::default().fg(theme.text_muted)));
+        let text = match (dropped, delayed) {
+            (0, delayed) => format!("{} results delayed", delayed),
+            (dropped, 0) => format!("{} results dropped", dropped),
+            (dropped, delayed) => format!("{} results dropped, {} delayed", dropped, delayed),
+        };
+        spans.push(Span::styled(text, Style::default().fg(theme.status_warning)));
+    }
+
+    // Notifications that never reached the user
+    let failed = app.notification_stats.failed.load(Ordering::Relaxed);
+    let dropped = app.notification_stats.dropped.load(Ordering::Relaxed);
+    if failed > 0 || dropped > 0 {
+        spans.push(Span::styled("  |  ", Style::default().fg(theme.text_muted)));
+        let mut text = match (failed, dropped) {
+            (failed, 0) => format!("{} notifications failed", failed),
+            (0, dropped) => format!("{} notifications dropped", dropped),
+            (failed, dropped) => format!("{} notifications failed, {} dropped", failed, dropped),
+        };
+        if let Some(error) = app.notification_stats.last_error.lock().unwrap().as_ref() {
+            text.push_str(&format!(" (last: {})", error));
+        }
+        spans.push(Span::styled(text, Style::default().fg(theme.status_warning)));
+    }
+
+    let line = Line::from(spans);
+
     let paragraph = Paragraph::new(line).style(Style::default().fg(theme.text_primary));
 
     frame.render_widget(paragraph, area);
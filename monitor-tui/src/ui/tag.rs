@@ -0,0 +1,58 @@
+use crate::app::App;
+use crate::i18n::t;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Widest the dialog gets, in columns
+const MAX_WIDTH: u16 = 80;
+
+/// Draw the tags being typed centered over the current view, above the
+/// sites they go on
+pub fn render_tag_input(frame: &mut Frame, app: &App, input: &str) {
+    let theme = &app.theme;
+    let targets = app.tag_targets();
+    let target_text = match targets.as_slice() {
+        [site] => format!(" {}: {}", site, app.site_tags(site).join(", ")),
+        _ => format!(" {} marked sites", targets.len()),
+    };
+
+    let hint = format!(" {}", t("footer.tag"));
+    let screen = frame.size();
+    let width = MAX_WIDTH.min(screen.width);
+    // Borders, input, and the sites and hint as wrapped
+    let inner_width = width.saturating_sub(2).max(1);
+    let wrapped = |text: &str| (text.chars().count() as u16).div_ceil(inner_width).max(1);
+    let height = (3 + wrapped(&target_text) + wrapped(&hint)).min(screen.height);
+    let area = Rect {
+        x: screen.x + (screen.width - width) / 2,
+        y: screen.y + (screen.height - height) / 3,
+        width,
+        height,
+    };
+
+    let muted = Style::default().fg(theme.text_muted);
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(" > ", Style::default().fg(theme.status_warning).add_modifier(Modifier::BOLD)),
+            Span::styled(input, Style::default().fg(theme.text_primary)),
+            Span::styled("█", muted),
+        ]),
+        Line::from(Span::styled(target_text, Style::default().fg(theme.text_secondary))),
+        Line::from(Span::styled(hint, muted)),
+    ];
+
+    let dialog = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Tag sites ")
+            .border_style(Style::default().fg(theme.status_warning).add_modifier(Modifier::BOLD)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(dialog, area);
+}
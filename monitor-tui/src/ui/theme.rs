@@ -1,5 +1,5 @@
 use ratatui::style::{Color, Modifier, Style};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::checker::Status;
 
@@ -23,6 +23,19 @@ impl ThemeName {
     }
 }
 
+impl Serialize for ThemeName {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(match self {
+            ThemeName::Dark => "dark",
+            ThemeName::Light => "light",
+            ThemeName::HighContrast => "high-contrast",
+        })
+    }
+}
+
 impl<'de> Deserialize<'de> for ThemeName {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -60,6 +73,7 @@ pub struct Theme {
     pub alert_critical: Color,
     pub alert_warning: Color,
     pub alert_recovery: Color,
+    pub alert_info: Color,
 
     // Chart/graph
     pub chart_line: Color,
@@ -87,6 +101,7 @@ impl Theme {
             alert_critical: Color::Red,
             alert_warning: Color::Yellow,
             alert_recovery: Color::Green,
+            alert_info: Color::Cyan,
             chart_line: Color::Cyan,
             chart_axis: Color::Gray,
             error_fg: Color::Black,
@@ -112,6 +127,7 @@ impl Theme {
             alert_critical: Color::Rgb(255, 100, 100),
             alert_warning: Color::Rgb(255, 200, 100),
             alert_recovery: Color::Rgb(100, 220, 100),
+            alert_info: Color::Rgb(150, 180, 255),
             chart_line: Color::Rgb(150, 180, 255),
             chart_axis: Color::Rgb(120, 120, 140),
             error_fg: Color::Black,
@@ -135,6 +151,7 @@ impl Theme {
             alert_critical: Color::Rgb(255, 0, 0),
             alert_warning: Color::Rgb(255, 255, 0),
             alert_recovery: Color::Rgb(0, 255, 0),
+            alert_info: Color::Rgb(0, 255, 255),
             chart_line: Color::Rgb(0, 255, 255),
             chart_axis: Color::White,
             error_fg: Color::Black,
@@ -156,6 +173,8 @@ impl Theme {
             Status::Up => self.status_up,
             Status::Down => self.status_down,
             Status::Warning => self.status_warning,
+            Status::Skipped => self.status_unknown,
+            Status::RateLimited => self.status_warning,
         }
     }
 
@@ -228,15 +247,9 @@ impl ResponsiveLayout {
         self.width >= 80
     }
 
-    /// Number of lines per list item (for mouse click calculations)
+    /// Number of lines per dashboard list item
     pub fn lines_per_site_item(&self) -> u16 {
-        if self.show_sparkline() {
-            4
-        } else if self.show_detailed_metrics() {
-            3
-        } else {
-            2
-        }
+        2 + self.show_detailed_metrics() as u16 + self.show_sparkline() as u16
     }
 
     /// Truncate string with ellipsis
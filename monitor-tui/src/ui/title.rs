@@ -0,0 +1,64 @@
+use crate::app::App;
+use crossterm::execute;
+use crossterm::terminal::SetTitle;
+use std::io::{stdout, Write};
+
+/// Save the terminal's title on its title stack (xterm and most emulators)
+const PUSH_TITLE: &str = "\x1b[22;0t";
+
+/// Put back the title saved by `PUSH_TITLE`
+const POP_TITLE: &str = "\x1b[23;0t";
+
+/// Keeps the terminal window title (the pane title in tmux) showing a summary
+/// of the fleet, so it can be seen from a tab bar; the old title comes back on drop
+pub struct TerminalTitle {
+    last: String,
+}
+
+impl TerminalTitle {
+    pub fn new() -> anyhow::Result<Self> {
+        let mut out = stdout();
+        out.write_all(PUSH_TITLE.as_bytes())?;
+        out.flush()?;
+        Ok(Self { last: String::new() })
+    }
+
+    /// Set the title if the summary changed since the last call
+    pub fn update(&mut self, app: &App) -> anyhow::Result<()> {
+        let title = summary(app);
+        if title != self.last {
+            execute!(stdout(), SetTitle(&title))?;
+            self.last = title;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for TerminalTitle {
+    fn drop(&mut self) {
+        let mut out = stdout();
+        let _ = out.write_all(POP_TITLE.as_bytes());
+        let _ = out.flush();
+    }
+}
+
+/// e.g. "monitor: 12/14 UP, 2 DOWN"
+fn summary(app: &App) -> String {
+    let (up, down, warn, unknown) = app.status_counts();
+    let total = up + down + warn + unknown;
+    let mut title = if up + down + warn == 0 {
+        format!("monitor: checking {} sites", total)
+    } else {
+        format!("monitor: {}/{} UP", up, total)
+    };
+    if down > 0 {
+        title.push_str(&format!(", {} DOWN", down));
+    }
+    if warn > 0 {
+        title.push_str(&format!(", {} WARN", warn));
+    }
+    if app.paused_since.is_some() {
+        title.push_str(" (paused)");
+    }
+    title
+}
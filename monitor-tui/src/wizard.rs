@@ -0,0 +1,358 @@
+use crate::checker::http::{describe_error, DEFAULT_USER_AGENT};
+use crate::config::Config;
+use anyhow::{Context, Result};
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use futures::StreamExt;
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame, Terminal,
+};
+use std::io::stdout;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use url::Url;
+
+/// How long the test check of each URL gets
+const TEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Suggested check interval, in seconds
+const DEFAULT_INTERVAL: u64 = 60;
+
+/// Questions, in the order they're asked
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Step {
+    Urls,
+    Interval,
+    Desktop,
+    Bell,
+    Path,
+}
+
+/// How a URL's test check went
+enum TestOutcome {
+    Pending,
+    Responded { status: u16, ms: u128 },
+    Failed(String),
+}
+
+struct WizardSite {
+    name: String,
+    url: String,
+    test: TestOutcome,
+}
+
+/// Answers so far and what's being typed
+struct Wizard {
+    step: Step,
+    input: String,
+    sites: Vec<WizardSite>,
+    interval: u64,
+    desktop: bool,
+    bell: bool,
+    path: PathBuf,
+    error: Option<String>,
+}
+
+/// Ask for a few URLs, how often to check them and how to notify, test each
+/// URL live, and write a config file. Returns where it was written, or None
+/// if setup was cancelled.
+pub async fn run() -> Result<Option<PathBuf>> {
+    let _cleanup = crate::TerminalCleanup::new()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    let client = reqwest::Client::builder().user_agent(DEFAULT_USER_AGENT).timeout(TEST_TIMEOUT).build()?;
+    let (test_tx, mut test_rx) = mpsc::channel::<(usize, TestOutcome)>(16);
+    let mut events = EventStream::new();
+
+    let mut wizard = Wizard {
+        step: Step::Urls,
+        input: String::new(),
+        sites: Vec::new(),
+        interval: DEFAULT_INTERVAL,
+        desktop: true,
+        bell: true,
+        path: default_path(),
+        error: None,
+    };
+
+    loop {
+        terminal.draw(|frame| render(frame, &wizard))?;
+        tokio::select! {
+            Some((index, outcome)) = test_rx.recv() => {
+                if let Some(site) = wizard.sites.get_mut(index) {
+                    site.test = outcome;
+                }
+            }
+            event = events.next() => {
+                let Some(Ok(Event::Key(key))) = event else {
+                    continue;
+                };
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match wizard.handle_key(key) {
+                    Action::Continue => {}
+                    Action::Cancel => return Ok(None),
+                    Action::Test(index) => {
+                        let (client, url, tx) = (client.clone(), wizard.sites[index].url.clone(), test_tx.clone());
+                        tokio::spawn(async move {
+                            let _ = tx.send((index, test(&client, &url).await)).await;
+                        });
+                    }
+                    Action::Finish => match wizard.write() {
+                        Ok(()) => return Ok(Some(wizard.path)),
+                        Err(e) => {
+                            wizard.error = Some(format!("{:#}", e));
+                            wizard.input = wizard.path.display().to_string();
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// What a key press asks the wizard loop to do
+enum Action {
+    Continue,
+    Cancel,
+    /// Check the site just added, by index
+    Test(usize),
+    Finish,
+}
+
+impl Wizard {
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Action::Cancel;
+        }
+        self.error = None;
+        match key.code {
+            KeyCode::Esc => return self.back(),
+            KeyCode::Enter => return self.submit(),
+            KeyCode::Backspace => {
+                let erased = self.input.pop();
+                // On an empty line, take back the last URL
+                if erased.is_none() && self.step == Step::Urls {
+                    self.sites.pop();
+                }
+            }
+            KeyCode::Char(c) if matches!(self.step, Step::Desktop | Step::Bell) => match c {
+                'y' | 'Y' | 'n' | 'N' => {
+                    self.input = c.to_lowercase().to_string();
+                    return self.submit();
+                }
+                _ => {}
+            },
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => self.input.push(c),
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Accept the answer to the current question and move on
+    fn submit(&mut self) -> Action {
+        let answer = std::mem::take(&mut self.input);
+        let answer = answer.trim();
+        match self.step {
+            Step::Urls if answer.is_empty() => {
+                if self.sites.is_empty() {
+                    self.error = Some("Add at least one URL".to_string());
+                } else {
+                    self.step = Step::Interval;
+                }
+            }
+            Step::Urls => match self.add_site(answer) {
+                Ok(index) => return Action::Test(index),
+                Err(e) => {
+                    self.error = Some(format!("{:#}", e));
+                    self.input = answer.to_string();
+                }
+            },
+            Step::Interval => match answer {
+                "" => self.step = Step::Desktop,
+                _ => match answer.parse::<u64>() {
+                    Ok(secs) if secs > 0 => {
+                        self.interval = secs;
+                        self.step = Step::Desktop;
+                    }
+                    _ => {
+                        self.error = Some(format!("'{}' isn't a number of seconds", answer));
+                        self.input = answer.to_string();
+                    }
+                },
+            },
+            Step::Desktop => {
+                self.desktop = answer != "n";
+                self.step = Step::Bell;
+            }
+            Step::Bell => {
+                self.bell = answer != "n";
+                self.step = Step::Path;
+                self.input = self.path.display().to_string();
+            }
+            Step::Path => {
+                if answer.is_empty() {
+                    self.error = Some("Type where to write the file".to_string());
+                } else {
+                    self.path = PathBuf::from(answer);
+                    return Action::Finish;
+                }
+            }
+        }
+        Action::Continue
+    }
+
+    /// Go back a question; ESC on the first one cancels setup
+    fn back(&mut self) -> Action {
+        self.input.clear();
+        self.step = match self.step {
+            Step::Urls => return Action::Cancel,
+            Step::Interval => Step::Urls,
+            Step::Desktop => Step::Interval,
+            Step::Bell => Step::Desktop,
+            Step::Path => Step::Bell,
+        };
+        Action::Continue
+    }
+
+    /// Add a URL (https:// is assumed without a scheme), named after its host
+    fn add_site(&mut self, text: &str) -> Result<usize> {
+        let text = if text.contains("://") { text.to_string() } else { format!("https://{}", text) };
+        let url = Url::parse(&text).with_context(|| format!("'{}' isn't a valid URL", text))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            anyhow::bail!("'{}' must start with http:// or https://", text);
+        }
+        if self.sites.iter().any(|site| site.url == url.as_str()) {
+            anyhow::bail!("{} is already added", url);
+        }
+        let mut host = url.host_str().context("The URL needs a host")?.to_string();
+        if let Some(port) = url.port() {
+            host.push_str(&format!(":{}", port));
+        }
+        let mut name = match url.path() {
+            "/" => host,
+            path => format!("{}{}", host, path.trim_end_matches('/')),
+        };
+        // Site names must be unique
+        if self.sites.iter().any(|site| site.name == name) {
+            name = format!("{} ({})", name, self.sites.len() + 1);
+        }
+        self.sites.push(WizardSite { name, url: url.to_string(), test: TestOutcome::Pending });
+        Ok(self.sites.len() - 1)
+    }
+
+    /// The config file the answers make
+    fn config_text(&self) -> String {
+        let quote = |text: &str| toml::Value::String(text.to_string()).to_string();
+        let mut text = String::from("# Written by the monitor-tui setup wizard; sites.toml.example lists every option\n\n");
+        text.push_str("[settings]\n# Global check interval in seconds (can be overridden per site)\n");
+        text.push_str(&format!("refresh_interval = {}\n\n", self.interval));
+        text.push_str("[settings.alerts]\n# Show desktop notifications\n");
+        text.push_str(&format!("desktop_notifications = {}\n", self.desktop));
+        text.push_str("# Ring terminal bell on alerts\n");
+        text.push_str(&format!("terminal_bell = {}\n", self.bell));
+        for site in &self.sites {
+            text.push_str(&format!("\n[[sites]]\nname = {}\nurl = {}\n", quote(&site.name), quote(&site.url)));
+        }
+        text
+    }
+
+    /// Write the config, refusing to replace a file that's there
+    fn write(&self) -> Result<()> {
+        if self.path.exists() {
+            anyhow::bail!("{} already exists", self.path.display());
+        }
+        let text = self.config_text();
+        Config::parse(&text, &self.path).context("The answers don't make a valid config")?;
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        }
+        std::fs::write(&self.path, text).with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+/// The user config location, where a config is found without being pointed at
+fn default_path() -> PathBuf {
+    dirs::config_dir()
+        .map(|dir| dir.join("monitor").join("sites.toml"))
+        .unwrap_or_else(|| PathBuf::from("./sites.toml"))
+}
+
+/// One GET of the URL, the way a check would make it
+async fn test(client: &reqwest::Client, url: &str) -> TestOutcome {
+    let start = Instant::now();
+    match client.get(url).send().await {
+        Ok(response) => TestOutcome::Responded {
+            status: response.status().as_u16(),
+            ms: start.elapsed().as_millis(),
+        },
+        Err(e) => TestOutcome::Failed(describe_error(&e)),
+    }
+}
+
+fn render(frame: &mut Frame, wizard: &Wizard) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(1)])
+        .split(frame.size());
+
+    let header = Paragraph::new("Monitor TUI setup - no configuration found, so let's write one")
+        .block(Block::default().borders(Borders::ALL))
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    frame.render_widget(header, chunks[0]);
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let dim = Style::default().add_modifier(Modifier::DIM);
+    let mut lines = vec![Line::from(Span::styled("Sites", bold))];
+    if wizard.sites.is_empty() {
+        lines.push(Line::from(Span::styled("  none yet", dim)));
+    }
+    for site in &wizard.sites {
+        let (mark, result) = match &site.test {
+            TestOutcome::Pending => (Span::styled("  …  ", dim), "testing".to_string()),
+            TestOutcome::Responded { status, ms } if (200..300).contains(status) => {
+                (Span::styled("  ✓  ", Style::default().fg(Color::Green)), format!("HTTP {} in {}ms", status, ms))
+            }
+            TestOutcome::Responded { status, ms } => (
+                Span::styled("  !  ", Style::default().fg(Color::Yellow)),
+                format!("HTTP {} in {}ms - set expected_status if that's right", status, ms),
+            ),
+            TestOutcome::Failed(error) => (Span::styled("  ✗  ", Style::default().fg(Color::Red)), error.clone()),
+        };
+        lines.push(Line::from(vec![mark, Span::raw(format!("{}  ", site.url)), Span::styled(result, dim)]));
+    }
+    lines.push(Line::from(""));
+
+    let question = match wizard.step {
+        Step::Urls => "Add a URL to monitor (Enter on an empty line when done):".to_string(),
+        Step::Interval => format!("Check each site every how many seconds? [{}]", wizard.interval),
+        Step::Desktop => format!("Show desktop notifications when a site goes down or recovers? [{}]", yes_no(wizard.desktop)),
+        Step::Bell => format!("Ring the terminal bell on alerts? [{}]", yes_no(wizard.bell)),
+        Step::Path => "Write the configuration to:".to_string(),
+    };
+    lines.push(Line::from(Span::styled(question, bold)));
+    lines.push(Line::from(vec![Span::raw("> "), Span::raw(wizard.input.as_str()), Span::styled("█", dim)]));
+    if let Some(error) = &wizard.error {
+        lines.push(Line::from(Span::styled(error.as_str(), Style::default().fg(Color::Red))));
+    }
+
+    let body = Paragraph::new(lines).wrap(Wrap { trim: false }).block(Block::default().borders(Borders::ALL));
+    frame.render_widget(body, chunks[1]);
+
+    let footer = match wizard.step {
+        Step::Urls => " Enter: Add URL / continue | Backspace on an empty line: Remove last | ESC: Cancel",
+        Step::Desktop | Step::Bell => " y/n: Answer | Enter: Default | ESC: Back",
+        _ => " Enter: Continue | ESC: Back",
+    };
+    frame.render_widget(Paragraph::new(footer).style(dim), chunks[2]);
+}
+
+/// The default answer of a yes/no question, capitalized
+fn yes_no(default: bool) -> &'static str {
+    if default { "Y/n" } else { "y/N" }
+}